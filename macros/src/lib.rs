@@ -0,0 +1,341 @@
+//! `#[derive(Validate)]`：为请求 DTO 生成 `backend::api::schema::Validate` 实现。
+//!
+//! 字段规则（可组合，`Option<T>` 字段仅在有值时检查）：
+//! - `trim`：去掉首尾空白；`Option<String>` 去空白后为空视为 `None`
+//! - `trim(keep_empty)`：同 `trim`，但 `Option<String>` 保留空串（PATCH 中空值与省略含义不同）
+//! - `non_blank`：去空白后不能为空
+//! - `length(min = 1, max = 256)`：按字符计数
+//! - `range(min = 1, max = "0xffff")`：非负整数边界（十进制或 0x 十六进制，至多 256 位），
+//!   编译期解析并检查 min <= max；字段类型需实现 `RangeBound`（整数、`U256`、`DbU256`）
+//! - `address`：0x 前缀的 20 字节地址
+//! - `url`：http(s) URL；`uri`：任意带 scheme 的绝对 URI（如 `ipfs://`）
+//! - `custom = "path"`：`fn(&T) -> Result<(), String>`
+//!
+//! 结构体规则：
+//! - `normalize = "path"`：`fn(Self) -> Self`，在字段规则之前执行（分页收敛、去重等）
+//! - `schema = "path"`：`fn(&Self, &mut ValidationError)`，跨字段规则，可出现多次
+//!
+//! 所有违反的规则一次性收集，逐字段返回。
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Expr, ExprLit, Fields, GenericArgument, Lit, Path,
+    PathArguments, Type, meta::ParseNestedMeta, parse_macro_input,
+};
+
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Rule {
+    NonBlank,
+    Length(Option<TokenStream2>, Option<TokenStream2>),
+    Range(Option<Bound>, Option<Bound>),
+    Address,
+    Url,
+    Uri,
+    Custom(Path),
+}
+
+struct FieldRules {
+    ident: syn::Ident,
+    optional: bool,
+    trim: bool,
+    keep_empty: bool,
+    rules: Vec<Rule>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Validate can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Validate requires named fields",
+        ));
+    };
+
+    let mut normalize = None;
+    let mut schemas = Vec::new();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("validate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("normalize") {
+                normalize = Some(parse_path(&meta)?);
+            } else if meta.path.is_ident("schema") {
+                schemas.push(parse_path(&meta)?);
+            } else {
+                return Err(meta.error("expected `normalize` or `schema`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let mut parsed = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.clone().expect("named field");
+        let mut rules = FieldRules {
+            ident,
+            optional: option_inner(&field.ty).is_some(),
+            trim: false,
+            keep_empty: false,
+            rules: Vec::new(),
+        };
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("validate"))
+        {
+            attr.parse_nested_meta(|meta| parse_rule(&meta, &mut rules))?;
+        }
+        parsed.push(rules);
+    }
+
+    let schema = quote!(::backend::api::schema);
+    let rules = quote!(::backend::api::validation);
+    let normalize = normalize.map(|path| quote!(this = #path(this);));
+    let trims = parsed.iter().filter(|f| f.trim).map(|f| {
+        let ident = &f.ident;
+        if f.keep_empty && f.optional {
+            quote!(this.#ident = this.#ident.map(#rules::Trim::trim);)
+        } else {
+            quote!(this.#ident = #rules::Trim::trim(this.#ident);)
+        }
+    });
+    let checks = parsed.iter().filter(|f| !f.rules.is_empty()).map(|f| {
+        let ident = &f.ident;
+        let field = ident.to_string();
+        let value = format_ident!("value");
+        let each = f.rules.iter().map(|rule| {
+            let check = match rule {
+                Rule::NonBlank => quote!(#rules::non_blank(#value)),
+                Rule::Length(min, max) => {
+                    let min = opt(min.clone());
+                    let max = opt(max.clone());
+                    quote!(#rules::length(#value, #min, #max))
+                }
+                Rule::Range(min, max) => {
+                    let bound = |b: &Bound| {
+                        let Bound { limbs, literal } = b;
+                        quote!(#rules::Bound::new([#(#limbs),*], #literal))
+                    };
+                    let min = opt(min.as_ref().map(bound));
+                    let max = opt(max.as_ref().map(bound));
+                    quote!(#rules::range(#value, #min, #max))
+                }
+                Rule::Address => quote!(#rules::address(#value)),
+                Rule::Url => quote!(#rules::url(#value)),
+                Rule::Uri => quote!(#rules::uri(#value)),
+                Rule::Custom(path) => quote!(#path(#value).err()),
+            };
+            quote! {
+                if let ::core::option::Option::Some(message) = #check {
+                    errors.add(#field, message);
+                }
+            }
+        });
+        if f.optional {
+            quote! {
+                if let ::core::option::Option::Some(#value) = &this.#ident {
+                    #(#each)*
+                }
+            }
+        } else {
+            quote! {{
+                let #value = &this.#ident;
+                #(#each)*
+            }}
+        }
+    });
+    let schemas = schemas
+        .iter()
+        .map(|path| quote!(#path(&this, &mut errors);));
+
+    Ok(quote! {
+        impl #impl_generics #schema::Validate for #name #ty_generics #where_clause {
+            type Err = #schema::ValidationError;
+
+            #[allow(unused_mut)]
+            fn validate(self) -> ::core::result::Result<Self, Self::Err> {
+                let mut this = self;
+                #normalize
+                #(#trims)*
+                let mut errors = #schema::ValidationError::default();
+                #(#checks)*
+                #(#schemas)*
+                errors.into_result(this)
+            }
+        }
+    })
+}
+
+fn opt(value: Option<TokenStream2>) -> TokenStream2 {
+    match value {
+        Some(v) => quote!(::core::option::Option::Some(#v)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn parse_rule(meta: &ParseNestedMeta, f: &mut FieldRules) -> syn::Result<()> {
+    let p = &meta.path;
+    if p.is_ident("trim") {
+        f.trim = true;
+        if meta.input.peek(syn::token::Paren) {
+            let mut keep_empty = false;
+            meta.parse_nested_meta(|m| {
+                if m.path.is_ident("keep_empty") {
+                    keep_empty = true;
+                    Ok(())
+                } else {
+                    Err(m.error("expected `keep_empty`"))
+                }
+            })?;
+            f.keep_empty = keep_empty;
+        }
+    } else if p.is_ident("non_blank") {
+        f.rules.push(Rule::NonBlank);
+    } else if p.is_ident("address") {
+        f.rules.push(Rule::Address);
+    } else if p.is_ident("url") {
+        f.rules.push(Rule::Url);
+    } else if p.is_ident("uri") {
+        f.rules.push(Rule::Uri);
+    } else if p.is_ident("custom") {
+        f.rules.push(Rule::Custom(parse_path(meta)?));
+    } else if p.is_ident("length") {
+        let (mut min, mut max) = (None, None);
+        meta.parse_nested_meta(|m| {
+            let expr: Expr = m.value()?.parse()?;
+            if m.path.is_ident("min") {
+                min = Some(quote!(#expr));
+            } else if m.path.is_ident("max") {
+                max = Some(quote!(#expr));
+            } else {
+                return Err(m.error("expected `min` or `max`"));
+            }
+            Ok(())
+        })?;
+        f.rules.push(Rule::Length(min, max));
+    } else if p.is_ident("range") {
+        let (mut min, mut max) = (None, None);
+        meta.parse_nested_meta(|m| {
+            let bound = parse_bound(&m)?;
+            if m.path.is_ident("min") {
+                min = Some(bound);
+            } else if m.path.is_ident("max") {
+                max = Some(bound);
+            } else {
+                return Err(m.error("expected `min` or `max`"));
+            }
+            Ok(())
+        })?;
+        if min.is_none() && max.is_none() {
+            return Err(meta.error("range needs `min` and/or `max`"));
+        }
+        if let (Some(lo), Some(hi)) = (&min, &max)
+            && lo.limbs.iter().rev().gt(hi.limbs.iter().rev())
+        {
+            return Err(meta.error("range `min` must not exceed `max`"));
+        }
+        f.rules.push(Rule::Range(min, max));
+    } else {
+        return Err(meta.error("unknown validation rule"));
+    }
+    Ok(())
+}
+
+/// `custom = "path::to::fn"` 或 `custom = path::to::fn`
+fn parse_path(meta: &ParseNestedMeta) -> syn::Result<Path> {
+    let expr: Expr = meta.value()?.parse()?;
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => s.parse(),
+        Expr::Path(p) => Ok(p.path),
+        other => {
+            Err(syn::Error::new_spanned(other, "expected a function path"))
+        }
+    }
+}
+
+/// range 边界：编译期解析出的 256 位值（小端 u64 limbs）与错误信息中展示的字面量
+struct Bound {
+    limbs: [u64; 4],
+    literal: String,
+}
+
+/// 整数字面量或字符串字面量（十进制 / 0x 十六进制）；非法或超出 256 位时报错在字面量上
+fn parse_bound(meta: &ParseNestedMeta) -> syn::Result<Bound> {
+    let lit: Lit = meta.value()?.parse()?;
+    let (digits, radix, literal) = match &lit {
+        Lit::Int(i) => {
+            let digits = i.base10_digits().to_string();
+            (digits.clone(), 10, digits)
+        }
+        Lit::Str(s) => {
+            let value = s.value();
+            match value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+            {
+                Some(hex) => (hex.to_string(), 16, value),
+                None => (value.clone(), 10, value),
+            }
+        }
+        other => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "range bound must be an integer or string literal",
+            ));
+        }
+    };
+    let limbs = parse_u256(&digits, radix).ok_or_else(|| {
+        syn::Error::new_spanned(
+            &lit,
+            "range bound must be a non-negative integer of at most 256 bits",
+        )
+    })?;
+    Ok(Bound { limbs, literal })
+}
+
+fn parse_u256(digits: &str, radix: u32) -> Option<[u64; 4]> {
+    if digits.is_empty() {
+        return None;
+    }
+    let mut limbs = [0u64; 4];
+    for c in digits.chars() {
+        let mut carry = u128::from(c.to_digit(radix)?);
+        for limb in &mut limbs {
+            let v = u128::from(*limb) * u128::from(radix) + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(limbs)
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(p) = ty else { return None };
+    let last = p.path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(t) => Some(t),
+        _ => None,
+    }
+}
@@ -0,0 +1,25 @@
+-- Organizer show creation requests submitted on chain via ShowManager.createShow.
+-- A job is CONFIRMED once the resulting ShowCreated event has been indexed into `shows`.
+CREATE TYPE SHOW_JOB_STATUS AS ENUM ('PENDING', 'SUBMITTED', 'CONFIRMED', 'FAILED');
+
+CREATE TABLE IF NOT EXISTS show_create_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    signer TEXT NOT NULL,
+    organizer TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    location TEXT NOT NULL,
+    event_time NUMERIC(78,0) NOT NULL,
+    end_time NUMERIC(78,0) NOT NULL,
+    ticket_price NUMERIC(78,0) NOT NULL,
+    max_tickets NUMERIC(78,0) NOT NULL,
+    metadata_uri TEXT NOT NULL DEFAULT '',
+    status SHOW_JOB_STATUS NOT NULL DEFAULT 'PENDING',
+    tx_hash TEXT,
+    show_id NUMERIC(78,0),
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_show_create_jobs_status ON show_create_jobs (status);
+CREATE UNIQUE INDEX idx_show_create_jobs_tx_hash ON show_create_jobs (tx_hash);
@@ -0,0 +1,14 @@
+-- Full-text search and filter/sort indexes for GET /shows
+ALTER TABLE shows
+    ADD COLUMN IF NOT EXISTS search_vector TSVECTOR
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B') ||
+        setweight(to_tsvector('simple', coalesce(location, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_shows_search_vector ON shows USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_shows_ticket_price ON shows (ticket_price);
+CREATE INDEX IF NOT EXISTS idx_shows_sold_tickets ON shows (sold_tickets);
+CREATE INDEX IF NOT EXISTS idx_shows_created_at ON shows (created_at);
+CREATE INDEX IF NOT EXISTS idx_shows_organizer_lower ON shows (lower(organizer));
@@ -0,0 +1,32 @@
+-- TicketManager 事件投影：票据当前状态 + 转移历史
+CREATE TYPE TICKET_STATUS AS ENUM ('VALID', 'USED', 'CANCELLED');
+
+CREATE TABLE IF NOT EXISTS tickets (
+    token_id NUMERIC(78,0) PRIMARY KEY,
+    event_id NUMERIC(78,0) NOT NULL,
+    owner TEXT NOT NULL,
+    seat_number NUMERIC(78,0) NOT NULL,
+    price NUMERIC(78,0) NOT NULL,
+    status TICKET_STATUS NOT NULL DEFAULT 'VALID',
+    minted_tx_hash TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_tickets_owner ON tickets (lower(owner), token_id);
+CREATE INDEX idx_tickets_event_id ON tickets (event_id, token_id);
+
+CREATE TABLE IF NOT EXISTS ticket_transfers (
+    tx_hash TEXT NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    token_id NUMERIC(78,0) NOT NULL,
+    from_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tx_hash, log_index)
+);
+-- keyset 分页按 (block_number, log_index) 倒序
+CREATE INDEX idx_ticket_transfers_position ON ticket_transfers (block_number DESC, log_index DESC);
+CREATE INDEX idx_ticket_transfers_token_id ON ticket_transfers (token_id, block_number DESC, log_index DESC);
+CREATE INDEX idx_ticket_transfers_from ON ticket_transfers (lower(from_address));
+CREATE INDEX idx_ticket_transfers_to ON ticket_transfers (lower(to_address));
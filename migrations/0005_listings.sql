@@ -0,0 +1,19 @@
+-- Marketplace 挂单投影（ListingCreated/Updated/Cancelled/TicketSold）
+CREATE TYPE LISTING_STATUS AS ENUM ('ACTIVE', 'SOLD', 'CANCELLED');
+
+CREATE TABLE IF NOT EXISTS listings (
+    listing_id NUMERIC(78,0) PRIMARY KEY,
+    token_id NUMERIC(78,0) NOT NULL,
+    seller TEXT NOT NULL,
+    price NUMERIC(78,0) NOT NULL,
+    eth_price NUMERIC(78,0) NOT NULL,
+    expires_at NUMERIC(78,0) NOT NULL,
+    status LISTING_STATUS NOT NULL DEFAULT 'ACTIVE',
+    buyer TEXT,
+    tx_hash TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_listings_token_id ON listings (token_id);
+CREATE INDEX idx_listings_seller ON listings (lower(seller));
+CREATE INDEX idx_listings_status ON listings (status);
@@ -0,0 +1,20 @@
+-- DIDRegistry 投影：DID 当前状态 + 地址绑定
+CREATE TABLE IF NOT EXISTS dids (
+    did_hash TEXT PRIMARY KEY,
+    did TEXT NOT NULL,
+    controller TEXT NOT NULL,
+    cid TEXT NOT NULL,
+    verified BOOLEAN NOT NULL DEFAULT FALSE,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_dids_controller ON dids (lower(controller));
+
+-- 一个地址最多绑定一个 DID（与合约 addressToDid 一致）；地址统一存小写
+CREATE TABLE IF NOT EXISTS did_addresses (
+    address TEXT PRIMARY KEY,
+    did_hash TEXT NOT NULL REFERENCES dids (did_hash) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_did_addresses_did_hash ON did_addresses (did_hash);
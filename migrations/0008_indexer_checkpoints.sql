@@ -0,0 +1,7 @@
+-- 索引进度：每个合约最后一次处理到的区块（用于 /status/sync 计算落后程度）
+CREATE TABLE IF NOT EXISTS indexer_checkpoints (
+    contract TEXT PRIMARY KEY,
+    address TEXT NOT NULL,
+    last_block BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,88 @@
+-- 多链：所有索引表增加 chain_id，并纳入主键 / 唯一约束。
+-- 升级前的数据来自唯一配置的链，但迁移时无法得知其 ID：先以 0（不是合法的链 ID）占位，
+-- 进程启动时再统一改为配置的默认链 CHAIN_ID（见 db::backfill_legacy_chain_id）。
+-- 回填后去掉默认值，之后的写入必须显式给出 chain_id。
+
+-- show_created_events
+ALTER TABLE show_created_events ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE show_created_events ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE show_created_events
+    DROP CONSTRAINT show_created_events_pkey,
+    ADD PRIMARY KEY (chain_id, show_id);
+ALTER TABLE show_created_events
+    DROP CONSTRAINT show_created_events_tx_hash_log_index_key,
+    ADD UNIQUE (chain_id, tx_hash, log_index);
+
+-- show_created_events_detail
+ALTER TABLE show_created_events_detail ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE show_created_events_detail ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE show_created_events_detail
+    DROP CONSTRAINT show_created_events_detail_pkey,
+    ADD PRIMARY KEY (chain_id, show_id);
+
+-- shows
+ALTER TABLE shows ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE shows ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE shows
+    DROP CONSTRAINT shows_pkey,
+    ADD PRIMARY KEY (chain_id, id);
+DROP INDEX IF EXISTS idx_shows_created_at;
+CREATE INDEX idx_shows_chain_created_at ON shows (chain_id, created_at, id);
+
+-- tickets
+ALTER TABLE tickets ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE tickets ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE tickets
+    DROP CONSTRAINT tickets_pkey,
+    ADD PRIMARY KEY (chain_id, token_id);
+DROP INDEX IF EXISTS idx_tickets_owner;
+DROP INDEX IF EXISTS idx_tickets_event_id;
+CREATE INDEX idx_tickets_owner ON tickets (chain_id, lower(owner), token_id);
+CREATE INDEX idx_tickets_event_id ON tickets (chain_id, event_id, token_id);
+
+-- ticket_transfers
+ALTER TABLE ticket_transfers ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE ticket_transfers ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE ticket_transfers
+    DROP CONSTRAINT ticket_transfers_pkey,
+    ADD PRIMARY KEY (chain_id, tx_hash, log_index);
+DROP INDEX IF EXISTS idx_ticket_transfers_position;
+DROP INDEX IF EXISTS idx_ticket_transfers_token_id;
+CREATE INDEX idx_ticket_transfers_position ON ticket_transfers (chain_id, block_number DESC, log_index DESC);
+CREATE INDEX idx_ticket_transfers_token_id ON ticket_transfers (chain_id, token_id, block_number DESC, log_index DESC);
+
+-- listings
+ALTER TABLE listings ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE listings ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE listings
+    DROP CONSTRAINT listings_pkey,
+    ADD PRIMARY KEY (chain_id, listing_id);
+DROP INDEX IF EXISTS idx_listings_token_id;
+CREATE INDEX idx_listings_token_id ON listings (chain_id, token_id);
+
+-- dids / did_addresses：外键需先删除，待两边主键重建后再按 (chain_id, did_hash) 引用；
+-- ON UPDATE CASCADE 使启动时回填 dids.chain_id 时 did_addresses 随之更新
+ALTER TABLE did_addresses DROP CONSTRAINT did_addresses_did_hash_fkey;
+
+ALTER TABLE dids ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE dids ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE dids
+    DROP CONSTRAINT dids_pkey,
+    ADD PRIMARY KEY (chain_id, did_hash);
+
+ALTER TABLE did_addresses ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE did_addresses ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE did_addresses
+    DROP CONSTRAINT did_addresses_pkey,
+    ADD PRIMARY KEY (chain_id, address);
+ALTER TABLE did_addresses
+    ADD FOREIGN KEY (chain_id, did_hash) REFERENCES dids (chain_id, did_hash) ON DELETE CASCADE ON UPDATE CASCADE;
+DROP INDEX IF EXISTS idx_did_addresses_did_hash;
+CREATE INDEX idx_did_addresses_did_hash ON did_addresses (chain_id, did_hash);
+
+-- indexer_checkpoints
+ALTER TABLE indexer_checkpoints ADD COLUMN chain_id BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE indexer_checkpoints ALTER COLUMN chain_id DROP DEFAULT;
+ALTER TABLE indexer_checkpoints
+    DROP CONSTRAINT indexer_checkpoints_pkey,
+    ADD PRIMARY KEY (chain_id, contract);
@@ -0,0 +1,4 @@
+-- 乐观并发：shows 每次写入（API 更新或索引器刷新快照）递增 version，作为 ETag；
+-- updated_at 记录最后一次写入时间，created_at 不再随更新改变。
+ALTER TABLE shows ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE shows ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
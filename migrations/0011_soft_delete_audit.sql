@@ -0,0 +1,22 @@
+-- 软删除：DELETE /show/{id} 只标记 deleted_at，查询一律过滤已删除的演出
+ALTER TABLE shows ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- 审计日志：通过 API 发起的每次写操作一行，before/after 只保存发生变化的字段
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    chain_id BIGINT,
+    -- 资源类型：show / show_job
+    resource TEXT NOT NULL,
+    resource_id TEXT NOT NULL,
+    -- create / update / delete
+    action TEXT NOT NULL,
+    -- 调用方地址（小写 0x 十六进制）；未认证请求为空
+    actor TEXT,
+    request_id TEXT,
+    before JSONB,
+    after JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+-- GET /admin/audit 按资源或调用方过滤，按 id 倒序翻页
+CREATE INDEX IF NOT EXISTS audit_log_resource_idx ON audit_log (resource, resource_id, id DESC);
+CREATE INDEX IF NOT EXISTS audit_log_actor_idx ON audit_log (actor, id DESC);
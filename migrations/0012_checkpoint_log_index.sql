@@ -0,0 +1,3 @@
+-- 检查点精确到日志：last_log_index 为 last_block 中最后一条已成功处理的日志，
+-- 重启后从该区块继续并跳过不晚于它的日志。NULL（升级前的数据）表示整个区块已处理完。
+ALTER TABLE indexer_checkpoints ADD COLUMN last_log_index BIGINT;
@@ -0,0 +1,6 @@
+-- createShow 先签名、再认领、最后广播：PENDING -> SUBMITTING 时记录已签名交易的哈希、发送地址与 nonce。
+-- 进程在广播前后崩溃时，SUBMITTING 的 job 按链上状态核对，不会被再次发送。
+ALTER TYPE show_job_status ADD VALUE IF NOT EXISTS 'SUBMITTING' AFTER 'PENDING';
+ALTER TABLE show_create_jobs
+    ADD COLUMN sender TEXT,
+    ADD COLUMN nonce BIGINT;
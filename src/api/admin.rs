@@ -0,0 +1,146 @@
+use crate::{
+    api::{
+        AppState,
+        error::AppError,
+        request::{CursorQuery, ValidatedQuery},
+        response::{ApiResponse, ok_page},
+        schema::{RawCursorParams, ValidationError, into_page},
+        validation::Validate,
+    },
+    config,
+    repo::audit_repo::{
+        AUDIT_RESOURCES, AuditCursor, AuditFilter, AuditRecord,
+    },
+};
+use axum::extract::{FromRequestParts, State};
+use axum::http::{header::AUTHORIZATION, request::Parts};
+use axum::response::Response;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+/// 管理接口的认证：`Authorization: Bearer <ADMIN_TOKEN>`。
+/// 未配置 ADMIN_TOKEN 时管理接口整体关闭（403），token 缺失或不符时 401。
+pub struct AdminAuth;
+
+impl<S> FromRequestParts<S> for AdminAuth
+where
+    S: Send + Sync + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+        check_admin_token(config::get().admin_token.as_deref(), header)
+            .map(|()| AdminAuth)
+            .map_err(|e| e.to_response())
+    }
+}
+
+/// 按配置的 token 校验 Authorization 头（单独暴露便于测试）
+pub fn check_admin_token(
+    expected: Option<&str>,
+    authorization: Option<&str>,
+) -> Result<(), AppError> {
+    let Some(expected) = expected else {
+        return Err(AppError::Forbidden(
+            "admin endpoints are disabled (ADMIN_TOKEN is not set)".into(),
+        ));
+    };
+    let given = authorization
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    match given {
+        Some(token)
+            if constant_time_eq(token.as_bytes(), expected.as_bytes()) =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(AppError::Unauthorized("invalid admin token".into())),
+        None => Err(AppError::Unauthorized("missing bearer token".into())),
+    }
+}
+
+/// 逐字节比较不提前返回，避免通过响应时间猜测 token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// GET /admin/audit 过滤参数；分页由 `?cursor=&limit=` 控制。
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+#[validate(schema = "Self::check_resource")]
+pub struct AuditQuery {
+    /// 资源类型：show / show_job
+    #[validate(trim)]
+    pub resource: Option<String>,
+    /// 资源 id（show id 或 job id）
+    #[validate(trim, length(max = 128))]
+    pub resource_id: Option<String>,
+    /// 发起写操作的调用方地址
+    #[validate(trim, address)]
+    pub actor: Option<String>,
+}
+
+impl AuditQuery {
+    fn check_resource(&self, errors: &mut ValidationError) {
+        if let Some(r) = &self.resource
+            && !AUDIT_RESOURCES.contains(&r.as_str())
+        {
+            errors.add(
+                "resource",
+                format!("must be one of: {}", AUDIT_RESOURCES.join(", ")),
+            );
+        }
+    }
+}
+
+/// 审计日志：按 id 倒序，可按资源与调用方过滤。需要管理 token。
+#[utoipa::path(
+    get,
+    path = "/admin/audit",
+    tag = "admin",
+    params(
+        AuditQuery,
+        RawCursorParams,
+        ("Authorization" = String, Header, description = "Bearer <ADMIN_TOKEN>")
+    ),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<Vec<AuditRecord>>),
+        (status = 400, description = "invalid query params", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "missing or invalid admin token", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "ADMIN_TOKEN is not configured", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn list_audit_log(
+    State(state): State<AppState>,
+    _auth: AdminAuth,
+    ValidatedQuery(q): ValidatedQuery<AuditQuery>,
+    CursorQuery(page): CursorQuery<AuditCursor>,
+) -> Response {
+    let filter = AuditFilter {
+        resource: q.resource,
+        resource_id: q.resource_id,
+        actor: q.actor,
+    };
+    match state
+        .api
+        .repos
+        .audit
+        .list_audit(&filter, page.cursor.as_ref(), page.fetch_limit())
+        .await
+    {
+        Ok(rows) => {
+            let (items, next) =
+                into_page(rows, page.limit, |r| AuditCursor { id: r.id });
+            ok_page(items, next)
+        }
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
@@ -0,0 +1,274 @@
+use super::error::AppError;
+use crate::db::redis_cache::get_redis_connection;
+use alloy::primitives::{Address, B256, Signature, keccak256};
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, Method},
+    middleware::Next,
+    response::Response,
+};
+use eyre::Result;
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+/// 调用方地址、签名时间（unix 秒）与对 [`auth_message`] 的 EIP-191 签名
+pub const AUTH_ADDRESS: HeaderName = HeaderName::from_static("x-auth-address");
+pub const AUTH_TIMESTAMP: HeaderName =
+    HeaderName::from_static("x-auth-timestamp");
+pub const AUTH_SIGNATURE: HeaderName =
+    HeaderName::from_static("x-auth-signature");
+
+/// 签名时间与服务器时间允许的最大偏差；超出即视为过期，限制签名被重放的窗口
+pub const MAX_SKEW_SECS: i64 = 300;
+
+/// 参与签名的请求体上限，与 axum 默认的请求体上限一致
+pub const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// 已认证调用方的地址，由 [`authenticate`] 写入请求扩展：
+/// 审计记录以它为 actor，限流在 IP 之外另按它计数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticatedAddress(pub Address);
+
+/// 待签名的消息：方法、路径（含查询串）、时间戳与请求体的 keccak256，签名只对这一个请求有效
+pub fn auth_message(
+    method: &Method,
+    path: &str,
+    timestamp: i64,
+    body: &[u8],
+) -> String {
+    format!(
+        "ticket-backend auth\n{method} {path}\n{timestamp}\n{:#x}",
+        keccak256(body)
+    )
+}
+
+/// 校验通过的签名：调用方地址与所签消息的哈希（用于拒绝重放）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedRequest {
+    pub address: Address,
+    pub digest: B256,
+}
+
+/// 校验签名头：三个头都没有时为匿名请求（Ok(None)）；缺一部分、过期或签名与地址不符时返回 401。
+pub fn verify(
+    headers: &HeaderMap,
+    method: &Method,
+    path: &str,
+    body: &[u8],
+    now: i64,
+) -> Result<Option<SignedRequest>, AppError> {
+    let get = |name: &HeaderName| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+    };
+    let (address, timestamp, signature) = match (
+        get(&AUTH_ADDRESS),
+        get(&AUTH_TIMESTAMP),
+        get(&AUTH_SIGNATURE),
+    ) {
+        (None, None, None) => return Ok(None),
+        (Some(a), Some(t), Some(s)) => (a, t, s),
+        _ => {
+            return Err(AppError::Unauthorized(format!(
+                "{AUTH_ADDRESS}, {AUTH_TIMESTAMP} and {AUTH_SIGNATURE} must be sent together"
+            )));
+        }
+    };
+    let unauthorized = |msg: &str| AppError::Unauthorized(msg.to_string());
+    let address = Address::from_str(address)
+        .map_err(|_| unauthorized("invalid auth address"))?;
+    let timestamp: i64 = timestamp
+        .parse()
+        .map_err(|_| unauthorized("invalid auth timestamp"))?;
+    if (now - timestamp).abs() > MAX_SKEW_SECS {
+        return Err(unauthorized("auth signature has expired"));
+    }
+    let signature = Signature::from_str(signature)
+        .map_err(|_| unauthorized("invalid auth signature"))?;
+    let message = auth_message(method, path, timestamp, body);
+    match signature.recover_address_from_msg(message.as_bytes()) {
+        Ok(signer) if signer == address => Ok(Some(SignedRequest {
+            address,
+            digest: keccak256(message.as_bytes()),
+        })),
+        _ => Err(unauthorized("auth signature does not match address")),
+    }
+}
+
+/// 已使用签名的记录：同一请求签名在有效期内只接受一次
+#[async_trait]
+pub trait ReplayStore: Send + Sync + fmt::Debug {
+    fn backend(&self) -> &'static str;
+    /// 首次见到 key 时记录并返回 true，ttl 内再次出现返回 false
+    async fn first_use(&self, key: &str, ttl_secs: u64) -> Result<bool>;
+}
+
+/// 进程内记录，key -> 过期时间（unix 秒）
+#[derive(Debug, Default)]
+pub struct MemoryReplayStore {
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+/// 超过该数量时清理过期记录
+const MEMORY_PRUNE_THRESHOLD: usize = 10_000;
+
+#[async_trait]
+impl ReplayStore for MemoryReplayStore {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn first_use(&self, key: &str, ttl_secs: u64) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        if seen.len() > MEMORY_PRUNE_THRESHOLD {
+            seen.retain(|_, expires| *expires > now);
+        }
+        match seen.get(key) {
+            Some(expires) if *expires > now => Ok(false),
+            _ => {
+                seen.insert(key.to_string(), now + ttl_secs as i64);
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// Redis 记录：多实例共享，`SET NX EX` 保证只有一次成功
+#[derive(Clone)]
+pub struct RedisReplayStore {
+    conn: redis::aio::MultiplexedConnection,
+}
+
+impl fmt::Debug for RedisReplayStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisReplayStore").finish_non_exhaustive()
+    }
+}
+
+impl RedisReplayStore {
+    pub async fn connect(redis_url: &str) -> Result<Self> {
+        Ok(Self {
+            conn: get_redis_connection(redis_url).await?,
+        })
+    }
+}
+
+#[async_trait]
+impl ReplayStore for RedisReplayStore {
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn first_use(&self, key: &str, ttl_secs: u64) -> Result<bool> {
+        let mut conn = self.conn.clone();
+        let set: Option<String> = redis::cmd("SET")
+            .arg(format!("auth:seen:{key}"))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_secs)
+            .query_async(&mut conn)
+            .await?;
+        Ok(set.is_some())
+    }
+}
+
+/// 认证中间件的状态：记录已使用的签名
+#[derive(Debug, Clone)]
+pub struct Authenticator {
+    store: Arc<dyn ReplayStore>,
+    fallback: Arc<MemoryReplayStore>,
+}
+
+impl Authenticator {
+    pub fn new(store: Arc<dyn ReplayStore>) -> Self {
+        Self {
+            store,
+            fallback: Arc::new(MemoryReplayStore::default()),
+        }
+    }
+
+    pub fn memory() -> Self {
+        Self::new(Arc::new(MemoryReplayStore::default()))
+    }
+
+    /// 配置了 REDIS_URL 时在 Redis 中记录，连接失败退回进程内记录
+    pub async fn from_config() -> Self {
+        match crate::config::get().redis_url.as_deref() {
+            Some(url) => match RedisReplayStore::connect(url).await {
+                Ok(store) => Self::new(Arc::new(store)),
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Redis unavailable, tracking auth signatures per instance");
+                    Self::memory()
+                }
+            },
+            None => Self::memory(),
+        }
+    }
+
+    /// 签名在时间偏差窗口两侧都可能有效，记录保留两倍窗口
+    async fn first_use(&self, signed: &SignedRequest) -> bool {
+        let key = format!("{:#x}:{:#x}", signed.address, signed.digest);
+        let ttl = 2 * MAX_SKEW_SECS as u64;
+        match self.store.first_use(&key, ttl).await {
+            Ok(first) => first,
+            Err(e) => {
+                tracing::warn!(error = ?e, backend = self.store.backend(), "auth replay store failed, using in-memory records");
+                self.fallback.first_use(&key, ttl).await.unwrap_or(false)
+            }
+        }
+    }
+}
+
+/// 认证中间件：签名有效且未被使用过时写入 [`AuthenticatedAddress`]，无签名头的请求原样放行。
+/// 带签名头时读出请求体参与校验，再原样交给后续处理。须位于限流之外，限流才能按地址计数。
+pub async fn authenticate(
+    State(auth): State<Authenticator>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !req.headers().contains_key(&AUTH_SIGNATURE)
+        && !req.headers().contains_key(&AUTH_ADDRESS)
+        && !req.headers().contains_key(&AUTH_TIMESTAMP)
+    {
+        return next.run(req).await;
+    }
+    let (parts, body) = req.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES).await
+    else {
+        return AppError::Unauthorized(
+            "request body is too large to authenticate".into(),
+        )
+        .to_response();
+    };
+    let path = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+    let now = chrono::Utc::now().timestamp();
+    let signed = match verify(&parts.headers, &parts.method, path, &body, now) {
+        Ok(Some(signed)) => signed,
+        Ok(None) => unreachable!("auth headers are present"),
+        Err(e) => return e.to_response(),
+    };
+    if !auth.first_use(&signed).await {
+        return AppError::Unauthorized(
+            "auth signature has already been used".into(),
+        )
+        .to_response();
+    }
+    let mut req = Request::from_parts(parts, Body::from(body));
+    req.extensions_mut()
+        .insert(AuthenticatedAddress(signed.address));
+    next.run(req).await
+}
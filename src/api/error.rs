@@ -1,9 +1,40 @@
-use super::response::{bad_request, internal_error, not_found};
-use axum::response::Response;
+use super::response::ApiResponse;
+use super::schema::{FieldError, ValidationError};
+use axum::{
+    Json, http::StatusCode, response::IntoResponse, response::Response,
+};
 use serde::Serialize;
 use thiserror::Error;
 
-#[derive(Debug, Clone, Copy, Serialize)]
+tokio::task_local! {
+    /// 当前请求的 request id，由 [`scope_request_id`] 设置，写入每个错误响应体
+    static REQUEST_ID: String;
+}
+
+/// 中间件：把请求头中的 request id 放入 task-local，供 [`AppError::to_response`] 读取。
+/// 需挂在 SetRequestIdLayer 之内（此时请求头已有 id）。
+pub async fn scope_request_id(
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let rid = req
+        .headers()
+        .get(super::request_id_header())
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    REQUEST_ID.scope(rid, next.run(req)).await
+}
+
+/// 当前请求的 request id；不在请求上下文中（如单元测试）时为空
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID
+        .try_with(|rid| rid.clone())
+        .ok()
+        .filter(|rid| !rid.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[repr(i32)]
 pub enum ErrorCode {
     Ok = 0,
@@ -12,12 +43,47 @@ pub enum ErrorCode {
     JsonInvalid = 1002,
     QueryInvalid = 1003,
     ShowNotFound = 2000,
+    JobNotFound = 2001,
+    TicketNotFound = 2002,
+    WebhookNotFound = 2003,
+    RouteNotFound = 2004,
+    SignerUnavailable = 3000,
+    NotReady = 3001,
+    RateLimited = 4000,
+    Unauthorized = 4001,
+    Forbidden = 4002,
+    Conflict = 4003,
+    PreconditionFailed = 4004,
     Database = 9001,
     Decode = 9002,
     Internal = 9000,
 }
 
 impl ErrorCode {
+    /// 全部错误码，用于生成 OpenAPI 文档
+    pub const ALL: [ErrorCode; 20] = [
+        ErrorCode::Ok,
+        ErrorCode::Validation,
+        ErrorCode::ParseIdInvalid,
+        ErrorCode::JsonInvalid,
+        ErrorCode::QueryInvalid,
+        ErrorCode::ShowNotFound,
+        ErrorCode::JobNotFound,
+        ErrorCode::TicketNotFound,
+        ErrorCode::WebhookNotFound,
+        ErrorCode::RouteNotFound,
+        ErrorCode::SignerUnavailable,
+        ErrorCode::NotReady,
+        ErrorCode::RateLimited,
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::Conflict,
+        ErrorCode::PreconditionFailed,
+        ErrorCode::Database,
+        ErrorCode::Decode,
+        ErrorCode::Internal,
+    ];
+
     pub fn code(self) -> i32 {
         self as i32
     }
@@ -25,29 +91,94 @@ impl ErrorCode {
         match self {
             ErrorCode::Ok => "ok",
             ErrorCode::Validation => "validation error",
-            ErrorCode::ParseIdInvalid => "invalid show id",
+            ErrorCode::ParseIdInvalid => "invalid id",
             ErrorCode::JsonInvalid => "invalid json body",
             ErrorCode::QueryInvalid => "invalid query params",
             ErrorCode::ShowNotFound => "show not found",
+            ErrorCode::JobNotFound => "job not found",
+            ErrorCode::TicketNotFound => "ticket not found",
+            ErrorCode::WebhookNotFound => "webhook not found",
+            ErrorCode::RouteNotFound => "route not found",
+            ErrorCode::SignerUnavailable => "signer unavailable",
+            ErrorCode::NotReady => "service not ready",
+            ErrorCode::RateLimited => "rate limit exceeded",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::PreconditionFailed => "precondition failed",
             ErrorCode::Database => "database error",
             ErrorCode::Decode => "decode error",
             ErrorCode::Internal => "internal error",
         }
     }
+    /// 错误码对应的 HTTP 状态：1xxx 400，2xxx 404，3xxx 503，4xxx 各自的 4xx，9xxx 500
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::Ok => StatusCode::OK,
+            ErrorCode::Validation
+            | ErrorCode::ParseIdInvalid
+            | ErrorCode::JsonInvalid
+            | ErrorCode::QueryInvalid => StatusCode::BAD_REQUEST,
+            ErrorCode::ShowNotFound
+            | ErrorCode::JobNotFound
+            | ErrorCode::TicketNotFound
+            | ErrorCode::WebhookNotFound
+            | ErrorCode::RouteNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::SignerUnavailable | ErrorCode::NotReady => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::Conflict => StatusCode::CONFLICT,
+            ErrorCode::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            ErrorCode::Database | ErrorCode::Decode | ErrorCode::Internal => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+    /// 服务端内部错误：原始信息只写日志，响应体使用默认消息
+    pub fn is_internal(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Database | ErrorCode::Decode | ErrorCode::Internal
+        )
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("validation error: {0}")]
-    Validation(String),
-    #[error("invalid show id: {0}")]
-    ParseIdInvalid(String),
+    Validation(ValidationError),
+    /// 路径中的 id 无法解析；第一个字段为资源名（show / ticket）
+    #[error("invalid {0} id: {1}")]
+    ParseIdInvalid(&'static str, String),
     #[error("invalid json body: {0}")]
-    JsonInvalid(String),
+    JsonInvalid(ValidationError),
     #[error("invalid query params: {0}")]
-    QueryInvalid(String),
+    QueryInvalid(ValidationError),
     #[error("show not found: {0}")]
     ShowNotFound(String),
+    #[error("job not found: {0}")]
+    JobNotFound(String),
+    #[error("ticket not found: {0}")]
+    TicketNotFound(String),
+    #[error("webhook not found: {0}")]
+    WebhookNotFound(String),
+    #[error("route not found: {0}")]
+    RouteNotFound(String),
+    #[error("signer unavailable: {0}")]
+    SignerUnavailable(String),
+    #[error("rate limit exceeded: {0}")]
+    RateLimited(String),
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    #[error("conflict: {0}")]
+    Conflict(String),
+    #[error("precondition failed: {0}")]
+    PreconditionFailed(String),
     #[error("database error: {0}")]
     Database(String),
     #[error("decode error: {0}")]
@@ -60,35 +191,105 @@ impl AppError {
     pub fn code(&self) -> ErrorCode {
         match self {
             AppError::Validation(_) => ErrorCode::Validation,
-            AppError::ParseIdInvalid(_) => ErrorCode::ParseIdInvalid,
+            AppError::ParseIdInvalid(..) => ErrorCode::ParseIdInvalid,
             AppError::JsonInvalid(_) => ErrorCode::JsonInvalid,
             AppError::QueryInvalid(_) => ErrorCode::QueryInvalid,
             AppError::ShowNotFound(_) => ErrorCode::ShowNotFound,
+            AppError::JobNotFound(_) => ErrorCode::JobNotFound,
+            AppError::TicketNotFound(_) => ErrorCode::TicketNotFound,
+            AppError::WebhookNotFound(_) => ErrorCode::WebhookNotFound,
+            AppError::RouteNotFound(_) => ErrorCode::RouteNotFound,
+            AppError::SignerUnavailable(_) => ErrorCode::SignerUnavailable,
+            AppError::RateLimited(_) => ErrorCode::RateLimited,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::Forbidden(_) => ErrorCode::Forbidden,
+            AppError::Conflict(_) => ErrorCode::Conflict,
+            AppError::PreconditionFailed(_) => ErrorCode::PreconditionFailed,
             AppError::Database(_) => ErrorCode::Database,
             AppError::Decode(_) => ErrorCode::Decode,
             AppError::Internal(_) => ErrorCode::Internal,
         }
     }
-    pub fn to_response(&self) -> Response {
-        match self.code() {
-            ErrorCode::Validation
-            | ErrorCode::ParseIdInvalid
-            | ErrorCode::JsonInvalid
-            | ErrorCode::QueryInvalid => bad_request(self.to_string()),
-            ErrorCode::ShowNotFound => not_found(self.to_string()),
-            ErrorCode::Database | ErrorCode::Decode => {
-                internal_error(self.to_string())
+
+    pub fn status(&self) -> StatusCode {
+        self.code().status()
+    }
+
+    /// 逐字段的校验错误（仅校验类错误有）
+    pub fn details(&self) -> Option<&[FieldError]> {
+        match self {
+            AppError::Validation(e)
+            | AppError::JsonInvalid(e)
+            | AppError::QueryInvalid(e)
+                if !e.fields.is_empty() =>
+            {
+                Some(&e.fields)
             }
-            ErrorCode::Internal => internal_error(self.to_string()),
-            ErrorCode::Ok => bad_request("unexpected ok code"),
+            _ => None,
+        }
+    }
+
+    /// 返回给客户端的消息：内部错误（数据库、解码等）不暴露原始信息
+    pub fn public_message(&self) -> String {
+        if self.code().is_internal() {
+            self.code().default_message().to_string()
+        } else {
+            self.to_string()
         }
     }
+
+    pub fn to_response(&self) -> Response {
+        let request_id = current_request_id();
+        if self.code().is_internal() {
+            tracing::error!(code = self.code().code(), request_id = request_id.as_deref().unwrap_or_default(), error = %self, "Request failed");
+        }
+        let body = ApiResponse::<serde_json::Value> {
+            details: self.details().map(<[FieldError]>::to_vec),
+            request_id,
+            ..ApiResponse::error(self.code(), Some(self.public_message()))
+        };
+        (self.status(), Json(body)).into_response()
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        self.to_response()
+    }
+}
+
+impl From<ValidationError> for AppError {
+    fn from(e: ValidationError) -> Self {
+        AppError::Validation(e)
+    }
 }
 
 impl From<sqlx::Error> for AppError {
     fn from(e: sqlx::Error) -> Self {
-        // 简单分类，可按需细化
-        AppError::Database(e.to_string())
+        match e.as_database_error() {
+            // 唯一约束 / 外键约束：请求与现有数据冲突，约束名可以安全返回
+            Some(db)
+                if db.is_unique_violation()
+                    || db.is_foreign_key_violation() =>
+            {
+                AppError::Conflict(
+                    db.constraint()
+                        .map(|c| format!("violates {c}"))
+                        .unwrap_or_else(|| "constraint violation".into()),
+                )
+            }
+            _ => AppError::Database(e.to_string()),
+        }
+    }
+}
+
+/// 仓储层返回 eyre::Report：其中的 sqlx 错误按 [`From<sqlx::Error>`] 分类，其余视为内部错误
+impl From<eyre::Report> for AppError {
+    fn from(e: eyre::Report) -> Self {
+        match e.downcast::<sqlx::Error>() {
+            Ok(e) => e.into(),
+            Err(e) => AppError::Internal(format!("{e:#}")),
+        }
     }
 }
 
@@ -98,4 +299,7 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for AppError {
     }
 }
 
-// (AppResult alias removed as current handlers return Response directly)
+/// 未匹配任何路由时的 JSON 404
+pub async fn route_not_found(uri: axum::http::Uri) -> Response {
+    AppError::RouteNotFound(uri.path().to_string()).to_response()
+}
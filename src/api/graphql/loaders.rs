@@ -0,0 +1,254 @@
+use crate::{
+    repo::{
+        did_repo::DidRecord, listing_repo::ListingRecord,
+        show_repo::ShowDataRecord, ticket_repo::TicketRecord, traits::Repos,
+    },
+    utils::uint256::DbU256,
+};
+use async_graphql::dataloader::Loader;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// 批量加载的错误：克隆开销小，解析到字段上时再转换为 GraphQL 错误
+pub type LoadError = Arc<eyre::Report>;
+
+/// 同一请求内所有 DataLoader 的数据源；每种 key 对应 repo 层的一次批量查询。
+/// 一个请求只查询一条链（`?chain_id=`），因此 key 中不再带 chain_id。
+pub struct RepoLoader {
+    repos: Repos,
+    chain_id: i64,
+}
+
+impl RepoLoader {
+    pub fn new(repos: Repos, chain_id: i64) -> Self {
+        Self { repos, chain_id }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowKey(pub DbU256);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TicketKey(pub DbU256);
+
+/// 某张票当前 ACTIVE 的挂单
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActiveListingKey(pub DbU256);
+
+/// 地址（小写）绑定的 DID
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DidKey(pub String);
+
+/// show 的前 limit 张票
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowTicketsKey {
+    pub show_id: DbU256,
+    pub limit: i64,
+}
+
+/// 持有人（小写地址）的前 limit 张票
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnerTicketsKey {
+    pub owner: String,
+    pub limit: i64,
+}
+
+/// 卖家（小写地址）的前 limit 条挂单
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SellerListingsKey {
+    pub seller: String,
+    pub limit: i64,
+}
+
+/// 一对多 key 按 limit 分组，每组一次查询
+fn group_by_limit<K: Clone, P: Clone>(
+    keys: &[K],
+    parts: impl Fn(&K) -> (P, i64),
+) -> BTreeMap<i64, Vec<P>> {
+    let mut groups: BTreeMap<i64, Vec<P>> = BTreeMap::new();
+    for k in keys {
+        let (parent, limit) = parts(k);
+        groups.entry(limit).or_default().push(parent);
+    }
+    groups
+}
+
+impl Loader<ShowKey> for RepoLoader {
+    type Value = ShowDataRecord;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[ShowKey],
+    ) -> Result<HashMap<ShowKey, Self::Value>, Self::Error> {
+        let ids: Vec<DbU256> = keys.iter().map(|k| k.0.clone()).collect();
+        let recs = self
+            .repos
+            .shows
+            .get_shows_by_ids(self.chain_id, &ids)
+            .await
+            .map_err(Arc::new)?;
+        Ok(recs
+            .into_iter()
+            .map(|r| (ShowKey(r.id.clone()), r))
+            .collect())
+    }
+}
+
+impl Loader<TicketKey> for RepoLoader {
+    type Value = TicketRecord;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[TicketKey],
+    ) -> Result<HashMap<TicketKey, Self::Value>, Self::Error> {
+        let ids: Vec<DbU256> = keys.iter().map(|k| k.0.clone()).collect();
+        let recs = self
+            .repos
+            .tickets
+            .get_tickets_by_ids(self.chain_id, &ids)
+            .await
+            .map_err(Arc::new)?;
+        Ok(recs
+            .into_iter()
+            .map(|r| (TicketKey(r.token_id.clone()), r))
+            .collect())
+    }
+}
+
+impl Loader<ActiveListingKey> for RepoLoader {
+    type Value = ListingRecord;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[ActiveListingKey],
+    ) -> Result<HashMap<ActiveListingKey, Self::Value>, Self::Error> {
+        let ids: Vec<DbU256> = keys.iter().map(|k| k.0.clone()).collect();
+        let recs = self
+            .repos
+            .listings
+            .get_active_listings_by_tokens(self.chain_id, &ids)
+            .await
+            .map_err(Arc::new)?;
+        Ok(recs
+            .into_iter()
+            .map(|r| (ActiveListingKey(r.token_id.clone()), r))
+            .collect())
+    }
+}
+
+impl Loader<DidKey> for RepoLoader {
+    type Value = DidRecord;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[DidKey],
+    ) -> Result<HashMap<DidKey, Self::Value>, Self::Error> {
+        let addrs: Vec<String> = keys.iter().map(|k| k.0.clone()).collect();
+        let recs = self
+            .repos
+            .dids
+            .get_dids_by_addresses(self.chain_id, &addrs)
+            .await
+            .map_err(Arc::new)?;
+        Ok(recs
+            .into_iter()
+            .map(|r| (DidKey(r.address), r.did))
+            .collect())
+    }
+}
+
+impl Loader<ShowTicketsKey> for RepoLoader {
+    type Value = Vec<TicketRecord>;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[ShowTicketsKey],
+    ) -> Result<HashMap<ShowTicketsKey, Self::Value>, Self::Error> {
+        let mut out: HashMap<ShowTicketsKey, Vec<TicketRecord>> =
+            keys.iter().map(|k| (k.clone(), Vec::new())).collect();
+        let groups = group_by_limit(keys, |k| (k.show_id.clone(), k.limit));
+        for (limit, show_ids) in groups {
+            let recs = self
+                .repos
+                .tickets
+                .list_tickets_for_shows(self.chain_id, &show_ids, limit)
+                .await
+                .map_err(Arc::new)?;
+            for r in recs {
+                let key = ShowTicketsKey {
+                    show_id: r.event_id.clone(),
+                    limit,
+                };
+                out.entry(key).or_default().push(r);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl Loader<OwnerTicketsKey> for RepoLoader {
+    type Value = Vec<TicketRecord>;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[OwnerTicketsKey],
+    ) -> Result<HashMap<OwnerTicketsKey, Self::Value>, Self::Error> {
+        let mut out: HashMap<OwnerTicketsKey, Vec<TicketRecord>> =
+            keys.iter().map(|k| (k.clone(), Vec::new())).collect();
+        let groups = group_by_limit(keys, |k| (k.owner.clone(), k.limit));
+        for (limit, owners) in groups {
+            let recs = self
+                .repos
+                .tickets
+                .list_tickets_for_owners(self.chain_id, &owners, limit)
+                .await
+                .map_err(Arc::new)?;
+            for r in recs {
+                let key = OwnerTicketsKey {
+                    owner: r.owner.to_lowercase(),
+                    limit,
+                };
+                out.entry(key).or_default().push(r);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl Loader<SellerListingsKey> for RepoLoader {
+    type Value = Vec<ListingRecord>;
+    type Error = LoadError;
+
+    async fn load(
+        &self,
+        keys: &[SellerListingsKey],
+    ) -> Result<HashMap<SellerListingsKey, Self::Value>, Self::Error> {
+        let mut out: HashMap<SellerListingsKey, Vec<ListingRecord>> =
+            keys.iter().map(|k| (k.clone(), Vec::new())).collect();
+        let groups = group_by_limit(keys, |k| (k.seller.clone(), k.limit));
+        for (limit, sellers) in groups {
+            let recs = self
+                .repos
+                .listings
+                .list_listings_for_sellers(self.chain_id, &sellers, limit)
+                .await
+                .map_err(Arc::new)?;
+            for r in recs {
+                let key = SellerListingsKey {
+                    seller: r.seller.to_lowercase(),
+                    limit,
+                };
+                out.entry(key).or_default().push(r);
+            }
+        }
+        Ok(out)
+    }
+}
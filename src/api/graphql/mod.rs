@@ -0,0 +1,105 @@
+//! POST /graphql：shows / tickets / owners / listings / DIDs 的只读查询。
+//! 关联字段经请求级 DataLoader 批量加载，深度与复杂度在执行前校验。
+pub mod loaders;
+pub mod types;
+
+use crate::{
+    api::{
+        AppState, error::AppError, request::ChainQuery, schema::ChainParams,
+    },
+    config,
+    utils::uint256::DbU256,
+};
+use async_graphql::{
+    EmptyMutation, EmptySubscription, InputValueError, InputValueResult,
+    Scalar, ScalarType, Schema, Value, dataloader::DataLoader,
+};
+use axum::{
+    Json,
+    extract::{State, rejection::JsonRejection},
+    response::{IntoResponse, Response},
+};
+use loaders::RepoLoader;
+use std::{fmt, str::FromStr};
+use types::{QueryRoot, RequestChain};
+
+pub type AppSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// uint256 以十进制字符串输出；输入接受十进制/0x 字符串或非负整数
+#[Scalar(name = "U256")]
+impl ScalarType for DbU256 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => {
+                DbU256::from_str(s).map_err(InputValueError::custom)
+            }
+            Value::Number(n) => n
+                .as_u64()
+                .map(DbU256::from)
+                .ok_or_else(|| InputValueError::custom("expected uint256")),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+/// 构造 schema；不依赖数据库，数据在每个请求中注入。
+pub fn build_schema(max_depth: usize, max_complexity: usize) -> AppSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(max_depth)
+        .limit_complexity(max_complexity)
+        .finish()
+}
+
+/// ApiContext 中持有的 schema（Schema 本身未实现 Debug）
+#[derive(Clone)]
+pub struct GraphqlSchema(pub AppSchema);
+
+impl GraphqlSchema {
+    pub fn from_config() -> Self {
+        let cfg = config::get();
+        Self(build_schema(
+            cfg.graphql_max_depth,
+            cfg.graphql_max_complexity,
+        ))
+    }
+}
+
+impl fmt::Debug for GraphqlSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphqlSchema").finish_non_exhaustive()
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    tag = "graphql",
+    params(ChainParams),
+    request_body(content = serde_json::Value, description = "GraphQL request: {query, variables?, operationName?}"),
+    responses(
+        (status = 200, description = "GraphQL response: {data?, errors?}", body = serde_json::Value),
+        (status = 400, description = "Body is not a GraphQL request", body = crate::api::response::ApiResponse<serde_json::Value>),
+    )
+)]
+pub async fn graphql_handler(
+    State(state): State<AppState>,
+    ChainQuery(chain_id): ChainQuery,
+    body: Result<Json<async_graphql::Request>, JsonRejection>,
+) -> Response {
+    let Json(req) = match body {
+        Ok(b) => b,
+        Err(e) => {
+            return AppError::JsonInvalid(e.body_text().into()).to_response();
+        }
+    };
+    let repos = state.api.repos.clone();
+    // DataLoader 按请求创建：批量与缓存只在一次查询内生效，不会读到其他请求的旧数据
+    let loader =
+        DataLoader::new(RepoLoader::new(repos.clone(), chain_id), tokio::spawn);
+    let req = req.data(repos).data(loader).data(RequestChain(chain_id));
+    Json(state.api.graphql.0.execute(req).await).into_response()
+}
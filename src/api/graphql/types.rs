@@ -0,0 +1,527 @@
+use super::loaders::{
+    ActiveListingKey, DidKey, LoadError, OwnerTicketsKey, RepoLoader,
+    SellerListingsKey, ShowKey, ShowTicketsKey, TicketKey,
+};
+use crate::{
+    api::{
+        schema::{decode_cursor, into_page},
+        ticket_manager::normalize_address,
+    },
+    repo::{
+        did_repo::DidRecord,
+        listing_repo::{ListingCursor, ListingFilter, ListingRecord},
+        show_repo::{ShowCursor, ShowDataRecord, ShowFilter, SortOrder},
+        ticket_repo::{TicketCursor, TicketFilter, TicketRecord},
+        traits::Repos,
+    },
+    utils::uint256::DbU256,
+};
+use async_graphql::{
+    Context, Enum, Error, Object, Result, SimpleObject, dataloader::DataLoader,
+};
+use chrono::{DateTime, Utc};
+
+/// 列表与嵌套列表的默认/最大条数；复杂度按 limit * 子字段复杂度计算
+pub const DEFAULT_PAGE_LIMIT: i32 = 20;
+pub const MAX_PAGE_LIMIT: i32 = 100;
+
+pub fn page_limit(limit: i32) -> i64 {
+    i64::from(limit.clamp(1, MAX_PAGE_LIMIT))
+}
+
+fn loader<'a>(ctx: &Context<'a>) -> &'a DataLoader<RepoLoader> {
+    ctx.data_unchecked::<DataLoader<RepoLoader>>()
+}
+
+fn repos<'a>(ctx: &Context<'a>) -> &'a Repos {
+    ctx.data_unchecked::<Repos>()
+}
+
+/// 本次请求查询的链（由 `?chain_id=` 决定，默认链）
+#[derive(Debug, Clone, Copy)]
+pub struct RequestChain(pub i64);
+
+fn chain_id(ctx: &Context<'_>) -> i64 {
+    ctx.data_unchecked::<RequestChain>().0
+}
+
+/// 数据库错误只记录日志，不把 SQL 细节返回给调用方
+fn db_error(e: impl std::fmt::Display) -> Error {
+    tracing::error!(error = %e, "graphql repo query failed");
+    Error::new("database error")
+}
+
+fn load_error(e: LoadError) -> Error {
+    db_error(e)
+}
+
+fn address_arg(address: &str, field: &str) -> Result<String> {
+    normalize_address(Some(address.to_string()), field)
+        .map_err(|e| Error::new(e.message))?
+        .map(|a| a.to_lowercase())
+        .ok_or_else(|| Error::new(format!("{field} must not be empty")))
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(
+    name = "TicketStatus",
+    remote = "crate::repo::ticket_repo::TicketStatus"
+)]
+enum GqlTicketStatus {
+    Valid,
+    Used,
+    Cancelled,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(
+    name = "ListingStatus",
+    remote = "crate::repo::listing_repo::ListingStatus"
+)]
+enum GqlListingStatus {
+    Active,
+    Sold,
+    Cancelled,
+}
+
+pub struct Show(pub ShowDataRecord);
+
+#[Object]
+impl Show {
+    async fn chain_id(&self) -> i64 {
+        self.0.chain_id
+    }
+    async fn id(&self) -> &DbU256 {
+        &self.0.id
+    }
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+    async fn location(&self) -> &str {
+        &self.0.location
+    }
+    async fn event_time(&self) -> &DbU256 {
+        &self.0.event_time
+    }
+    async fn ticket_price(&self) -> &DbU256 {
+        &self.0.ticket_price
+    }
+    async fn max_tickets(&self) -> &DbU256 {
+        &self.0.max_tickets
+    }
+    async fn sold_tickets(&self) -> &DbU256 {
+        &self.0.sold_tickets
+    }
+    async fn is_active(&self) -> bool {
+        self.0.is_active
+    }
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+    async fn organizer(&self) -> Owner {
+        Owner::new(&self.0.organizer)
+    }
+    /// 该 show 的前 limit 张票（token_id 升序）
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<Ticket>> {
+        let key = ShowTicketsKey {
+            show_id: self.0.id.clone(),
+            limit: page_limit(limit),
+        };
+        let recs = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(recs.unwrap_or_default().into_iter().map(Ticket).collect())
+    }
+}
+
+pub struct Ticket(pub TicketRecord);
+
+#[Object]
+impl Ticket {
+    async fn chain_id(&self) -> i64 {
+        self.0.chain_id
+    }
+    async fn token_id(&self) -> &DbU256 {
+        &self.0.token_id
+    }
+    async fn show_id(&self) -> &DbU256 {
+        &self.0.event_id
+    }
+    async fn seat_number(&self) -> &DbU256 {
+        &self.0.seat_number
+    }
+    async fn price(&self) -> &DbU256 {
+        &self.0.price
+    }
+    async fn status(&self) -> GqlTicketStatus {
+        self.0.status.into()
+    }
+    async fn minted_tx_hash(&self) -> Option<&str> {
+        self.0.minted_tx_hash.as_deref()
+    }
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+    async fn show(&self, ctx: &Context<'_>) -> Result<Option<Show>> {
+        let key = ShowKey(self.0.event_id.clone());
+        let rec = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(rec.map(Show))
+    }
+    async fn owner(&self) -> Owner {
+        Owner::new(&self.0.owner)
+    }
+    /// 当前有效的挂单（若有）
+    async fn listing(&self, ctx: &Context<'_>) -> Result<Option<Listing>> {
+        let key = ActiveListingKey(self.0.token_id.clone());
+        let rec = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(rec.map(Listing))
+    }
+}
+
+pub struct Listing(pub ListingRecord);
+
+#[Object]
+impl Listing {
+    async fn chain_id(&self) -> i64 {
+        self.0.chain_id
+    }
+    async fn listing_id(&self) -> &DbU256 {
+        &self.0.listing_id
+    }
+    async fn token_id(&self) -> &DbU256 {
+        &self.0.token_id
+    }
+    async fn price(&self) -> &DbU256 {
+        &self.0.price
+    }
+    async fn eth_price(&self) -> &DbU256 {
+        &self.0.eth_price
+    }
+    async fn expires_at(&self) -> &DbU256 {
+        &self.0.expires_at
+    }
+    async fn status(&self) -> GqlListingStatus {
+        self.0.status.into()
+    }
+    async fn tx_hash(&self) -> Option<&str> {
+        self.0.tx_hash.as_deref()
+    }
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+    async fn seller(&self) -> Owner {
+        Owner::new(&self.0.seller)
+    }
+    async fn buyer(&self) -> Option<Owner> {
+        self.0.buyer.as_deref().map(Owner::new)
+    }
+    async fn ticket(&self, ctx: &Context<'_>) -> Result<Option<Ticket>> {
+        let key = TicketKey(self.0.token_id.clone());
+        let rec = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(rec.map(Ticket))
+    }
+}
+
+/// 任意链上地址：持票人、卖家、主办方或 DID controller
+pub struct Owner {
+    address: String,
+}
+
+impl Owner {
+    fn new(address: &str) -> Self {
+        Self {
+            address: address.to_lowercase(),
+        }
+    }
+}
+
+#[Object]
+impl Owner {
+    async fn address(&self) -> &str {
+        &self.address
+    }
+    /// 绑定到该地址的 DID（若有）
+    async fn did(&self, ctx: &Context<'_>) -> Result<Option<Did>> {
+        let key = DidKey(self.address.clone());
+        let rec = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(rec.map(Did))
+    }
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<Ticket>> {
+        let key = OwnerTicketsKey {
+            owner: self.address.clone(),
+            limit: page_limit(limit),
+        };
+        let recs = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(recs.unwrap_or_default().into_iter().map(Ticket).collect())
+    }
+    /// 作为卖家发布的挂单（listing_id 升序）
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn listings(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i32,
+    ) -> Result<Vec<Listing>> {
+        let key = SellerListingsKey {
+            seller: self.address.clone(),
+            limit: page_limit(limit),
+        };
+        let recs = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(recs.unwrap_or_default().into_iter().map(Listing).collect())
+    }
+}
+
+pub struct Did(pub DidRecord);
+
+#[Object]
+impl Did {
+    async fn did_hash(&self) -> &str {
+        &self.0.did_hash
+    }
+    async fn did(&self) -> &str {
+        &self.0.did
+    }
+    async fn cid(&self) -> &str {
+        &self.0.cid
+    }
+    async fn verified(&self) -> bool {
+        self.0.verified
+    }
+    async fn revoked(&self) -> bool {
+        self.0.revoked
+    }
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+    async fn controller(&self) -> Owner {
+        Owner::new(&self.0.controller)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ShowPage {
+    items: Vec<Show>,
+    /// 为 null 表示没有下一页
+    next_cursor: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct TicketPage {
+    items: Vec<Ticket>,
+    next_cursor: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct ListingPage {
+    items: Vec<Listing>,
+    next_cursor: Option<String>,
+}
+
+fn cursor_arg<C: serde::de::DeserializeOwned>(
+    after: Option<&str>,
+) -> Result<Option<C>> {
+    match after.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(s) => decode_cursor(s)
+            .map(Some)
+            .map_err(|e| Error::new(e.message)),
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn show(
+        &self,
+        ctx: &Context<'_>,
+        id: DbU256,
+    ) -> Result<Option<Show>> {
+        let rec = loader(ctx)
+            .load_one(ShowKey(id))
+            .await
+            .map_err(load_error)?;
+        Ok(rec.map(Show))
+    }
+
+    /// 按创建时间倒序分页；指定 q（全文检索）时按相关度排序且只返回第一页
+    #[graphql(complexity = "page_limit(first) as usize * child_complexity")]
+    async fn shows(
+        &self,
+        ctx: &Context<'_>,
+        active: Option<bool>,
+        organizer: Option<String>,
+        q: Option<String>,
+        #[graphql(default = 20)] first: i32,
+        after: Option<String>,
+    ) -> Result<ShowPage> {
+        let limit = page_limit(first);
+        let q = q.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let after: Option<ShowCursor> = cursor_arg(after.as_deref())?;
+        if q.is_some() && after.is_some() {
+            return Err(Error::new("after is not supported together with q"));
+        }
+        if let Some(c) = &after
+            && !c.matches(None, SortOrder::Desc)
+        {
+            return Err(Error::new("invalid cursor"));
+        }
+        let filter = ShowFilter {
+            chain_id: Some(chain_id(ctx)),
+            is_active: active,
+            organizer: organizer
+                .map(|o| address_arg(&o, "organizer"))
+                .transpose()?,
+            q,
+            ..Default::default()
+        };
+        let rows = repos(ctx)
+            .shows
+            .search_shows(
+                &filter,
+                None,
+                SortOrder::Desc,
+                after.as_ref(),
+                limit + 1,
+                0,
+            )
+            .await
+            .map_err(db_error)?;
+        let (rows, next_cursor) = if filter.q.is_some() {
+            let mut rows = rows;
+            rows.truncate(limit as usize);
+            (rows, None)
+        } else {
+            into_page(rows, limit, |r| {
+                ShowCursor::from_record(r, None, SortOrder::Desc)
+            })
+        };
+        Ok(ShowPage {
+            items: rows.into_iter().map(Show).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn ticket(
+        &self,
+        ctx: &Context<'_>,
+        id: DbU256,
+    ) -> Result<Option<Ticket>> {
+        let rec = loader(ctx)
+            .load_one(TicketKey(id))
+            .await
+            .map_err(load_error)?;
+        Ok(rec.map(Ticket))
+    }
+
+    #[graphql(complexity = "page_limit(first) as usize * child_complexity")]
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        owner: Option<String>,
+        show_id: Option<DbU256>,
+        status: Option<GqlTicketStatus>,
+        #[graphql(default = 20)] first: i32,
+        after: Option<String>,
+    ) -> Result<TicketPage> {
+        let limit = page_limit(first);
+        let after: Option<TicketCursor> = cursor_arg(after.as_deref())?;
+        let filter = TicketFilter {
+            chain_id: Some(chain_id(ctx)),
+            owner: owner.map(|o| address_arg(&o, "owner")).transpose()?,
+            event_id: show_id,
+            status: status.map(Into::into),
+        };
+        let rows = repos(ctx)
+            .tickets
+            .list_tickets(&filter, after.as_ref(), limit + 1)
+            .await
+            .map_err(db_error)?;
+        let (rows, next_cursor) = into_page(rows, limit, |r| TicketCursor {
+            token_id: r.token_id.clone(),
+        });
+        Ok(TicketPage {
+            items: rows.into_iter().map(Ticket).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn listing(
+        &self,
+        ctx: &Context<'_>,
+        id: DbU256,
+    ) -> Result<Option<Listing>> {
+        let rec = repos(ctx)
+            .listings
+            .get_listing(chain_id(ctx), &id)
+            .await
+            .map_err(db_error)?;
+        Ok(rec.map(Listing))
+    }
+
+    #[graphql(complexity = "page_limit(first) as usize * child_complexity")]
+    async fn listings(
+        &self,
+        ctx: &Context<'_>,
+        seller: Option<String>,
+        token_id: Option<DbU256>,
+        status: Option<GqlListingStatus>,
+        #[graphql(default = 20)] first: i32,
+        after: Option<String>,
+    ) -> Result<ListingPage> {
+        let limit = page_limit(first);
+        let after: Option<ListingCursor> = cursor_arg(after.as_deref())?;
+        let filter = ListingFilter {
+            chain_id: Some(chain_id(ctx)),
+            status: status.map(Into::into),
+            seller: seller.map(|s| address_arg(&s, "seller")).transpose()?,
+            token_id,
+        };
+        let rows = repos(ctx)
+            .listings
+            .list_listings(&filter, after.as_ref(), limit + 1)
+            .await
+            .map_err(db_error)?;
+        let (rows, next_cursor) = into_page(rows, limit, |r| ListingCursor {
+            listing_id: r.listing_id.clone(),
+        });
+        Ok(ListingPage {
+            items: rows.into_iter().map(Listing).collect(),
+            next_cursor,
+        })
+    }
+
+    async fn owner(&self, address: String) -> Result<Owner> {
+        Ok(Owner::new(&address_arg(&address, "address")?))
+    }
+
+    /// 按绑定地址查询 DID
+    async fn did(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> Result<Option<Did>> {
+        let key = DidKey(address_arg(&address, "address")?);
+        let rec = loader(ctx).load_one(key).await.map_err(load_error)?;
+        Ok(rec.map(Did))
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    api::{
+        AppState,
+        error::{AppError, ErrorCode},
+        request::ChainQuery,
+        response::{ApiResponse, ok},
+        schema::ChainParams,
+    },
+    config,
+    contract::{providers, status::IndexerSnapshot},
+    db::redis_cache::get_redis_connection,
+    metrics::observe_rpc,
+    repo::checkpoint_repo::CheckpointRecord,
+};
+use alloy::{eips::BlockNumberOrTag, providers::Provider};
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use eyre::Result;
+use redis::aio::MultiplexedConnection;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+/// 单项依赖检查的超时
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Liveness {
+    pub status: &'static str,
+    pub version: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CheckResult {
+    pub ok: bool,
+    pub latency_ms: u64,
+    /// 固定的 "unavailable" / "timeout"；具体原因只写日志，不对外暴露
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'static str>,
+}
+
+/// /ready 的结果：全部检查通过才 ready
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Readiness {
+    pub ready: bool,
+    /// postgres / redis（仅配置了 REDIS_URL 时）/ rpc（所有已配置的链）
+    pub checks: BTreeMap<&'static str, CheckResult>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ContractSync {
+    pub contract: &'static str,
+    pub address: String,
+    /// 该合约最后一条已处理日志所在区块；从未处理过为 null
+    pub last_indexed_block: Option<u64>,
+    /// 链头与 last_indexed_block 的区块差。注意：合约长时间没有事件时该值同样会增长，
+    /// 需结合 indexer.subscription 判断索引器是否仍在运行。
+    pub lag_blocks: Option<u64>,
+    /// 链头与 last_indexed_block 的出块时间差
+    pub lag_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SyncStatus {
+    pub chain_id: i64,
+    /// 链头区块；RPC 不可用时为 null
+    pub head_block: Option<u64>,
+    pub head_timestamp: Option<u64>,
+    pub indexer: IndexerSnapshot,
+    pub contracts: Vec<ContractSync>,
+}
+
+/// 计算落后程度；链头未知时为 None，检查点超过链头（RPC 节点落后）时按 0 计。
+pub fn lag(
+    head: Option<(u64, u64)>,
+    last: Option<(u64, Option<u64>)>,
+) -> (Option<u64>, Option<u64>) {
+    let (Some((head_block, head_ts)), Some((last_block, last_ts))) =
+        (head, last)
+    else {
+        return (None, None);
+    };
+    (
+        Some(head_block.saturating_sub(last_block)),
+        last_ts.map(|ts| head_ts.saturating_sub(ts)),
+    )
+}
+
+/// 执行一项检查并计时。失败原因（可能含主机名、账号等）只记日志，响应中只给出固定的描述。
+pub async fn timed<F>(check: &'static str, fut: F) -> CheckResult
+where
+    F: Future<Output = Result<()>>,
+{
+    let started = Instant::now();
+    let res = tokio::time::timeout(CHECK_TIMEOUT, fut).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let error = match res {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => {
+            tracing::warn!(check, error = ?e, "Readiness check failed");
+            Some("unavailable")
+        }
+        Err(_) => {
+            tracing::warn!(
+                check,
+                timeout_secs = CHECK_TIMEOUT.as_secs(),
+                "Readiness check timed out"
+            );
+            Some("timeout")
+        }
+    };
+    CheckResult {
+        ok: error.is_none(),
+        latency_ms,
+        error,
+    }
+}
+
+async fn check_rpc() -> Result<()> {
+    let Some(pool) = providers::try_get_pool() else {
+        eyre::bail!("rpc provider not initialized");
+    };
+    for chain in &config::get().chains {
+        let Some(provider) = pool.ws_reader_for(chain.chain_id) else {
+            eyre::bail!("chain {}: provider not initialized", chain.chain_id);
+        };
+        observe_rpc("eth_blockNumber", provider.get_block_number())
+            .await
+            .map_err(|e| eyre::eyre!("chain {}: {e}", chain.chain_id))?;
+    }
+    Ok(())
+}
+
+/// /ready 复用的 Redis 连接：首次探测时建立，PING 失败后丢弃，下次探测重新连接
+static REDIS_PROBE: Mutex<Option<MultiplexedConnection>> =
+    Mutex::const_new(None);
+
+async fn check_redis(url: &str) -> Result<()> {
+    let mut conn = {
+        let mut cached = REDIS_PROBE.lock().await;
+        match cached.as_ref() {
+            Some(conn) => conn.clone(),
+            None => cached.insert(get_redis_connection(url).await?).clone(),
+        }
+    };
+    let pong: redis::RedisResult<String> =
+        redis::cmd("PING").query_async(&mut conn).await;
+    if pong.is_err() {
+        REDIS_PROBE.lock().await.take();
+    }
+    pong?;
+    Ok(())
+}
+
+/// GET /health：进程存活即返回 200，不检查依赖（liveness probe）
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "进程存活", body = ApiResponse<Liveness>))
+)]
+pub async fn health() -> Response {
+    ok(Liveness {
+        status: "ok",
+        version: env!("CARGO_PKG_VERSION"),
+    })
+}
+
+/// GET /ready：并发检查 Postgres、Redis（若配置）与 RPC，任一失败返回 503（readiness probe）
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "全部依赖可达", body = ApiResponse<Readiness>),
+        (status = 503, description = "存在不可达的依赖；data 中给出各项结果", body = ApiResponse<Readiness>)
+    )
+)]
+pub async fn ready(State(state): State<AppState>) -> Response {
+    let pool = state.api.db.pool().clone();
+    let redis_url = config::get().redis_url.clone();
+    let (pg, redis, rpc) = tokio::join!(
+        timed("postgres", async move {
+            sqlx::query("SELECT 1").execute(&pool).await?;
+            Ok(())
+        }),
+        async {
+            match redis_url.as_deref() {
+                Some(url) => Some(timed("redis", check_redis(url)).await),
+                None => None,
+            }
+        },
+        timed("rpc", check_rpc()),
+    );
+    let mut checks = BTreeMap::from([("postgres", pg), ("rpc", rpc)]);
+    if let Some(r) = redis {
+        checks.insert("redis", r);
+    }
+    let ready = checks.values().all(|c| c.ok);
+    let report = Readiness { ready, checks };
+    if ready {
+        return ok(report);
+    }
+    let mut body = ApiResponse::error(ErrorCode::NotReady, None);
+    body.data = Some(report);
+    (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
+}
+
+/// GET /status/sync：某条链（`?chain_id=`，默认链）的链头、各合约最后索引区块、落后区块数/秒数与订阅状态
+#[utoipa::path(
+    get,
+    path = "/status/sync",
+    tag = "health",
+    params(ChainParams),
+    responses(
+        (status = 200, description = "索引同步状态", body = ApiResponse<SyncStatus>),
+        (status = 400, description = "chain_id is not configured", body = ApiResponse<serde_json::Value>),
+        (status = 500, description = "database error", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn sync_status(
+    State(state): State<AppState>,
+    ChainQuery(chain_id): ChainQuery,
+) -> Response {
+    let checkpoints: Vec<CheckpointRecord> =
+        match state.api.repos.events.list_checkpoints().await {
+            Ok(c) => c.into_iter().filter(|c| c.chain_id == chain_id).collect(),
+            Err(e) => return AppError::from(e).to_response(),
+        };
+    let by_name: HashMap<&str, &CheckpointRecord> = checkpoints
+        .iter()
+        .map(|c| (c.contract.as_str(), c))
+        .collect();
+    let config = config::get();
+    let configured = config
+        .chain(chain_id)
+        .map(|c| c.addresses.contracts())
+        .unwrap_or_default();
+
+    // 链头及各检查点区块的出块时间；RPC 不可用时只返回区块号
+    let provider =
+        providers::try_get_pool().and_then(|p| p.ws_reader_for(chain_id));
+    let mut head = None;
+    let mut block_times: HashMap<u64, u64> = HashMap::new();
+    if let Some(p) = &provider {
+        let latest = tokio::time::timeout(
+            CHECK_TIMEOUT,
+            observe_rpc(
+                "eth_getBlockByNumber",
+                p.get_block_by_number(BlockNumberOrTag::Latest),
+            ),
+        )
+        .await;
+        if let Ok(Ok(Some(b))) = latest {
+            head = Some((b.header.number, b.header.timestamp));
+        }
+        for c in &checkpoints {
+            let n = c.last_block as u64;
+            if head.is_none() || block_times.contains_key(&n) {
+                continue;
+            }
+            let block = tokio::time::timeout(
+                CHECK_TIMEOUT,
+                observe_rpc(
+                    "eth_getBlockByNumber",
+                    p.get_block_by_number(BlockNumberOrTag::Number(n)),
+                ),
+            )
+            .await;
+            if let Ok(Ok(Some(b))) = block {
+                block_times.insert(n, b.header.timestamp);
+            }
+        }
+    }
+
+    let contracts = configured
+        .into_iter()
+        .map(|(name, addr)| {
+            let last = by_name
+                .get(name)
+                .map(|c| c.last_block as u64)
+                .map(|n| (n, block_times.get(&n).copied()));
+            let (lag_blocks, lag_seconds) = lag(head, last);
+            ContractSync {
+                contract: name,
+                address: format!("0x{}", hex::encode(addr.as_slice())),
+                last_indexed_block: last.map(|(n, _)| n),
+                lag_blocks,
+                lag_seconds,
+            }
+        })
+        .collect();
+    ok(SyncStatus {
+        chain_id,
+        head_block: head.map(|(n, _)| n),
+        head_timestamp: head.map(|(_, ts)| ts),
+        indexer: state.api.indexer.snapshot(chain_id),
+        contracts,
+    })
+}
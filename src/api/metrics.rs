@@ -0,0 +1,95 @@
+use crate::{
+    api::{AppState, health::CHECK_TIMEOUT},
+    config,
+    contract::providers,
+    metrics::{self, observe_rpc},
+};
+use alloy::providers::Provider;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header::CONTENT_TYPE,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use prometheus::{Encoder, TextEncoder};
+use std::time::Instant;
+
+/// HTTP 指标中间件：route 取路由模板（如 /show/{id}），未匹配的请求归为 "unmatched"，避免标签基数失控。
+pub async fn track_http(req: Request, next: Next) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = req.method().clone();
+    let started = Instant::now();
+    let res = next.run(req).await;
+    let status = res.status().as_u16().to_string();
+    let labels = [method.as_str(), route.as_str(), status.as_str()];
+    let m = metrics::get();
+    m.http_requests.with_label_values(&labels).inc();
+    m.http_duration
+        .with_label_values(&labels)
+        .observe(started.elapsed().as_secs_f64());
+    res
+}
+
+/// 刷新各链链头与各合约索引进度；RPC 或数据库不可用时保留上次的值
+async fn sample_indexer(state: &AppState) {
+    let m = metrics::get();
+    let checkpoints = match state.api.repos.events.list_checkpoints().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(error = ?e, "metrics: failed to read checkpoints");
+            Vec::new()
+        }
+    };
+    for c in &checkpoints {
+        m.indexer_last_block
+            .with_label_values(&[&c.chain_id.to_string(), c.contract.as_str()])
+            .set(c.last_block);
+    }
+    let Some(pool) = providers::try_get_pool() else {
+        return;
+    };
+    for chain in &config::get().chains {
+        let Some(provider) = pool.ws_reader_for(chain.chain_id) else {
+            continue;
+        };
+        let head = tokio::time::timeout(
+            CHECK_TIMEOUT,
+            observe_rpc("eth_blockNumber", provider.get_block_number()),
+        )
+        .await;
+        let Ok(Ok(head)) = head else {
+            continue;
+        };
+        let head = head as i64;
+        let chain_label = chain.chain_id.to_string();
+        m.chain_head_block
+            .with_label_values(&[&chain_label])
+            .set(head);
+        for c in checkpoints.iter().filter(|c| c.chain_id == chain.chain_id) {
+            m.indexer_lag_blocks
+                .with_label_values(&[&chain_label, c.contract.as_str()])
+                .set((head - c.last_block).max(0));
+        }
+    }
+}
+
+/// GET /metrics：Prometheus 文本格式
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "meta",
+    responses((status = 200, description = "Prometheus text exposition format", content_type = "text/plain", body = String))
+)]
+pub async fn metrics_handler(State(state): State<AppState>) -> Response {
+    sample_indexer(&state).await;
+    let body = metrics::get().render();
+    (
+        [(CONTENT_TYPE, TextEncoder::new().format_type().to_string())],
+        body,
+    )
+        .into_response()
+}
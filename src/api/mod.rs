@@ -1,12 +1,30 @@
-use crate::db::Db;
+use crate::contract::status::IndexerStatus;
+use crate::db::{Db, cache::AppCache};
+use crate::realtime::EventHub;
+use crate::repo::traits::Repos;
+use crate::shutdown::Shutdown;
+pub mod admin;
+pub mod auth;
 pub mod error;
+pub mod graphql;
+pub mod health;
+pub mod metrics;
+pub mod openapi;
+pub mod rate_limit;
 pub mod request;
 pub mod response;
 pub mod schema;
+pub mod show_jobs;
 pub mod show_manager;
+pub mod stream;
+pub mod ticket_manager;
+pub mod validation;
+pub mod webhooks;
 use crate::config;
+use axum::extract::FromRef;
 use axum::http::HeaderName;
 use axum::http::{HeaderValue, Method};
+use axum::routing::{MethodRouter, delete, get, post};
 use bytes::Bytes;
 use eyre::Result;
 #[cfg(feature = "otel")]
@@ -29,6 +47,18 @@ use tracing_subscriber::{EnvFilter, Registry, layer::SubscriberExt};
 #[derive(Debug, Clone)]
 pub struct ApiContext {
     pub db: Db,
+    /// shows / tickets / 索引事件的仓储；测试中可替换为 `Repos::memory()`
+    pub repos: Repos,
+    pub show_jobs: show_jobs::ShowJobQueue,
+    /// CACHE_BACKEND=none 时为 None，读路径直接查库
+    pub cache: Option<AppCache>,
+    /// 索引器推送的实时事件，供 SSE / WebSocket 订阅
+    pub events: EventHub,
+    pub graphql: graphql::GraphqlSchema,
+    /// 关闭时结束 SSE / WebSocket 长连接，使优雅关闭不必等到超时
+    pub shutdown: Shutdown,
+    /// 与索引器共享的订阅状态，供 /status/sync 读取
+    pub indexer: IndexerStatus,
 }
 
 #[derive(Debug, Clone)]
@@ -36,31 +66,113 @@ pub struct AppState {
     pub api: ApiContext,
 }
 
-// 全局缓存 request-id header 名称，避免多处重复读取 env。
+// 全局缓存 request-id header 名称，避免多处重复读取配置。
 static REQ_ID_HEADER: OnceLock<HeaderName> = OnceLock::new();
 
 pub fn request_id_header() -> &'static HeaderName {
     REQ_ID_HEADER.get_or_init(|| {
-        std::env::var("REQ_ID_HEADER")
-            .ok()
+        config::var("REQ_ID_HEADER")
             .and_then(|s| s.parse().ok())
             .unwrap_or_else(|| HeaderName::from_static("x-request-id"))
     })
 }
 
-pub async fn listen_app() -> Result<()> {
+/// 全部路由：(路径, 方法)。外层 state 只要求能取出 AppState，
+/// 测试可以借此在不连接数据库的情况下构造路由并与 OpenAPI 文档比对。
+pub fn routes<S>() -> Vec<(&'static str, MethodRouter<S>)>
+where
+    S: Clone + Send + Sync + 'static,
+    AppState: FromRef<S>,
+{
+    vec![
+        (
+            "/show/{id}",
+            get(show_manager::show_with_id)
+                .put(show_manager::update_show)
+                .patch(show_manager::patch_show)
+                .delete(show_manager::delete_show),
+        ),
+        ("/show", post(show_manager::create_show)),
+        ("/show/jobs/{id}", get(show_manager::show_job_with_id)),
+        ("/shows", get(show_manager::list_shows)),
+        ("/ticket/{id}", get(ticket_manager::ticket_with_id)),
+        ("/tickets", get(ticket_manager::list_tickets)),
+        ("/stream/shows/{id}", get(stream::show_events_sse)),
+        ("/ws", get(stream::ws_handler)),
+        (
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        ),
+        ("/webhooks/{id}", delete(webhooks::delete_webhook)),
+        ("/webhooks/{id}/deliveries", get(webhooks::list_deliveries)),
+        ("/transfers", get(ticket_manager::list_transfers)),
+        ("/admin/audit", get(admin::list_audit_log)),
+        ("/graphql", post(graphql::graphql_handler)),
+        ("/openapi.json", get(openapi::openapi_json)),
+        ("/health", get(health::health)),
+        ("/ready", get(health::ready)),
+        ("/status/sync", get(health::sync_status)),
+        ("/metrics", get(metrics::metrics_handler)),
+    ]
+}
+
+pub fn router<S>() -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    AppState: FromRef<S>,
+{
+    routes()
+        .into_iter()
+        .fold(axum::Router::new(), |r, (path, method)| {
+            r.route(path, method)
+        })
+        .fallback(error::route_not_found)
+}
+
+/// 在 listener 上提供服务，直到 `shutdown` 触发：之后不再接受新连接，等待在途请求完成后返回。
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    app: axum::Router,
+    shutdown: Shutdown,
+) -> Result<()> {
+    // 保留对端地址，供限流按 IP 计数
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move { shutdown.wait().await })
+    .await?;
+    Ok(())
+}
+
+/// `cache`、`events` 与 `indexer` 与索引器共享同一实例：进程内 LRU / 广播时索引器的作废与推送才能对 API 生效。
+pub async fn listen_app(
+    cache: Option<AppCache>,
+    events: EventHub,
+    indexer: IndexerStatus,
+    shutdown: Shutdown,
+) -> Result<()> {
     init_tracing();
+    let db = Db::connect(config::get().database_url.as_str(), 5).await?;
+    crate::metrics::register_pool("api", db.pool().clone());
+    let repos = Repos::postgres(db.pool().clone());
+    let (show_jobs, show_job_worker) =
+        show_jobs::ShowJobQueue::start(repos.jobs.clone(), shutdown.clone())
+            .await?;
     let state = AppState {
         api: ApiContext {
-            db: Db::connect(config::get().database_url.as_str(), 5).await?,
+            repos,
+            db,
+            show_jobs,
+            cache,
+            events,
+            graphql: graphql::GraphqlSchema::from_config(),
+            shutdown: shutdown.clone(),
+            indexer,
         },
     };
-    let log_headers = std::env::var("LOG_HTTP_HEADERS")
-        .ok()
-        .map_or(false, |v| v == "1");
-    let log_body = std::env::var("LOG_HTTP_BODY")
-        .ok()
-        .map_or(false, |v| v == "1");
+    let log_headers = config::get().logging.http_headers;
+    let log_body = config::get().logging.http_body;
     let req_id_header = request_id_header().clone();
     let req_id_for_span = req_id_header.clone();
     let trace = TraceLayer::new_for_http()
@@ -134,48 +246,52 @@ pub async fn listen_app() -> Result<()> {
 
     // CORS: 允许前端读取自定义 request-id 头，需要显式 expose
     let cors = {
-        // 可按需从环境读取允许来源，这里默认允许所有（开发阶段）
-        let allow_origin = std::env::var("CORS_ALLOW_ORIGIN").ok();
-        let base = if let Some(o) = allow_origin {
-            if o == "*" {
-                CorsLayer::new().allow_origin(Any)
-            } else {
-                // 支持多个以逗号分隔
-                let origins: Vec<HeaderValue> = o
-                    .split(',')
-                    .filter_map(|s| s.trim().parse().ok())
-                    .collect();
-                let mut layer = CorsLayer::new();
-                for hv in origins {
-                    layer = layer.allow_origin(hv);
-                }
-                layer
-            }
-        } else {
+        // 默认允许所有来源（开发阶段），生产环境在 [cors] allow_origins 中列出前端域名
+        let cors_cfg = &config::get().cors;
+        let base = if cors_cfg.allow_any() {
             CorsLayer::new().allow_origin(Any)
+        } else {
+            let origins: Vec<HeaderValue> = cors_cfg
+                .allow_origins
+                .iter()
+                .filter_map(|s| s.parse().ok())
+                .collect();
+            CorsLayer::new().allow_origin(origins)
         };
         base.allow_headers(Any)
             .allow_methods([
                 Method::GET,
                 Method::POST,
                 Method::PUT,
+                Method::PATCH,
                 Method::DELETE,
             ])
-            .expose_headers([request_id_header().clone()])
+            .expose_headers([
+                request_id_header().clone(),
+                axum::http::header::ETAG,
+                rate_limit::RATELIMIT_LIMIT,
+                rate_limit::RATELIMIT_REMAINING,
+                rate_limit::RATELIMIT_RESET,
+                axum::http::header::RETRY_AFTER,
+            ])
     };
 
-    let app = axum::Router::new()
-        .route(
-            "/show/{id}",
-            axum::routing::get(show_manager::show_with_id)
-                .put(show_manager::update_show)
-                .delete(show_manager::delete_show),
-        )
-        .route(
-            "/show",
-            axum::routing::post(show_manager::create_show),
-        )
-        .route("/shows", axum::routing::get(show_manager::list_shows))
+    let limiter = rate_limit::RateLimiter::from_config().await;
+    let authenticator = auth::Authenticator::from_config().await;
+    let app = router::<AppState>()
+        // 限流与指标都在路由匹配之后执行，才能取到 MatchedPath；指标在外层，429 也会被计数
+        .layer(axum::middleware::from_fn_with_state(
+            limiter,
+            rate_limit::enforce,
+        ))
+        // 认证在限流之外：限流按已认证地址计数
+        .layer(axum::middleware::from_fn_with_state(
+            authenticator,
+            auth::authenticate,
+        ))
+        .layer(axum::middleware::from_fn(metrics::track_http))
+        // 错误响应体中的 request_id 取自此处；须在 SetRequestIdLayer 之内
+        .layer(axum::middleware::from_fn(error::scope_request_id))
         // Compose layers in correct order (outermost last): Propagate (inner) -> Trace -> Set (outer)
         .layer(PropagateRequestIdLayer::new(request_id_header().clone()))
         .layer(trace)
@@ -185,9 +301,13 @@ pub async fn listen_app() -> Result<()> {
             MakeRequestUuid::default(),
         ))
         .with_state(state);
-    tracing::info!("Listening on 127.0.0.1:3000");
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
-    let _ = axum::serve(listener, app).await;
+    let addr = config::get().api_addr;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(%addr, "Listening");
+    serve(listener, app, shutdown).await?;
+    // 在途的 createShow job 处理完再返回：main 的关闭等待时限同样覆盖它
+    show_job_worker.await?;
+    tracing::info!("HTTP server drained");
     Ok(())
 }
 
@@ -195,14 +315,15 @@ fn init_tracing() {
     // 无论是否启用 otel，都设置基础 EnvFilter（若用户外部已设置则忽略错误）
     static ONCE: OnceLock<()> = OnceLock::new();
     ONCE.get_or_init(|| {
-        let filter = EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| EnvFilter::new("info"));
+        let filter = config::var("RUST_LOG")
+            .and_then(|f| EnvFilter::try_new(f).ok())
+            .unwrap_or_else(|| EnvFilter::new("info"));
         #[cfg(feature = "otel")]
         {
-            let service_name = std::env::var("OTEL_SERVICE_NAME")
-                .unwrap_or_else(|_| "ticket-backend".into());
-            let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
-                .unwrap_or_else(|_| "http://127.0.0.1:4317".into());
+            let service_name = config::var("OTEL_SERVICE_NAME")
+                .unwrap_or_else(|| "ticket-backend".into());
+            let endpoint = config::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                .unwrap_or_else(|| "http://127.0.0.1:4317".into());
             let exporter = opentelemetry_otlp::new_exporter()
                 .tonic()
                 .with_endpoint(endpoint);
@@ -0,0 +1,106 @@
+use crate::{
+    api::{
+        admin, error::ErrorCode, graphql, health, metrics, show_manager,
+        stream, ticket_manager, webhooks,
+    },
+    realtime::RealtimeEvent,
+    repo::show_repo::{ShowSortKey, SortOrder},
+    webhook::WebhookPayload,
+};
+use axum::{
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+};
+use std::sync::OnceLock;
+use utoipa::{
+    OpenApi,
+    openapi::schema::{ObjectBuilder, Type},
+};
+
+/// ApiResponse.code 的 schema：列出全部 ErrorCode 取值
+pub fn error_code_schema() -> ObjectBuilder {
+    let description = ErrorCode::ALL
+        .iter()
+        .map(|c| format!("{}: {:?} ({})", c.code(), c, c.default_message()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    ObjectBuilder::new()
+        .schema_type(Type::Integer)
+        .format(Some(utoipa::openapi::SchemaFormat::KnownFormat(
+            utoipa::openapi::KnownFormat::Int32,
+        )))
+        .enum_values(Some(ErrorCode::ALL.iter().map(|c| c.code())))
+        .description(Some(format!("0 表示成功，其余为错误码：\n{description}")))
+}
+
+/// 由 handler 上的 `#[utoipa::path]` 与 DTO 的 ToSchema 生成；新增路由时需同步加入 paths，
+/// 否则 tests/openapi_tests.rs 会失败。
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Ticket Backend API"),
+    paths(
+        openapi_json,
+        show_manager::show_with_id,
+        show_manager::update_show,
+        show_manager::patch_show,
+        show_manager::delete_show,
+        show_manager::create_show,
+        show_manager::show_job_with_id,
+        show_manager::list_shows,
+        ticket_manager::ticket_with_id,
+        ticket_manager::list_tickets,
+        ticket_manager::list_transfers,
+        stream::show_events_sse,
+        stream::ws_handler,
+        webhooks::create_webhook,
+        webhooks::list_webhooks,
+        webhooks::delete_webhook,
+        webhooks::list_deliveries,
+        admin::list_audit_log,
+        graphql::graphql_handler,
+        health::health,
+        health::ready,
+        health::sync_status,
+        metrics::metrics_handler,
+    ),
+    components(schemas(
+        RealtimeEvent,
+        WebhookPayload,
+        show_manager::ShowStatusFilter,
+        ShowSortKey,
+        SortOrder
+    )),
+    tags(
+        (name = "shows", description = "演出：查询、创建（异步上链）与维护"),
+        (name = "tickets", description = "票据与转移记录（索引器投影）"),
+        (name = "stream", description = "实时事件：SSE 与 WebSocket"),
+        (name = "webhooks", description = "出站 webhook 订阅与投递日志"),
+        (name = "admin", description = "管理接口（需 ADMIN_TOKEN）：审计日志"),
+        (name = "graphql", description = "GraphQL 只读查询（shows / tickets / owners / listings / DIDs）"),
+        (name = "health", description = "存活、就绪与索引同步状态（供编排系统探针使用）"),
+        (name = "meta", description = "API 描述与 Prometheus 指标"),
+    )
+)]
+pub struct ApiDoc;
+
+/// 生成后的 JSON 只需序列化一次
+static SPEC_JSON: OnceLock<String> = OnceLock::new();
+
+pub fn spec_json() -> &'static str {
+    SPEC_JSON.get_or_init(|| {
+        ApiDoc::openapi()
+            .to_pretty_json()
+            .expect("openapi spec must serialize")
+    })
+}
+
+/// OpenAPI 3 文档
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    tag = "meta",
+    responses((status = 200, description = "OpenAPI 3 document", content_type = "application/json"))
+)]
+pub async fn openapi_json() -> Response {
+    ([(CONTENT_TYPE, "application/json")], spec_json()).into_response()
+}
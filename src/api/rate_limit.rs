@@ -0,0 +1,404 @@
+use super::{auth::AuthenticatedAddress, error::AppError};
+use crate::db::redis_cache::get_redis_connection;
+use async_trait::async_trait;
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, Method, header::RETRY_AFTER},
+    middleware::Next,
+    response::Response,
+};
+use eyre::Result;
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const RATELIMIT_LIMIT: HeaderName =
+    HeaderName::from_static("ratelimit-limit");
+pub const RATELIMIT_REMAINING: HeaderName =
+    HeaderName::from_static("ratelimit-remaining");
+pub const RATELIMIT_RESET: HeaderName =
+    HeaderName::from_static("ratelimit-reset");
+
+/// 不限流的路由：探针与指标抓取不应被自身的流量打满
+const EXEMPT_ROUTES: [&str; 3] = ["/health", "/ready", "/metrics"];
+
+/// 列表接口按 limit 计费：每 PAGE_COST_UNIT 行算一次请求，`limit=1000` 消耗 10 次额度
+const PAGE_COST_UNIT: u64 = 100;
+
+/// 单条路由的独立预算，格式 `[METHOD ]PATH=N`，如 `POST /show=10`、`/graphql=60`；
+/// 不写方法时对该路由的所有方法生效。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteBudget {
+    pub method: Option<Method>,
+    pub path: String,
+    pub limit: u64,
+}
+
+impl FromStr for RouteBudget {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, limit) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected [METHOD ]PATH=N, got {s:?}"))?;
+        let limit = limit
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or_else(|| {
+                format!("limit must be a positive integer in {s:?}")
+            })?;
+        let target = target.trim();
+        let (method, path) = match target.split_once(char::is_whitespace) {
+            Some((m, p)) => {
+                let m = Method::from_str(&m.to_ascii_uppercase())
+                    .map_err(|_| format!("invalid method in {s:?}"))?;
+                (Some(m), p.trim())
+            }
+            None => (None, target),
+        };
+        if !path.starts_with('/') {
+            return Err(format!("path must start with '/' in {s:?}"));
+        }
+        Ok(Self {
+            method,
+            path: path.to_string(),
+            limit,
+        })
+    }
+}
+
+/// 逗号分隔的 RouteBudget 列表
+pub fn parse_route_budgets(s: &str) -> Result<Vec<RouteBudget>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(RouteBudget::from_str)
+        .collect()
+}
+
+/// 限流配置：固定窗口，读（GET/HEAD）与写各一份默认预算，`routes` 中的路由单独计数。
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub window_secs: u64,
+    pub read_limit: u64,
+    pub write_limit: u64,
+    pub routes: Vec<RouteBudget>,
+    /// 部署在反向代理之后时取 X-Forwarded-For 的最后一个地址（由代理追加）；直连时必须关闭，否则客户端可伪造
+    pub trust_forwarded: bool,
+}
+
+impl RateLimitConfig {
+    /// 返回 (计数桶, 预算)；豁免路由返回 None
+    pub fn budget_for(
+        &self,
+        method: &Method,
+        route: &str,
+    ) -> Option<(String, u64)> {
+        if EXEMPT_ROUTES.contains(&route) || method == Method::OPTIONS {
+            return None;
+        }
+        let specific = self
+            .routes
+            .iter()
+            .filter(|b| b.path == route)
+            // 指定了方法的条目优先于通配条目
+            .find(|b| b.method.as_ref() == Some(method))
+            .or_else(|| {
+                self.routes
+                    .iter()
+                    .find(|b| b.path == route && b.method.is_none())
+            });
+        if let Some(b) = specific {
+            let bucket = match &b.method {
+                Some(m) => format!("{m} {}", b.path),
+                None => b.path.clone(),
+            };
+            return Some((bucket, b.limit));
+        }
+        if method == Method::GET || method == Method::HEAD {
+            Some(("read".to_string(), self.read_limit))
+        } else {
+            Some(("write".to_string(), self.write_limit))
+        }
+    }
+}
+
+/// 计数后端：在 key 的当前窗口内累加 cost，返回累加后的计数
+#[async_trait]
+pub trait RateLimitStore: Send + Sync + fmt::Debug {
+    fn backend(&self) -> &'static str;
+    async fn hit(&self, key: &str, cost: u64, window_secs: u64) -> Result<u64>;
+}
+
+/// 进程内计数：每个 key 只保留当前窗口的计数，窗口切换时归零。
+#[derive(Debug, Default)]
+pub struct MemoryRateLimitStore {
+    counters: Mutex<HashMap<String, (u64, u64)>>,
+}
+
+/// 超过该数量时清理过期窗口，防止大量一次性 IP 撑大内存
+const MEMORY_PRUNE_THRESHOLD: usize = 10_000;
+
+#[async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn hit(&self, key: &str, cost: u64, window_secs: u64) -> Result<u64> {
+        let window = now_secs() / window_secs;
+        let mut counters =
+            self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        if counters.len() > MEMORY_PRUNE_THRESHOLD {
+            counters.retain(|_, (w, _)| *w == window);
+        }
+        let entry = counters.entry(key.to_string()).or_insert((window, 0));
+        if entry.0 != window {
+            *entry = (window, 0);
+        }
+        entry.1 += cost;
+        Ok(entry.1)
+    }
+}
+
+/// Redis 计数：多实例共享额度。key 带窗口序号，INCRBY 与 EXPIRE 在同一事务中执行。
+#[derive(Clone)]
+pub struct RedisRateLimitStore {
+    conn: redis::aio::MultiplexedConnection,
+}
+
+impl fmt::Debug for RedisRateLimitStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisRateLimitStore")
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedisRateLimitStore {
+    pub async fn connect(redis_url: &str) -> Result<Self> {
+        Ok(Self {
+            conn: get_redis_connection(redis_url).await?,
+        })
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn hit(&self, key: &str, cost: u64, window_secs: u64) -> Result<u64> {
+        let window = now_secs() / window_secs;
+        let key = format!("ratelimit:{key}:{window}");
+        let mut conn = self.conn.clone();
+        let (count,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(&key, cost)
+            .expire(&key, window_secs as i64)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(count)
+    }
+}
+
+/// 单次判定结果，用于生成 RateLimit-* 响应头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u64,
+    pub remaining: u64,
+    /// 距当前窗口结束的秒数
+    pub reset_secs: u64,
+}
+
+impl Decision {
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(RATELIMIT_LIMIT, HeaderValue::from(self.limit));
+        headers.insert(RATELIMIT_REMAINING, HeaderValue::from(self.remaining));
+        headers.insert(RATELIMIT_RESET, HeaderValue::from(self.reset_secs));
+        if !self.allowed {
+            headers.insert(RETRY_AFTER, HeaderValue::from(self.reset_secs));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: Arc<RateLimitConfig>,
+    store: Arc<dyn RateLimitStore>,
+    /// 主存储（Redis）出错时使用，保证限流不因 Redis 故障而整体失效
+    fallback: Arc<MemoryRateLimitStore>,
+}
+
+impl RateLimiter {
+    pub fn new(
+        config: RateLimitConfig,
+        store: Arc<dyn RateLimitStore>,
+    ) -> Self {
+        Self {
+            config: Arc::new(config),
+            store,
+            fallback: Arc::new(MemoryRateLimitStore::default()),
+        }
+    }
+
+    pub fn memory(config: RateLimitConfig) -> Self {
+        Self::new(config, Arc::new(MemoryRateLimitStore::default()))
+    }
+
+    /// 按全局配置构造；配置了 REDIS_URL 时使用 Redis 计数，连接失败退回进程内计数。
+    pub async fn from_config() -> Self {
+        let cfg = crate::config::get();
+        let config = cfg.rate_limit.clone();
+        let limiter = match cfg.redis_url.as_deref() {
+            Some(url) => match RedisRateLimitStore::connect(url).await {
+                Ok(store) => Self::new(config, Arc::new(store)),
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Redis unavailable, rate limiting per instance");
+                    Self::memory(config)
+                }
+            },
+            None => Self::memory(config),
+        };
+        tracing::info!(
+            enabled = limiter.config.enabled,
+            backend = limiter.store.backend(),
+            "Rate limiting configured"
+        );
+        limiter
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// 为 client 在 bucket 中计入 cost 次请求
+    pub async fn check(
+        &self,
+        bucket: &str,
+        client: &str,
+        limit: u64,
+        cost: u64,
+    ) -> Decision {
+        let window = self.config.window_secs;
+        let key = format!("{bucket}:{client}");
+        let count = match self.store.hit(&key, cost, window).await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!(error = ?e, backend = self.store.backend(), "rate limit store failed, using in-memory counters");
+                self.fallback.hit(&key, cost, window).await.unwrap_or(0)
+            }
+        };
+        Decision {
+            allowed: count <= limit,
+            limit,
+            remaining: limit.saturating_sub(count),
+            reset_secs: window - now_secs() % window,
+        }
+    }
+
+    /// 限流身份：客户端 IP 总是计数；`auth::authenticate` 校验过的签名地址额外计数。
+    /// 地址由调用方自行生成，不能替代 IP，否则每次换一把新私钥即可绕过限流。
+    pub fn client_keys(&self, req: &Request) -> Vec<String> {
+        let mut keys = vec![self.client_ip_key(req)];
+        if let Some(AuthenticatedAddress(addr)) = req.extensions().get() {
+            keys.push(format!("addr:{addr:#x}"));
+        }
+        keys
+    }
+
+    /// X-Forwarded-For 只取最后一个地址：它由受信任的代理追加，之前的条目都可能由客户端伪造
+    fn client_ip_key(&self, req: &Request) -> String {
+        if self.config.trust_forwarded {
+            let forwarded = req
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.rsplit(',').next())
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            if let Some(ip) = forwarded {
+                return format!("ip:{ip}");
+            }
+        }
+        match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+            None => "ip:unknown".to_string(),
+        }
+    }
+}
+
+/// 列表请求的计费：`limit` 越大消耗越多，避免用大页绕过额度
+pub fn request_cost(req: &Request) -> u64 {
+    if req.method() != Method::GET {
+        return 1;
+    }
+    let limit = req.uri().query().and_then(|q| {
+        q.split('&')
+            .filter_map(|kv| kv.split_once('='))
+            .find(|(k, _)| *k == "limit")
+            .and_then(|(_, v)| v.parse::<u64>().ok())
+    });
+    limit.map_or(1, |l| l.div_ceil(PAGE_COST_UNIT).max(1))
+}
+
+/// 限流中间件：需在路由匹配之后执行以取得 MatchedPath。超限时返回 429，
+/// 响应体为 ApiResponse 错误信封，并带 RateLimit-* 与 Retry-After 头。
+pub async fn enforce(
+    State(limiter): State<RateLimiter>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !limiter.config.enabled {
+        return next.run(req).await;
+    }
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let Some((bucket, limit)) = limiter.config.budget_for(req.method(), &route)
+    else {
+        return next.run(req).await;
+    };
+    let cost = request_cost(&req);
+    let mut decision: Option<(String, Decision)> = None;
+    for client in limiter.client_keys(&req) {
+        let d = limiter.check(&bucket, &client, limit, cost).await;
+        // 任一身份超限即拒绝；响应头取最紧的那一份
+        if decision.as_ref().is_none_or(|(_, cur)| {
+            (d.allowed, d.remaining) < (cur.allowed, cur.remaining)
+        }) {
+            decision = Some((client, d));
+        }
+    }
+    let (client, decision) = decision.expect("at least the IP key is checked");
+    let mut res = if decision.allowed {
+        next.run(req).await
+    } else {
+        tracing::info!(%bucket, %client, "rate limit exceeded");
+        AppError::RateLimited(format!(
+            "{bucket}: {limit} requests per {}s",
+            limiter.config.window_secs
+        ))
+        .to_response()
+    };
+    decision.apply_headers(res.headers_mut());
+    res
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
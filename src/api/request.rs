@@ -1,6 +1,11 @@
-use super::schema::{ParamsValidateExt, Validate, ValidationError};
+use super::schema::{
+    ChainParams, CursorPagination, ParamsValidateExt, RawCursorParams,
+    Validate, ValidationError,
+};
+use crate::api::auth::AuthenticatedAddress;
 use crate::api::error::AppError;
 use crate::api::request_id_header;
+use crate::repo::audit_repo::AuditContext;
 use axum::extract::Json;
 use axum::http::HeaderMap;
 use axum::response::Response;
@@ -51,8 +56,8 @@ where
                     }
                     Err(e) => {
                         let rid = extract_request_id(&parts.headers);
-                        tracing::debug!(target="extractor", extractor="ValidatedQuery", error=%e.0, request_id = rid.unwrap_or(""), "query validation failed");
-                        Err(AppError::QueryInvalid(e.0).to_response())
+                        tracing::debug!(target="extractor", extractor="ValidatedQuery", error=%e, request_id = rid.unwrap_or(""), "query validation failed");
+                        Err(AppError::QueryInvalid(e).to_response())
                     }
                 },
                 Err(_) => {
@@ -71,6 +76,140 @@ where
     }
 }
 
+/// 通用：游标分页参数（`?cursor=&limit=`），cursor 解码为类型 C。
+/// 可与 ValidatedQuery 同时使用：后者负责过滤条件，两者读取同一 query string。
+pub struct CursorQuery<C>(pub CursorPagination<C>);
+
+impl<S, C> FromRequestParts<S> for CursorQuery<C>
+where
+    C: DeserializeOwned + Send + 'static,
+    S: Send + Sync + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let rid = extract_request_id(&parts.headers).unwrap_or("").to_string();
+        let Query(raw) =
+            Query::<RawCursorParams>::from_request_parts(parts, state)
+                .await
+                .map_err(|_| {
+                    tracing::debug!(target = "extractor", extractor = "CursorQuery", request_id = %rid, "cursor params deserialize failed");
+                    AppError::QueryInvalid("invalid query params".into())
+                        .to_response()
+                })?;
+        match CursorPagination::from_raw(raw) {
+            Ok(p) => Ok(CursorQuery(p)),
+            Err(e) => {
+                tracing::debug!(target = "extractor", extractor = "CursorQuery", error = %e, request_id = %rid, "cursor decode failed");
+                Err(AppError::QueryInvalid(e).to_response())
+            }
+        }
+    }
+}
+
+/// 通用：`?chain_id=` 解析出的链 ID；省略时为默认链，未配置的链返回 400。
+/// 与其他 Query 提取器读取同一 query string，互不影响。
+pub struct ChainQuery(pub i64);
+
+impl<S> FromRequestParts<S> for ChainQuery
+where
+    S: Send + Sync + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let rid = extract_request_id(&parts.headers).unwrap_or("").to_string();
+        let Query(params) =
+            Query::<ChainParams>::from_request_parts(parts, state)
+                .await
+                .map_err(|_| {
+                    tracing::debug!(target = "extractor", extractor = "ChainQuery", request_id = %rid, "chain_id deserialize failed");
+                    AppError::QueryInvalid(ValidationError::field(
+                        "chain_id",
+                        "must be an integer",
+                    ))
+                        .to_response()
+                })?;
+        match crate::config::get().resolve_chain(params.chain_id) {
+            Ok(chain) => Ok(ChainQuery(chain.chain_id)),
+            Err(e) => {
+                tracing::debug!(target = "extractor", extractor = "ChainQuery", error = %e, request_id = %rid, "unknown chain");
+                Err(AppError::QueryInvalid(ValidationError::field(
+                    "chain_id", e,
+                ))
+                .to_response())
+            }
+        }
+    }
+}
+
+/// 通用：`If-Match` 中的资源版本（ETag 形如 `"<version>"`，见 `response::etag`）。
+/// 未携带或为 `*` 时为 None；弱 ETag 与无法解析的值不会匹配任何版本，条件更新返回 412。
+pub struct IfMatch(pub Option<Vec<i64>>);
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(raw) = parts.headers.get(axum::http::header::IF_MATCH) else {
+            return Ok(IfMatch(None));
+        };
+        let raw = raw.to_str().unwrap_or_default().trim();
+        if raw == "*" {
+            return Ok(IfMatch(None));
+        }
+        Ok(IfMatch(Some(
+            raw.split(',').filter_map(parse_etag).collect(),
+        )))
+    }
+}
+
+/// 通用：写操作的发起方，用于审计。调用方地址来自 `auth::authenticate` 写入的 [`AuthenticatedAddress`]
+/// （请求未签名时为空），request id 来自当前请求。
+pub struct Actor(pub AuditContext);
+
+impl<S> FromRequestParts<S> for Actor
+where
+    S: Send + Sync + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let actor = parts
+            .extensions
+            .get::<AuthenticatedAddress>()
+            .map(|AuthenticatedAddress(addr)| format!("{addr:#x}"));
+        let request_id = extract_request_id(&parts.headers)
+            .filter(|rid| !rid.is_empty())
+            .map(str::to_string);
+        Ok(Actor(AuditContext { actor, request_id }))
+    }
+}
+
+/// 解析强 ETag `"<version>"`
+pub fn parse_etag(tag: &str) -> Option<i64> {
+    tag.trim()
+        .strip_prefix('"')?
+        .strip_suffix('"')?
+        .parse()
+        .ok()
+}
+
 /// 通用：校验后的路径参数（适用于将整个 Path 反序列化为一个结构体并实现 Validate）
 pub struct ValidatedPath<T>(pub T);
 
@@ -105,8 +244,8 @@ where
                     }
                     Err(e) => {
                         let rid = extract_request_id(&parts.headers);
-                        tracing::debug!(target="extractor", extractor="ValidatedPath", error=%e.0, request_id = rid.unwrap_or(""), "path validation failed");
-                        Err(AppError::Validation(e.0).to_response())
+                        tracing::debug!(target="extractor", extractor="ValidatedPath", error=%e, request_id = rid.unwrap_or(""), "path validation failed");
+                        Err(AppError::Validation(e).to_response())
                     }
                 },
                 Err(_) => {
@@ -179,19 +318,19 @@ where
                 }
                 Err(e) => {
                     let rid = extract_request_id(&headers_snapshot);
-                    tracing::debug!(target="extractor", extractor="ValidatedJson", error=%e.0, request_id = rid.unwrap_or(""), "json validation failed");
-                    Err(AppError::JsonInvalid(e.0).to_response())
+                    tracing::debug!(target="extractor", extractor="ValidatedJson", error=%e, request_id = rid.unwrap_or(""), "json validation failed");
+                    Err(AppError::JsonInvalid(e).to_response())
                 }
             }
         }
     }
 }
 
-// ==== 特殊：ShowId Path 处理（十进制 / 0x 十六进制）====
+// ==== 特殊：U256 id Path 处理（十进制 / 0x 十六进制），解析失败时错误消息带上资源名 ====
 
 #[macro_export]
 macro_rules! path_u256_extractor {
-    ($name:ident) => {
+    ($name:ident, $resource:literal) => {
         pub struct $name(pub crate::utils::uint256::DbU256);
         impl<S> axum::extract::FromRequestParts<S> for $name
         where
@@ -208,7 +347,7 @@ macro_rules! path_u256_extractor {
                             Ok(id) => Ok($name(id)),
                             Err(e) => {
                                 tracing::debug!(target="extractor", extractor=stringify!($name), error=%e, "u256 parse failed");
-                                Err(crate::api::error::AppError::ParseIdInvalid(e.to_string()).to_response())
+                                Err(crate::api::error::AppError::ParseIdInvalid($resource, e.to_string()).to_response())
                             }
                         },
                         Err(_) => {
@@ -222,15 +361,15 @@ macro_rules! path_u256_extractor {
     };
 }
 
-path_u256_extractor!(PathShowId);
-path_u256_extractor!(PathIdU256);
+path_u256_extractor!(PathShowId, "show");
+path_u256_extractor!(PathIdU256, "ticket");
 #[macro_export]
 macro_rules! json_validated {
     ($json_pat:ident) => {{
         match $json_pat.validate() {
             Ok(v) => v,
             Err(e) => {
-                return crate::api::error::AppError::JsonInvalid(e.0)
+                return crate::api::error::AppError::JsonInvalid(e)
                     .to_response();
             }
         }
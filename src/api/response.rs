@@ -1,17 +1,30 @@
 use super::error::ErrorCode;
+use super::schema::FieldError;
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header::ETAG},
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize)]
+/// 统一响应信封：code 为 0 表示成功，否则为 ErrorCode
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiResponse<T: Serialize> {
+    #[schema(schema_with = super::openapi::error_code_schema)]
     pub code: i32,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<T>,
+    /// 游标分页：存在下一页时返回，作为下次请求的 `?cursor=`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// 校验失败时逐字段的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Vec<FieldError>>,
+    /// 错误响应携带的 request id，与响应头中的 request id 一致，便于排查
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl<T: Serialize> ApiResponse<T> {
@@ -20,6 +33,15 @@ impl<T: Serialize> ApiResponse<T> {
             code: ErrorCode::Ok.code(),
             message: ErrorCode::Ok.default_message().to_string(),
             data: Some(data),
+            next_cursor: None,
+            details: None,
+            request_id: None,
+        }
+    }
+    pub fn page(data: T, next_cursor: Option<String>) -> Self {
+        Self {
+            next_cursor,
+            ..Self::success(data)
         }
     }
     pub fn error(code: ErrorCode, msg: Option<String>) -> Self {
@@ -27,6 +49,9 @@ impl<T: Serialize> ApiResponse<T> {
             code: code.code(),
             message: msg.unwrap_or_else(|| code.default_message().to_string()),
             data: None,
+            next_cursor: None,
+            details: None,
+            request_id: None,
         }
     }
 }
@@ -36,35 +61,23 @@ pub fn ok<T: Serialize>(data: T) -> Response {
     Json(ApiResponse::success(data)).into_response()
 }
 
-pub fn bad_request(msg: impl Into<String>) -> Response {
-    (
-        StatusCode::BAD_REQUEST,
-        Json(ApiResponse::<serde_json::Value>::error(
-            ErrorCode::Validation,
-            Some(msg.into()),
-        )),
-    )
-        .into_response()
+/// 列表分页响应：data + next_cursor
+pub fn ok_page<T: Serialize>(data: T, next_cursor: Option<String>) -> Response {
+    Json(ApiResponse::page(data, next_cursor)).into_response()
+}
+
+/// 202：请求已受理，异步处理中（例如上链 job）
+pub fn accepted<T: Serialize>(data: T) -> Response {
+    (StatusCode::ACCEPTED, Json(ApiResponse::success(data))).into_response()
 }
 
-pub fn not_found(msg: impl Into<String>) -> Response {
-    (
-        StatusCode::NOT_FOUND,
-        Json(ApiResponse::<serde_json::Value>::error(
-            ErrorCode::ShowNotFound,
-            Some(msg.into()),
-        )),
-    )
-        .into_response()
+/// 资源版本对应的强 ETag：`"<version>"`
+pub fn etag(version: i64) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{version}\""))
+        .expect("etag is a valid header value")
 }
 
-pub fn internal_error(msg: impl Into<String>) -> Response {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(ApiResponse::<serde_json::Value>::error(
-            ErrorCode::Internal,
-            Some(msg.into()),
-        )),
-    )
-        .into_response()
+/// 200 + ETag 头（支持 If-Match 条件更新的资源）
+pub fn ok_with_etag<T: Serialize>(data: T, version: i64) -> Response {
+    ([(ETAG, etag(version))], Json(ApiResponse::success(data))).into_response()
 }
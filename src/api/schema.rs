@@ -1,6 +1,8 @@
 use axum::http::StatusCode;
-use serde::Deserialize;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{fmt, result::Result as StdResult};
+use utoipa::{IntoParams, ToSchema};
 
 pub const DEFAULT_LIMIT: i64 = 20;
 pub const MAX_LIMIT: i64 = 1000;
@@ -17,12 +19,86 @@ fn default_limit() -> i64 {
     DEFAULT_LIMIT
 }
 
-#[derive(Debug, Clone)]
-pub struct ValidationError(pub String);
+/// 单个字段的校验失败，随 400 响应的 `details` 返回
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// 校验失败：message 为概要，fields 为逐字段的原因（可为空，如整体格式错误）
+#[derive(Debug, Clone, Default)]
+pub struct ValidationError {
+    pub message: String,
+    pub fields: Vec<FieldError>,
+}
+
+impl ValidationError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// 单个字段不合法
+    pub fn field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::default().with_field(field, message)
+    }
+
+    /// 追加一个字段错误；概要为各字段错误以 `; ` 连接
+    pub fn with_field(
+        mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.add(field, message);
+        self
+    }
+
+    pub fn add(
+        &mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        let field = field.into();
+        let message = message.into();
+        if !self.message.is_empty() {
+            self.message.push_str("; ");
+        }
+        self.message.push_str(&format!("{field}: {message}"));
+        self.fields.push(FieldError { field, message });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.message.is_empty() && self.fields.is_empty()
+    }
+
+    /// 没有收集到错误时返回 `Ok(value)`
+    pub fn into_result<T>(self, value: T) -> StdResult<T, Self> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl From<String> for ValidationError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for ValidationError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
 
 impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        f.write_str(&self.message)
     }
 }
 
@@ -50,6 +126,80 @@ impl Validate for Pagination {
     }
 }
 
+/// 链选择参数（`?chain_id=`）；省略时使用默认链（配置中的第一条链）。
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChainParams {
+    /// 链 ID，必须是已配置的链
+    pub chain_id: Option<i64>,
+}
+
+/// Keyset（游标）分页请求：`?cursor=<opaque>&limit=`；cursor 为空表示第一页。
+#[derive(Debug, Clone)]
+pub struct CursorPagination<C> {
+    pub cursor: Option<C>,
+    pub limit: i64,
+}
+
+/// 游标分页的原始查询参数（cursor 解码前）
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RawCursorParams {
+    /// 上一页返回的 next_cursor
+    pub cursor: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+impl<C: DeserializeOwned> CursorPagination<C> {
+    pub fn from_raw(raw: RawCursorParams) -> StdResult<Self, ValidationError> {
+        let limit = raw.limit.clamp(1, MAX_LIMIT);
+        let cursor = match raw.cursor.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(s) => Some(decode_cursor(s)?),
+        };
+        Ok(Self { cursor, limit })
+    }
+}
+
+impl<C> CursorPagination<C> {
+    /// 多取一条用于判断是否存在下一页
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+/// 将游标编码为不透明字符串（JSON + base64url）
+pub fn encode_cursor<C: Serialize>(cursor: &C) -> String {
+    let json = serde_json::to_vec(cursor).expect("cursor must serialize");
+    URL_SAFE_NO_PAD.encode(json)
+}
+
+pub fn decode_cursor<C: DeserializeOwned>(
+    s: &str,
+) -> StdResult<C, ValidationError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|_| ValidationError::field("cursor", "invalid cursor"))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|_| ValidationError::field("cursor", "invalid cursor"))
+}
+
+/// 截断到 limit 条并在还有更多数据时生成 next_cursor（rows 应按 fetch_limit 查询）。
+pub fn into_page<T, C: Serialize>(
+    mut rows: Vec<T>,
+    limit: i64,
+    key: impl Fn(&T) -> C,
+) -> (Vec<T>, Option<String>) {
+    let limit = usize::try_from(limit).unwrap_or(0);
+    if rows.len() <= limit {
+        return (rows, None);
+    }
+    rows.truncate(limit);
+    let next = rows.last().map(|last| encode_cursor(&key(last)));
+    (rows, next)
+}
+
 // 便捷扩展：任何实现了 Validate<Err=ValidationError> 的参数都可直接 .validated()
 pub trait ParamsValidateExt: Sized {
     fn validated(self) -> StdResult<Self, ValidationError>;
@@ -67,6 +217,6 @@ where
 // 可选：标准化错误消息（若未来用于统一 400 响应）
 impl From<ValidationError> for (StatusCode, String) {
     fn from(err: ValidationError) -> Self {
-        (StatusCode::BAD_REQUEST, err.0)
+        (StatusCode::BAD_REQUEST, err.message)
     }
 }
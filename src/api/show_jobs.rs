@@ -0,0 +1,333 @@
+use crate::{
+    config,
+    contract::{
+        contracts::show_manager::{broadcast_create_show, sign_create_show},
+        providers::{self, WsProvider},
+    },
+    repo::{
+        show_job_repo::{ShowCreateJobRecord, ShowJobStatus},
+        traits::ShowJobRepo,
+    },
+    shutdown::Shutdown,
+};
+use alloy::{
+    consensus::Transaction,
+    eips::BlockId,
+    primitives::{Address, TxHash},
+    providers::Provider,
+};
+use eyre::{Result, WrapErr};
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+/// 广播后轮询交易回执的间隔与次数；超出后交给下次启动时的核对
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const RECEIPT_POLL_ATTEMPTS: u32 = 720;
+
+/// createShow 提交队列：单个后台 worker 顺序处理，避免同一 signer 并发发送导致 nonce 冲突。
+#[derive(Debug, Clone)]
+pub struct ShowJobQueue {
+    tx: mpsc::UnboundedSender<i64>,
+}
+
+impl ShowJobQueue {
+    /// 启动 worker，并重新入队数据库中仍为 PENDING / SUBMITTING / SUBMITTED 的 job（进程重启恢复）。
+    /// `shutdown` 触发后 worker 处理完当前 job 即退出，返回的句柄在 worker 退出时完成；
+    /// 队列中剩下的 job 仍为 PENDING，下次启动时重新入队。
+    pub async fn start(
+        jobs: Arc<dyn ShowJobRepo>,
+        shutdown: Shutdown,
+    ) -> Result<(Self, JoinHandle<()>)> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let open = jobs.list_open_jobs().await?;
+        for id in open {
+            let _ = tx.send(id);
+        }
+        let worker = tokio::spawn(run_worker(jobs, tx.clone(), rx, shutdown));
+        Ok((Self { tx }, worker))
+    }
+
+    /// 不启动 worker 的队列（测试用）：enqueue 总是返回错误，job 保持 PENDING。
+    pub fn detached() -> Self {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        Self { tx }
+    }
+
+    pub fn enqueue(&self, job_id: i64) -> Result<()> {
+        self.tx
+            .send(job_id)
+            .map_err(|_| eyre::eyre!("show job worker has stopped"))
+    }
+}
+
+/// SUBMITTING / SUBMITTED job 链上核对的结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimedTxState {
+    /// 交易已在链上或交易池中
+    Known,
+    /// 交易已打包但执行失败（回执 status 为 false）：ShowCreated 永远不会出现
+    Reverted,
+    /// 交易查不到，且 nonce 已被其他交易占用：这笔交易不可能再上链
+    NonceTaken,
+    /// 交易查不到，nonce 仍空闲：认领后没有广播成功
+    NotBroadcast,
+}
+
+impl ClaimedTxState {
+    /// `receipt` 为交易回执的执行结果（未打包时为 None），
+    /// `mined_nonce` 为发送地址在最新区块的交易计数（下一个可用 nonce）。
+    pub fn classify(
+        known: bool,
+        receipt: Option<bool>,
+        mined_nonce: u64,
+        nonce: u64,
+    ) -> Self {
+        if receipt == Some(false) {
+            Self::Reverted
+        } else if known || receipt.is_some() {
+            Self::Known
+        } else if mined_nonce > nonce {
+            Self::NonceTaken
+        } else {
+            Self::NotBroadcast
+        }
+    }
+}
+
+async fn run_worker(
+    jobs: Arc<dyn ShowJobRepo>,
+    queue: mpsc::UnboundedSender<i64>,
+    mut rx: mpsc::UnboundedReceiver<i64>,
+    shutdown: Shutdown,
+) {
+    loop {
+        let job_id = tokio::select! {
+            biased;
+            _ = shutdown.wait() => break,
+            next = rx.recv() => match next {
+                Some(id) => id,
+                None => break,
+            },
+        };
+        if let Err(e) =
+            process_job(jobs.as_ref(), job_id, &queue, &shutdown).await
+        {
+            tracing::warn!(job_id, error = ?e, "createShow job processing failed");
+        }
+    }
+    tracing::info!("Show job worker stopped");
+}
+
+async fn process_job(
+    jobs: &dyn ShowJobRepo,
+    job_id: i64,
+    queue: &mpsc::UnboundedSender<i64>,
+    shutdown: &Shutdown,
+) -> Result<()> {
+    let Some(job) = jobs.get_job(job_id).await? else {
+        eyre::bail!("show job {} not found", job_id);
+    };
+    match job.status {
+        ShowJobStatus::Pending => {
+            if let Some(tx_hash) = submit_job(jobs, &job).await? {
+                tokio::spawn(await_receipt(
+                    job.id,
+                    tx_hash,
+                    queue.clone(),
+                    shutdown.clone(),
+                ));
+            }
+            Ok(())
+        }
+        ShowJobStatus::Submitting | ShowJobStatus::Submitted => {
+            reconcile_job(jobs, job.id, &Claimed::from_job(&job)?).await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 轮询已广播交易的回执，打包后把 job 重新入队核对（回滚的交易由此标记 FAILED）。
+/// 关闭或超出轮询次数时放弃，job 保持 SUBMITTED，下次启动时核对。
+async fn await_receipt(
+    job_id: i64,
+    tx_hash: TxHash,
+    queue: mpsc::UnboundedSender<i64>,
+    shutdown: Shutdown,
+) {
+    for _ in 0..RECEIPT_POLL_ATTEMPTS {
+        tokio::select! {
+            biased;
+            _ = shutdown.wait() => return,
+            _ = tokio::time::sleep(RECEIPT_POLL_INTERVAL) => {}
+        }
+        let mined = match reader() {
+            Ok(p) => p.get_transaction_receipt(tx_hash).await,
+            Err(_) => return,
+        };
+        match mined {
+            Ok(Some(_)) => {
+                let _ = queue.send(job_id);
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::debug!(job_id, error = ?e, "Receipt lookup failed; retrying")
+            }
+        }
+    }
+}
+
+/// 已认领交易的链上标识
+struct Claimed {
+    tx_hash: TxHash,
+    sender: Address,
+    nonce: u64,
+}
+
+impl Claimed {
+    fn from_job(job: &ShowCreateJobRecord) -> Result<Self> {
+        let (Some(tx_hash), Some(sender), Some(nonce)) =
+            (&job.tx_hash, &job.sender, job.nonce)
+        else {
+            eyre::bail!(
+                "show job {} is SUBMITTING without a signed tx",
+                job.id
+            );
+        };
+        Ok(Self {
+            tx_hash: tx_hash.parse()?,
+            sender: sender.parse()?,
+            nonce: u64::try_from(nonce)?,
+        })
+    }
+}
+
+/// 默认链的只读 provider：创建 show 的交易总是发往默认链
+fn reader() -> Result<WsProvider> {
+    providers::try_get_pool()
+        .map(|p| p.ws_reader())
+        .ok_or_else(|| eyre::eyre!("provider pool is not initialized"))
+}
+
+/// 签名 -> 认领 -> 广播。认领之前的失败直接标记 FAILED；认领之后交易可能已经发出，
+/// 不再标记 FAILED，而是交给 reconcile_job 按链上状态处理。
+/// 认领成功时返回交易哈希，由调用方等待回执。
+async fn submit_job(
+    jobs: &dyn ShowJobRepo,
+    job: &ShowCreateJobRecord,
+) -> Result<Option<TxHash>> {
+    let signed = async {
+        let provider = reader()?;
+        let wallet = providers::signer_pool().wallet_for(&job.signer)?;
+        let show_manager = config::get().default_chain().addresses.show_manager;
+        sign_create_show(provider, &wallet, show_manager, job).await
+    }
+    .await;
+    let signed = match signed {
+        Ok(tx) => tx,
+        Err(e) => {
+            jobs.mark_failed(job.id, &e.to_string()).await?;
+            return Err(e).wrap_err("signing createShow failed");
+        }
+    };
+    let claimed = Claimed {
+        tx_hash: *signed.tx_hash(),
+        sender: providers::signer_pool().address_of(&job.signer)?,
+        nonce: signed.nonce(),
+    };
+    let tx_hash = format!("0x{}", hex::encode(claimed.tx_hash.as_slice()));
+    let claimed_now = jobs
+        .claim_job(
+            job.id,
+            &tx_hash,
+            &format!("0x{}", hex::encode(claimed.sender.as_slice())),
+            i64::try_from(claimed.nonce)?,
+        )
+        .await?;
+    if !claimed_now {
+        tracing::info!(
+            job_id = job.id,
+            "Show job is no longer PENDING; not broadcasting"
+        );
+        return Ok(None);
+    }
+    if let Err(e) = broadcast_create_show(reader()?, signed).await {
+        tracing::warn!(job_id = job.id, tx_hash = %tx_hash, error = ?e, "createShow broadcast failed; checking chain");
+        reconcile_job(jobs, job.id, &claimed).await?;
+        return Ok(Some(claimed.tx_hash));
+    }
+    jobs.mark_submitted(job.id).await?;
+    tracing::info!(job_id = job.id, tx_hash = %tx_hash, "Submitted createShow transaction");
+    Ok(Some(claimed.tx_hash))
+}
+
+/// 核对 SUBMITTING / SUBMITTED 的 job：只查询链上状态，绝不重新发送。
+/// 查询失败时保持原状态，下次启动再核对。交易回滚时按回滚原因标记 FAILED；
+/// 因 nonce 判为 FAILED 后若交易仍被打包，索引器会按 tx_hash 将 job 改为 CONFIRMED。
+async fn reconcile_job(
+    jobs: &dyn ShowJobRepo,
+    job_id: i64,
+    claimed: &Claimed,
+) -> Result<()> {
+    let provider = reader()?;
+    // 先取 nonce 再查交易：两次查询之间被打包的交易仍会判为 Known
+    let mined_nonce = provider
+        .get_transaction_count(claimed.sender)
+        .latest()
+        .await?;
+    let tx = provider.get_transaction_by_hash(claimed.tx_hash).await?;
+    let receipt = provider.get_transaction_receipt(claimed.tx_hash).await?;
+    let state = ClaimedTxState::classify(
+        tx.is_some(),
+        receipt.as_ref().map(|r| r.status()),
+        mined_nonce,
+        claimed.nonce,
+    );
+    match state {
+        ClaimedTxState::Known => {
+            jobs.mark_submitted(job_id).await?;
+            tracing::info!(job_id, tx_hash = %claimed.tx_hash, "Recovered submitted createShow transaction");
+        }
+        ClaimedTxState::Reverted => {
+            let block =
+                receipt.and_then(|r| r.block_number).unwrap_or_default();
+            let reason = match tx {
+                Some(tx) => revert_reason(&provider, tx, block).await,
+                None => "unknown reason".to_string(),
+            };
+            let msg =
+                format!("transaction reverted in block {block}: {reason}");
+            jobs.mark_failed(job_id, &msg).await?;
+            tracing::warn!(job_id, tx_hash = %claimed.tx_hash, "{msg}");
+        }
+        ClaimedTxState::NonceTaken => {
+            let msg = format!(
+                "nonce {} of {} was used by another transaction",
+                claimed.nonce, claimed.sender
+            );
+            jobs.mark_failed(job_id, &msg).await?;
+            tracing::warn!(job_id, tx_hash = %claimed.tx_hash, "{msg}");
+        }
+        ClaimedTxState::NotBroadcast => {
+            let msg = "signed transaction was never broadcast";
+            jobs.mark_failed(job_id, msg).await?;
+            tracing::warn!(job_id, tx_hash = %claimed.tx_hash, "{msg}");
+        }
+    }
+    Ok(())
+}
+
+/// 在交易所在区块之前的状态上重放调用，取节点返回的回滚原因
+async fn revert_reason(
+    provider: &WsProvider,
+    tx: alloy::rpc::types::Transaction,
+    block: u64,
+) -> String {
+    let call = provider
+        .call(tx.into_request())
+        .block(BlockId::number(block.saturating_sub(1)));
+    match call.await {
+        Err(e) => e.to_string(),
+        Ok(_) => "unknown reason".to_string(),
+    }
+}
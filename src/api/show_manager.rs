@@ -2,176 +2,513 @@ use crate::{
     api::{
         AppState,
         error::AppError,
-        request::{PathShowId, ValidatedJson, ValidatedQuery},
-        response::ok,
-        schema::Pagination,
+        request::{
+            Actor, ChainQuery, CursorQuery, IfMatch, PathShowId, ValidatedJson,
+            ValidatedPath, ValidatedQuery,
+        },
+        response::{ApiResponse, accepted, ok, ok_page, ok_with_etag},
+        schema::{ChainParams, RawCursorParams, ValidationError, into_page},
+        validation::Validate,
     },
-    repo::show_repo::{ShowDataRecord, get_show_by_id, repo_list_shows},
-    utils::uint256::{DbU256, U256},
+    config,
+    contract::providers,
+    db::cache::{CachedPage, ListNamespace},
+    repo::show_job_repo::{NewShowCreateJob, ShowCreateJobRecord},
+    repo::show_repo::{
+        ShowCursor, ShowDataRecord, ShowFilter, ShowPatch, ShowPatchOutcome,
+        ShowSortKey, SortOrder,
+    },
+    utils::uint256::DbU256,
 };
-use axum::extract::State;
+use axum::extract::{RawQuery, State};
 use axum::response::Response;
-use chrono::Utc;
 use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 
 // === DTOs ===
-#[derive(Debug, Deserialize)]
+/// 创建演出请求：由服务端 signer 提交 ShowManager.createShow，show id 由链上分配。
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(schema = "Self::check_times")]
 pub struct CreateShowReq {
-    pub id: DbU256,
+    #[validate(trim, non_blank, length(max = 256))]
     pub name: String,
+    #[validate(length(max = 2048))]
     pub description: String,
+    #[validate(trim, length(max = 512))]
     pub location: String,
+    #[validate(custom = "in_future")]
     pub event_time: DbU256,
+    pub end_time: DbU256,
     pub ticket_price: DbU256,
+    #[validate(range(min = 1))]
     pub max_tickets: DbU256,
-    pub organizer: String,
+    /// 元数据地址，如 `ipfs://…` 或 `https://…`
+    #[validate(trim, uri, length(max = 2048))]
+    pub metadata_uri: Option<String>,
+}
+
+impl CreateShowReq {
+    fn check_times(&self, errors: &mut ValidationError) {
+        if self.end_time <= self.event_time {
+            errors.add("end_time", "must be after event_time");
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// 更新演出：只修改出现的字段
+#[derive(Debug, Deserialize, ToSchema, Validate)]
 pub struct UpdateShowReq {
+    #[validate(trim(keep_empty), non_blank, length(max = 256))]
     pub name: Option<String>,
+    #[validate(length(max = 2048))]
     pub description: Option<String>,
+    #[validate(trim(keep_empty), length(max = 512))]
     pub location: Option<String>,
+    #[validate(custom = "in_future")]
     pub event_time: Option<DbU256>,
     pub ticket_price: Option<DbU256>,
+    #[validate(range(min = 1))]
     pub max_tickets: Option<DbU256>,
     pub is_active: Option<bool>,
 }
 
-// 简单校验：示例性实现 Validate trait
-impl crate::api::schema::Validate for CreateShowReq {
-    type Err = crate::api::schema::ValidationError;
-    fn validate(self) -> Result<Self, Self::Err> {
-        if self.name.trim().is_empty() {
-            return Err(crate::api::schema::ValidationError(
-                "name empty".into(),
-            ));
-        }
-        if self.description.len() > 2048 {
-            return Err(crate::api::schema::ValidationError(
-                "description too long".into(),
-            ));
+impl UpdateShowReq {
+    pub fn patch(&self) -> ShowPatch {
+        ShowPatch {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            location: self.location.clone(),
+            event_time: self.event_time.clone(),
+            ticket_price: self.ticket_price.clone(),
+            max_tickets: self.max_tickets.clone(),
+            is_active: self.is_active,
         }
-        if self.location.len() > 512 {
-            return Err(crate::api::schema::ValidationError(
-                "location too long".into(),
-            ));
+    }
+
+    /// 依赖现有记录的规则：总票数不能少于已售
+    pub fn check_against(
+        &self,
+        existing: &ShowDataRecord,
+    ) -> Result<(), ValidationError> {
+        let mut errors = ValidationError::default();
+        if let Some(max) = &self.max_tickets
+            && *max < existing.sold_tickets
+        {
+            errors.add(
+                "max_tickets",
+                format!(
+                    "must not be below sold_tickets ({})",
+                    existing.sold_tickets
+                ),
+            );
         }
-        Ok(self)
+        errors.into_result(())
     }
 }
-impl crate::api::schema::Validate for UpdateShowReq {
-    type Err = crate::api::schema::ValidationError;
-    fn validate(self) -> Result<Self, Self::Err> {
-        Ok(self)
+
+/// 演出时间（unix 秒）必须晚于当前时间
+fn in_future(t: &DbU256) -> Result<(), String> {
+    let now = DbU256::from(chrono::Utc::now().timestamp().max(0) as u64);
+    if *t > now {
+        Ok(())
+    } else {
+        Err("must be in the future".into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShowStatusFilter {
+    Active,
+    Inactive,
+}
+
+/// GET /shows 查询参数：过滤 + 排序 + offset；`cursor` / `limit` 由 CursorQuery 解析。
+/// 例：`/shows?status=active&from=1735689600&q=concert&sort=price&order=asc`
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+#[validate(normalize = "Self::clamp_offset", schema = "Self::check_ranges")]
+pub struct ListShowsQuery {
+    #[serde(default)]
+    pub offset: i64,
+    pub status: Option<ShowStatusFilter>,
+    #[validate(trim, address)]
+    pub organizer: Option<String>,
+    /// event_time 下界（含，unix 秒）
+    pub from: Option<DbU256>,
+    /// event_time 上界（含，unix 秒）
+    pub to: Option<DbU256>,
+    #[validate(trim)]
+    pub location: Option<String>,
+    pub min_price: Option<DbU256>,
+    pub max_price: Option<DbU256>,
+    #[validate(trim, length(max = 256))]
+    pub q: Option<String>,
+    pub sort: Option<ShowSortKey>,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+impl ListShowsQuery {
+    fn clamp_offset(mut self) -> Self {
+        self.offset = self.offset.max(0);
+        self
+    }
+
+    fn check_ranges(&self, errors: &mut ValidationError) {
+        if let (Some(from), Some(to)) = (&self.from, &self.to)
+            && from > to
+        {
+            errors.add("from", "must not be after to");
+        }
+        if let (Some(min), Some(max)) = (&self.min_price, &self.max_price)
+            && min > max
+        {
+            errors.add("min_price", "must not exceed max_price");
+        }
+    }
+
+    pub fn filter(&self, chain_id: i64) -> ShowFilter {
+        ShowFilter {
+            chain_id: Some(chain_id),
+            is_active: self.status.map(|s| s == ShowStatusFilter::Active),
+            organizer: self.organizer.clone(),
+            event_time_from: self.from.clone(),
+            event_time_to: self.to.clone(),
+            location: self.location.clone(),
+            min_price: self.min_price.clone(),
+            max_price: self.max_price.clone(),
+            q: self.q.clone(),
+        }
     }
 }
 
-fn build_record_from_create(req: CreateShowReq) -> ShowDataRecord {
-    ShowDataRecord {
-        id: req.id,
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Path)]
+pub struct JobIdPath {
+    #[validate(range(min = 1))]
+    pub id: i64,
+}
+
+fn build_job_from_create(
+    req: CreateShowReq,
+    signer: String,
+    organizer: String,
+) -> NewShowCreateJob {
+    NewShowCreateJob {
+        signer,
+        organizer,
         name: req.name,
         description: req.description,
         location: req.location,
         event_time: req.event_time,
+        end_time: req.end_time,
         ticket_price: req.ticket_price,
-        max_tickets: req.max_tickets.clone(),
-        sold_tickets: DbU256(U256::from(0u64)),
-        is_active: true,
-        organizer: req.organizer,
-        created_at: Utc::now(),
+        max_tickets: req.max_tickets,
+        metadata_uri: req.metadata_uri.unwrap_or_default(),
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/show/{id}",
+    tag = "shows",
+    params(("id" = DbU256, Path, description = "show id"), ChainParams),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<ShowDataRecord>),
+        (status = 400, description = "invalid show id or chain_id", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "show not found", body = ApiResponse<serde_json::Value>)
+    )
+)]
 pub async fn show_with_id(
     State(state): State<AppState>,
     PathShowId(show_id): PathShowId,
+    ChainQuery(chain_id): ChainQuery,
 ) -> axum::response::Response {
-    let db = &state.api.db;
-    match get_show_by_id(db.pool(), show_id.clone()).await {
-        Ok(Some(rec)) => ok(rec),
+    let shows = &state.api.repos.shows;
+    // 有缓存时走 single-flight + 负缓存 + stale-while-revalidate
+    let res = match &state.api.cache {
+        Some(c) => c.show_read_through(shows, chain_id, &show_id).await,
+        None => shows.get_show(chain_id, &show_id).await,
+    };
+    match res {
+        Ok(Some(rec)) => {
+            let version = rec.version;
+            ok_with_etag(rec, version)
+        }
         Ok(None) => AppError::ShowNotFound(show_id.to_string()).to_response(),
         Err(e) => AppError::Internal(e.to_string()).to_response(),
     }
 }
 
+/// 支持 offset 与 keyset 两种分页：传入 `cursor` 时从游标之后继续（忽略 offset），
+/// 还有下一页时返回 next_cursor。按相关度排序（有 q 且无 sort）时不支持游标。
+#[utoipa::path(
+    get,
+    path = "/shows",
+    tag = "shows",
+    params(ListShowsQuery, ChainParams, RawCursorParams),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<Vec<ShowDataRecord>>),
+        (status = 400, description = "invalid query params", body = ApiResponse<serde_json::Value>)
+    )
+)]
 pub async fn list_shows(
     State(state): State<AppState>,
-    ValidatedQuery(p): ValidatedQuery<Pagination>,
+    ValidatedQuery(p): ValidatedQuery<ListShowsQuery>,
+    ChainQuery(chain_id): ChainQuery,
+    CursorQuery(page): CursorQuery<ShowCursor>,
+    RawQuery(raw): RawQuery,
 ) -> axum::response::Response {
-    let db = &state.api.db;
-    match repo_list_shows(db.pool(), p.limit, p.offset).await {
-        Ok(shows) => ok(shows),
-        Err(e) => AppError::Database(e.to_string()).to_response(),
+    let filter = p.filter(chain_id);
+    let ranked = p.sort.is_none() && p.q.is_some();
+    if let Some(c) = &page.cursor
+        && (ranked || !c.matches(p.sort, p.order))
+    {
+        return AppError::QueryInvalid(
+            "cursor does not match sort/order".into(),
+        )
+        .to_response();
+    }
+    // 列表页按原始查询串缓存，show 变更时整体作废
+    let cached = match state.api.cache.as_ref() {
+        Some(c) => Some((
+            c,
+            c.list_key(ListNamespace::Shows, &raw.unwrap_or_default())
+                .await,
+        )),
+        None => None,
+    };
+    if let Some((c, key)) = &cached
+        && let Some(hit) = c.get_list_page::<ShowDataRecord>(key).await
+    {
+        return ok_page(hit.items, hit.next_cursor);
     }
+    let res = match state
+        .api
+        .repos
+        .shows
+        .search_shows(
+            &filter,
+            p.sort,
+            p.order,
+            page.cursor.as_ref(),
+            page.fetch_limit(),
+            p.offset,
+        )
+        .await
+    {
+        Ok(shows) if ranked => {
+            let (items, _) = into_page(shows, page.limit, |_| ());
+            CachedPage {
+                items,
+                next_cursor: None,
+            }
+        }
+        Ok(shows) => {
+            let (items, next_cursor) = into_page(shows, page.limit, |r| {
+                ShowCursor::from_record(r, p.sort, p.order)
+            });
+            CachedPage { items, next_cursor }
+        }
+        Err(e) => return AppError::from(e).to_response(),
+    };
+    if let Some((c, key)) = &cached {
+        c.put_list_page(key, &res).await;
+    }
+    ok_page(res.items, res.next_cursor)
 }
 
+/// 受理创建请求：落一条 PENDING job 并交给后台 worker 上链，返回 202 + job。
+/// shows 表中的记录只会在索引器处理到对应 ShowCreated 事件后出现。交易总是发往默认链。
+#[utoipa::path(
+    post,
+    path = "/show",
+    tag = "shows",
+    request_body = CreateShowReq,
+    responses(
+        (status = 202, description = "accepted; the show appears once ShowCreated is indexed", body = ApiResponse<ShowCreateJobRecord>),
+        (status = 400, description = "invalid body", body = ApiResponse<serde_json::Value>),
+        (status = 503, description = "signer unavailable", body = ApiResponse<serde_json::Value>)
+    )
+)]
 pub async fn create_show(
     State(state): State<AppState>,
+    Actor(actor): Actor,
     ValidatedJson(body): ValidatedJson<CreateShowReq>,
 ) -> Response {
-    let db = &state.api.db;
-    let rec = build_record_from_create(body);
-    // 简单使用 insert_show_data（没有 upsert 冲突处理细节，这里演示）
-    match crate::repo::show_repo::insert_show_data(db.pool(), &rec).await {
-        Ok(_) => ok(rec),
-        Err(e) => AppError::Database(e.to_string()).to_response(),
+    let signer = config::get().show_signer.clone();
+    let organizer = match providers::try_signer_pool()
+        .ok_or_else(|| eyre::eyre!("signer pool is not initialized"))
+        .and_then(|p| p.address_of(&signer))
+    {
+        Ok(addr) => format!("0x{}", hex::encode(addr.as_slice())),
+        Err(e) => {
+            return AppError::SignerUnavailable(e.to_string()).to_response();
+        }
+    };
+    let job = build_job_from_create(body, signer, organizer);
+    let rec = match state.api.repos.jobs.insert_job(&job, &actor).await {
+        Ok(rec) => rec,
+        Err(e) => return AppError::from(e).to_response(),
+    };
+    if let Err(e) = state.api.show_jobs.enqueue(rec.id) {
+        return AppError::Internal(e.to_string()).to_response();
+    }
+    accepted(rec)
+}
+
+#[utoipa::path(
+    get,
+    path = "/show/jobs/{id}",
+    tag = "shows",
+    params(JobIdPath),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<ShowCreateJobRecord>),
+        (status = 404, description = "job not found", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn show_job_with_id(
+    State(state): State<AppState>,
+    ValidatedPath(p): ValidatedPath<JobIdPath>,
+) -> Response {
+    match state.api.repos.jobs.get_job(p.id).await {
+        Ok(Some(rec)) => ok(rec),
+        Ok(None) => AppError::JobNotFound(p.id.to_string()).to_response(),
+        Err(e) => AppError::from(e).to_response(),
     }
 }
 
+/// 与 PATCH 相同（保留给已有客户端）
+#[utoipa::path(
+    put,
+    path = "/show/{id}",
+    tag = "shows",
+    params(
+        ("id" = DbU256, Path, description = "show id"),
+        ChainParams,
+        ("If-Match" = Option<String>, Header, description = "GET 返回的 ETag；不匹配时返回 412")
+    ),
+    request_body = UpdateShowReq,
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<ShowDataRecord>),
+        (status = 400, description = "invalid body", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "show not found", body = ApiResponse<serde_json::Value>),
+        (status = 412, description = "If-Match does not match the current version", body = ApiResponse<serde_json::Value>)
+    )
+)]
 pub async fn update_show(
+    state: State<AppState>,
+    show_id: PathShowId,
+    chain: ChainQuery,
+    actor: Actor,
+    if_match: IfMatch,
+    update: ValidatedJson<UpdateShowReq>,
+) -> Response {
+    patch_show(state, show_id, chain, actor, if_match, update).await
+}
+
+/// 只更新请求中出现的字段，version 随之递增，响应带新的 ETag。
+/// 携带 `If-Match` 时仅在版本一致时更新（索引器刷新快照也会递增版本），否则返回 412。
+#[utoipa::path(
+    patch,
+    path = "/show/{id}",
+    tag = "shows",
+    params(
+        ("id" = DbU256, Path, description = "show id"),
+        ChainParams,
+        ("If-Match" = Option<String>, Header, description = "GET 返回的 ETag；不匹配时返回 412")
+    ),
+    request_body = UpdateShowReq,
+    responses(
+        (status = 200, description = "ok; ETag header carries the new version", body = ApiResponse<ShowDataRecord>),
+        (status = 400, description = "invalid body, or max_tickets below sold_tickets", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "show not found", body = ApiResponse<serde_json::Value>),
+        (status = 412, description = "If-Match does not match the current version", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn patch_show(
     State(state): State<AppState>,
     PathShowId(show_id): PathShowId,
+    ChainQuery(chain_id): ChainQuery,
+    Actor(actor): Actor,
+    IfMatch(if_match): IfMatch,
     ValidatedJson(update): ValidatedJson<UpdateShowReq>,
 ) -> Response {
-    let db = &state.api.db;
-    // 先获取
-    let existing = match get_show_by_id(db.pool(), show_id.clone()).await {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            return AppError::ShowNotFound(show_id.to_string()).to_response();
+    let patch = update.patch();
+    let res = state
+        .api
+        .repos
+        .shows
+        .update_show(chain_id, &show_id, &patch, if_match.as_deref(), &actor)
+        .await;
+    match res {
+        Ok(ShowPatchOutcome::Updated(rec)) => {
+            if !patch.is_empty()
+                && let Some(c) = &state.api.cache
+            {
+                c.invalidate_show(chain_id, &show_id).await;
+            }
+            let version = rec.version;
+            ok_with_etag(rec, version)
         }
-        Err(e) => return AppError::Database(e.to_string()).to_response(),
-    };
-    // 构造更新 SQL（仅示范：直接替换字段，不做部分字段保持原值的批量 set 生成器）
-    let new_rec = ShowDataRecord {
-        name: update.name.unwrap_or(existing.name),
-        description: update.description.unwrap_or(existing.description),
-        location: update.location.unwrap_or(existing.location),
-        event_time: update.event_time.unwrap_or(existing.event_time),
-        ticket_price: update.ticket_price.unwrap_or(existing.ticket_price),
-        max_tickets: update.max_tickets.unwrap_or(existing.max_tickets),
-        sold_tickets: existing.sold_tickets, // 不改
-        is_active: update.is_active.unwrap_or(existing.is_active),
-        organizer: existing.organizer,
-        id: existing.id,
-        created_at: existing.created_at, // 保留原创建时间
-    };
-    match crate::repo::show_repo::insert_show_data(db.pool(), &new_rec).await {
-        Ok(_) => ok(new_rec),
-        Err(e) => AppError::Database(e.to_string()).to_response(),
+        Ok(ShowPatchOutcome::NotFound) => {
+            AppError::ShowNotFound(show_id.to_string()).to_response()
+        }
+        Ok(ShowPatchOutcome::Rejected(current)) => {
+            if if_match
+                .as_ref()
+                .is_some_and(|vs| !vs.contains(&current.version))
+            {
+                return AppError::PreconditionFailed(format!(
+                    "current version is {}",
+                    current.version
+                ))
+                .to_response();
+            }
+            match update.check_against(&current) {
+                Err(e) => AppError::Validation(e).to_response(),
+                // 兜底：无法归因的条件失败，让客户端重试
+                Ok(()) => AppError::Conflict(
+                    "show changed concurrently, retry".into(),
+                )
+                .to_response(),
+            }
+        }
+        Err(e) => AppError::from(e).to_response(),
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/show/{id}",
+    tag = "shows",
+    params(("id" = DbU256, Path, description = "show id"), ChainParams),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "show not found", body = ApiResponse<serde_json::Value>)
+    )
+)]
 pub async fn delete_show(
     State(state): State<AppState>,
     PathShowId(show_id): PathShowId,
+    ChainQuery(chain_id): ChainQuery,
+    Actor(actor): Actor,
 ) -> Response {
-    let db = &state.api.db;
-    let res = sqlx::query("DELETE FROM shows WHERE id = $1")
-        .bind(show_id.clone())
-        .execute(db.pool())
-        .await;
-    match res {
-        Ok(r) => {
-            if r.rows_affected() == 0 {
-                AppError::ShowNotFound(show_id.to_string()).to_response()
-            } else {
-                ok(
-                    serde_json::json!({"deleted": true, "id": show_id.to_string()}),
-                )
+    match state
+        .api
+        .repos
+        .shows
+        .delete_show(chain_id, &show_id, &actor)
+        .await
+    {
+        Ok(Some(_)) => {
+            if let Some(c) = &state.api.cache {
+                c.invalidate_show(chain_id, &show_id).await;
             }
+            ok(serde_json::json!({"deleted": true, "id": show_id.to_string()}))
         }
-        Err(e) => AppError::Database(e.to_string()).to_response(),
+        Ok(None) => AppError::ShowNotFound(show_id.to_string()).to_response(),
+        Err(e) => AppError::from(e).to_response(),
     }
 }
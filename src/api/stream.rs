@@ -0,0 +1,268 @@
+use crate::{
+    api::{
+        AppState,
+        error::AppError,
+        request::{ChainQuery, PathShowId},
+        response::ApiResponse,
+        schema::ChainParams,
+    },
+    realtime::{RealtimeEvent, Topic},
+    shutdown::Shutdown,
+    utils::uint256::DbU256,
+};
+use axum::{
+    extract::{
+        State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
+    },
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures::{Stream, StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, convert::Infallible, sync::Arc};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// 单个连接最多订阅的主题数
+pub const MAX_TOPICS_PER_CONNECTION: usize = 100;
+
+/// 将广播接收端转为事件流：只保留命中 `filter` 的事件；落后过多时跳过丢失部分继续。
+pub fn event_stream(
+    rx: broadcast::Receiver<Arc<RealtimeEvent>>,
+    filter: impl Fn(&RealtimeEvent) -> bool + Send + 'static,
+) -> impl Stream<Item = Arc<RealtimeEvent>> + Send {
+    stream::unfold((rx, filter), |(mut rx, filter)| async move {
+        loop {
+            match rx.recv().await {
+                Ok(ev) if filter(&ev) => return Some((ev, (rx, filter))),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!(skipped = n, "Realtime subscriber lagged");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// GET /stream/shows/{id}：先推送一次当前快照（event: snapshot），之后推送该 show 的
+/// show_changed / ticket_sold / listing_changed / ticket_checked_in 事件（仅 `?chain_id=` 所选的链）。
+#[utoipa::path(
+    get,
+    path = "/stream/shows/{id}",
+    tag = "stream",
+    params(("id" = DbU256, Path, description = "show id"), ChainParams),
+    responses(
+        (status = 200, description = "Server-Sent Events：snapshot 之后为 RealtimeEvent，event 名为其 type", content_type = "text/event-stream", body = RealtimeEvent),
+        (status = 404, description = "show not found", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn show_events_sse(
+    State(state): State<AppState>,
+    PathShowId(show_id): PathShowId,
+    ChainQuery(chain_id): ChainQuery,
+) -> Response {
+    // 先订阅再读快照，避免两者之间的事件丢失
+    let rx = state.api.events.subscribe();
+    let shows = &state.api.repos.shows;
+    let snapshot = match &state.api.cache {
+        Some(c) => c.show_read_through(shows, chain_id, &show_id).await,
+        None => shows.get_show(chain_id, &show_id).await,
+    };
+    let show = match snapshot {
+        Ok(Some(s)) => s,
+        Ok(None) => {
+            return AppError::ShowNotFound(show_id.to_string()).to_response();
+        }
+        Err(e) => return AppError::from(e).to_response(),
+    };
+    let first = Event::default()
+        .event("snapshot")
+        .json_data(&show)
+        .unwrap_or_default();
+    let topic = Topic::Show(show_id);
+    let updates = event_stream(rx, move |ev| {
+        ev.chain_id() == chain_id && ev.matches(&topic)
+    })
+    .map(|ev| {
+        Event::default()
+            .event(ev.kind())
+            .json_data(&*ev)
+            .unwrap_or_default()
+    });
+    let shutdown = state.api.shutdown.clone();
+    let body = stream::once(async { first })
+        .chain(updates)
+        .take_until(async move { shutdown.wait().await })
+        .map(Ok::<_, Infallible>);
+    Sse::new(body)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// WebSocket 客户端消息：`{"op":"subscribe","topics":["show:1","listings"]}`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Subscribe { topics: Vec<Topic> },
+    Unsubscribe { topics: Vec<Topic> },
+    Ping,
+}
+
+/// WebSocket 服务端消息
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage<'a> {
+    Subscribed {
+        topics: Vec<Topic>,
+    },
+    Unsubscribed {
+        topics: Vec<Topic>,
+    },
+    Pong,
+    Error {
+        message: String,
+    },
+    Event {
+        topics: Vec<Topic>,
+        event: &'a RealtimeEvent,
+    },
+}
+
+/// 单个 WebSocket 连接的订阅集合
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    topics: HashSet<Topic>,
+}
+
+impl Subscriptions {
+    /// 处理一条客户端文本消息，返回应答
+    pub fn handle(&mut self, text: &str) -> ServerMessage<'static> {
+        let msg = match serde_json::from_str::<ClientMessage>(text) {
+            Ok(m) => m,
+            Err(e) => {
+                return ServerMessage::Error {
+                    message: format!("invalid message: {e}"),
+                };
+            }
+        };
+        match msg {
+            ClientMessage::Subscribe { topics } => {
+                let mut all = self.topics.clone();
+                all.extend(topics.iter().cloned());
+                if all.len() > MAX_TOPICS_PER_CONNECTION {
+                    return ServerMessage::Error {
+                        message: format!(
+                            "at most {MAX_TOPICS_PER_CONNECTION} topics per connection"
+                        ),
+                    };
+                }
+                self.topics = all;
+                ServerMessage::Subscribed { topics }
+            }
+            ClientMessage::Unsubscribe { topics } => {
+                for t in &topics {
+                    self.topics.remove(t);
+                }
+                ServerMessage::Unsubscribed { topics }
+            }
+            ClientMessage::Ping => ServerMessage::Pong,
+        }
+    }
+
+    /// 事件命中的已订阅主题；为空表示不投递
+    pub fn matched(&self, event: &RealtimeEvent) -> Vec<Topic> {
+        event
+            .topics()
+            .into_iter()
+            .filter(|t| self.topics.contains(t))
+            .collect()
+    }
+}
+
+/// GET /ws：升级为 WebSocket，按订阅主题推送实时事件；一个连接只接收 `?chain_id=` 所选链的事件
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "stream",
+    params(ChainParams),
+    responses(
+        (status = 101, description = "WebSocket 升级；客户端发送 {\"op\":\"subscribe\",\"topics\":[...]}，服务端推送 {\"type\":\"event\",...}")
+    )
+)]
+pub async fn ws_handler(
+    State(state): State<AppState>,
+    ChainQuery(chain_id): ChainQuery,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let rx = state.api.events.subscribe();
+    let shutdown = state.api.shutdown.clone();
+    ws.on_upgrade(move |socket| serve_socket(socket, chain_id, rx, shutdown))
+}
+
+async fn send_json(socket: &mut WebSocket, msg: &ServerMessage<'_>) -> bool {
+    match serde_json::to_string(msg) {
+        Ok(text) => socket.send(Message::Text(text.into())).await.is_ok(),
+        Err(_) => true,
+    }
+}
+
+async fn serve_socket(
+    mut socket: WebSocket,
+    chain_id: i64,
+    mut rx: broadcast::Receiver<Arc<RealtimeEvent>>,
+    shutdown: Shutdown,
+) {
+    let mut subs = Subscriptions::default();
+    loop {
+        tokio::select! {
+            _ = shutdown.wait() => {
+                // 1001 Going Away：服务端正在关闭
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "server shutting down".into(),
+                    })))
+                    .await;
+                break;
+            }
+            incoming = socket.recv() => {
+                let reply = match incoming {
+                    Some(Ok(Message::Text(text))) => subs.handle(&text),
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    // ping/pong 由 axum 自动处理；二进制帧不支持
+                    Some(Ok(Message::Binary(_))) => ServerMessage::Error {
+                        message: "binary frames are not supported".into(),
+                    },
+                    Some(Ok(_)) => continue,
+                };
+                if !send_json(&mut socket, &reply).await {
+                    break;
+                }
+            }
+            ev = rx.recv() => {
+                let ev = match ev {
+                    Ok(ev) => ev,
+                    Err(RecvError::Lagged(n)) => {
+                        tracing::warn!(skipped = n, "WebSocket subscriber lagged");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if ev.chain_id() != chain_id {
+                    continue;
+                }
+                let topics = subs.matched(&ev);
+                if topics.is_empty() {
+                    continue;
+                }
+                let msg = ServerMessage::Event { topics, event: &ev };
+                if !send_json(&mut socket, &msg).await {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    api::{
+        AppState,
+        error::AppError,
+        request::{ChainQuery, CursorQuery, PathIdU256, ValidatedQuery},
+        response::{ApiResponse, ok, ok_page},
+        schema::{ChainParams, RawCursorParams, ValidationError, into_page},
+        validation::{self, Trim, Validate},
+    },
+    db::cache::{CachedPage, ListNamespace},
+    repo::ticket_repo::{
+        TicketCursor, TicketFilter, TicketRecord, TicketStatus,
+        TicketTransferRecord, TransferCursor, TransferFilter,
+    },
+    utils::uint256::DbU256,
+};
+use axum::extract::{RawQuery, State};
+use axum::response::Response;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+/// GraphQL 参数用：去空白后为空视为未传，否则必须是地址
+pub(crate) fn normalize_address(
+    s: Option<String>,
+    field: &str,
+) -> Result<Option<String>, ValidationError> {
+    let v = Trim::trim(s);
+    match v.as_deref().and_then(validation::address) {
+        Some(message) => Err(ValidationError::field(field, message)),
+        None => Ok(v),
+    }
+}
+
+/// GET /tickets 过滤参数；分页由 `?cursor=&limit=` 控制。
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct ListTicketsQuery {
+    #[validate(trim, address)]
+    pub owner: Option<String>,
+    pub event_id: Option<DbU256>,
+    pub status: Option<TicketStatus>,
+}
+
+/// GET /transfers 过滤参数：按票或地址（from/to 任一）过滤。
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct ListTransfersQuery {
+    pub token_id: Option<DbU256>,
+    #[validate(trim, address)]
+    pub address: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/ticket/{id}",
+    tag = "tickets",
+    params(("id" = DbU256, Path, description = "ticket token id"), ChainParams),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<TicketRecord>),
+        (status = 400, description = "invalid ticket id or chain_id", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "ticket not found", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn ticket_with_id(
+    State(state): State<AppState>,
+    PathIdU256(token_id): PathIdU256,
+    ChainQuery(chain_id): ChainQuery,
+) -> Response {
+    let cache = state.api.cache.as_ref();
+    if let Some(c) = cache
+        && let Some(rec) = c.get_ticket(chain_id, &token_id).await
+    {
+        return ok(rec);
+    }
+    match state
+        .api
+        .repos
+        .tickets
+        .get_ticket(chain_id, &token_id)
+        .await
+    {
+        Ok(Some(rec)) => {
+            if let Some(c) = cache {
+                c.put_ticket(&rec).await;
+            }
+            ok(rec)
+        }
+        Ok(None) => {
+            AppError::TicketNotFound(token_id.to_string()).to_response()
+        }
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/tickets",
+    tag = "tickets",
+    params(ListTicketsQuery, ChainParams, RawCursorParams),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<Vec<TicketRecord>>),
+        (status = 400, description = "invalid query params", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn list_tickets(
+    State(state): State<AppState>,
+    ValidatedQuery(q): ValidatedQuery<ListTicketsQuery>,
+    ChainQuery(chain_id): ChainQuery,
+    CursorQuery(page): CursorQuery<TicketCursor>,
+    RawQuery(raw): RawQuery,
+) -> Response {
+    let cached = match state.api.cache.as_ref() {
+        Some(c) => Some((
+            c,
+            c.list_key(ListNamespace::Tickets, &raw.unwrap_or_default())
+                .await,
+        )),
+        None => None,
+    };
+    if let Some((c, key)) = &cached
+        && let Some(hit) = c.get_list_page::<TicketRecord>(key).await
+    {
+        return ok_page(hit.items, hit.next_cursor);
+    }
+    let filter = TicketFilter {
+        chain_id: Some(chain_id),
+        owner: q.owner,
+        event_id: q.event_id,
+        status: q.status,
+    };
+    match state
+        .api
+        .repos
+        .tickets
+        .list_tickets(&filter, page.cursor.as_ref(), page.fetch_limit())
+        .await
+    {
+        Ok(rows) => {
+            let (items, next_cursor) =
+                into_page(rows, page.limit, |r| TicketCursor {
+                    token_id: r.token_id.clone(),
+                });
+            let res = CachedPage { items, next_cursor };
+            if let Some((c, key)) = &cached {
+                c.put_list_page(key, &res).await;
+            }
+            ok_page(res.items, res.next_cursor)
+        }
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/transfers",
+    tag = "tickets",
+    params(ListTransfersQuery, ChainParams, RawCursorParams),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<Vec<TicketTransferRecord>>),
+        (status = 400, description = "invalid query params", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn list_transfers(
+    State(state): State<AppState>,
+    ValidatedQuery(q): ValidatedQuery<ListTransfersQuery>,
+    ChainQuery(chain_id): ChainQuery,
+    CursorQuery(page): CursorQuery<TransferCursor>,
+    RawQuery(raw): RawQuery,
+) -> Response {
+    let cached = match state.api.cache.as_ref() {
+        Some(c) => Some((
+            c,
+            c.list_key(ListNamespace::Transfers, &raw.unwrap_or_default())
+                .await,
+        )),
+        None => None,
+    };
+    if let Some((c, key)) = &cached
+        && let Some(hit) = c.get_list_page::<TicketTransferRecord>(key).await
+    {
+        return ok_page(hit.items, hit.next_cursor);
+    }
+    let filter = TransferFilter {
+        chain_id: Some(chain_id),
+        token_id: q.token_id,
+        address: q.address,
+    };
+    match state
+        .api
+        .repos
+        .tickets
+        .list_transfers(&filter, page.cursor.as_ref(), page.fetch_limit())
+        .await
+    {
+        Ok(rows) => {
+            let (items, next_cursor) =
+                into_page(rows, page.limit, |r| TransferCursor {
+                    block_number: r.block_number,
+                    log_index: r.log_index,
+                });
+            let res = CachedPage { items, next_cursor };
+            if let Some((c, key)) = &cached {
+                c.put_list_page(key, &res).await;
+            }
+            ok_page(res.items, res.next_cursor)
+        }
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
@@ -0,0 +1,163 @@
+//! `#[derive(Validate)]`（见 `backend-macros`）展开后调用的规则。
+//! 每条规则返回 `None` 表示通过，`Some(message)` 为该字段的错误原因。
+use crate::utils::uint256::DbU256;
+use alloy::primitives::U256;
+use std::cmp::Ordering;
+
+pub use backend_macros::Validate;
+
+/// `#[validate(trim)]`：去掉首尾空白；可选字段去空白后为空视为未传
+pub trait Trim {
+    fn trim(self) -> Self;
+}
+
+impl Trim for String {
+    fn trim(self) -> Self {
+        str::trim(&self).to_string()
+    }
+}
+
+impl Trim for Option<String> {
+    fn trim(self) -> Self {
+        self.map(Trim::trim).filter(|v| !v.is_empty())
+    }
+}
+
+pub fn non_blank(v: &str) -> Option<String> {
+    v.trim().is_empty().then(|| "must not be blank".into())
+}
+
+/// 按字符计数
+pub fn length(
+    v: &str,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Option<String> {
+    let n = v.chars().count();
+    match (min, max) {
+        (Some(min), Some(max)) if n < min || n > max => {
+            Some(format!("length must be between {min} and {max}"))
+        }
+        (Some(min), None) if n < min => {
+            Some(format!("length must be at least {min}"))
+        }
+        (None, Some(max)) if n > max => {
+            Some(format!("length must be at most {max}"))
+        }
+        _ => None,
+    }
+}
+
+/// `range` 的边界：derive 宏在编译期把字面量解析为 256 位无符号整数（小端 limbs），
+/// 非法字面量或 min > max 是编译错误；运行时只做比较。
+#[derive(Debug, Clone, Copy)]
+pub struct Bound {
+    limbs: [u64; 4],
+    literal: &'static str,
+}
+
+impl Bound {
+    #[doc(hidden)]
+    pub const fn new(limbs: [u64; 4], literal: &'static str) -> Self {
+        Self { limbs, literal }
+    }
+
+    pub fn value(&self) -> U256 {
+        U256::from_limbs(self.limbs)
+    }
+}
+
+/// 可用 `range` 检查的字段类型：与非负边界比较
+pub trait RangeBound {
+    fn cmp_bound(&self, bound: &Bound) -> Ordering;
+}
+
+macro_rules! unsigned_bound {
+    ($($t:ty),*) => {$(
+        impl RangeBound for $t {
+            fn cmp_bound(&self, bound: &Bound) -> Ordering {
+                U256::from(*self).cmp(&bound.value())
+            }
+        }
+    )*};
+}
+
+macro_rules! signed_bound {
+    ($($t:ty),*) => {$(
+        impl RangeBound for $t {
+            fn cmp_bound(&self, bound: &Bound) -> Ordering {
+                if *self < 0 {
+                    Ordering::Less
+                } else {
+                    U256::from(self.unsigned_abs()).cmp(&bound.value())
+                }
+            }
+        }
+    )*};
+}
+
+unsigned_bound!(u8, u16, u32, u64, u128, usize);
+signed_bound!(i8, i16, i32, i64, i128, isize);
+
+impl RangeBound for U256 {
+    fn cmp_bound(&self, bound: &Bound) -> Ordering {
+        self.cmp(&bound.value())
+    }
+}
+
+impl RangeBound for DbU256 {
+    fn cmp_bound(&self, bound: &Bound) -> Ordering {
+        self.0.cmp_bound(bound)
+    }
+}
+
+/// 错误信息按 DTO 中书写的字面量展示边界
+pub fn range<T: RangeBound>(
+    v: &T,
+    min: Option<Bound>,
+    max: Option<Bound>,
+) -> Option<String> {
+    let below = min.is_some_and(|m| v.cmp_bound(&m).is_lt());
+    let above = max.is_some_and(|m| v.cmp_bound(&m).is_gt());
+    if !below && !above {
+        return None;
+    }
+    Some(match (min, max) {
+        (Some(min), Some(max)) => {
+            format!("must be between {} and {}", min.literal, max.literal)
+        }
+        (Some(min), None) => format!("must be at least {}", min.literal),
+        (None, Some(max)) => format!("must be at most {}", max.literal),
+        (None, None) => unreachable!(),
+    })
+}
+
+pub fn address(v: &str) -> Option<String> {
+    let hex = v
+        .strip_prefix("0x")
+        .or_else(|| v.strip_prefix("0X"))
+        .unwrap_or("");
+    (hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| "must be a 0x-prefixed 20-byte address".into())
+}
+
+/// 任意带 scheme 与主机/路径的绝对 URI，如 `https://…`、`ipfs://…`
+pub fn uri(v: &str) -> Option<String> {
+    match v.parse::<axum::http::Uri>() {
+        Ok(u) if u.scheme().is_some() && u.authority().is_some() => None,
+        _ => Some("must be an absolute URI".into()),
+    }
+}
+
+/// 出站请求用的 http(s) URL
+pub fn url(v: &str) -> Option<String> {
+    match v.parse::<axum::http::Uri>() {
+        Ok(u)
+            if matches!(u.scheme_str(), Some("http" | "https"))
+                && u.host().is_some_and(|h| !h.is_empty()) =>
+        {
+            None
+        }
+        _ => Some("must be an http(s) URL".into()),
+    }
+}
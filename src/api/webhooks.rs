@@ -0,0 +1,211 @@
+use crate::{
+    api::{
+        AppState,
+        admin::AdminAuth,
+        error::AppError,
+        request::{CursorQuery, ValidatedJson, ValidatedPath, ValidatedQuery},
+        response::{ApiResponse, ok, ok_page},
+        schema::{RawCursorParams, ValidationError, into_page},
+        validation::Validate,
+    },
+    repo::webhook_repo::{
+        DeliveryCursor, DeliveryFilter, DeliveryStatus, NewWebhook,
+        WebhookDeliveryRecord, WebhookRecord,
+    },
+    webhook::{WebhookEventType, target::check_target},
+};
+use axum::extract::State;
+use axum::response::Response;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+const MAX_URL_LEN: usize = 2048;
+const MIN_SECRET_LEN: usize = 16;
+const MAX_SECRET_LEN: usize = 256;
+
+/// 注册 webhook：event_types 为空表示订阅全部；secret 省略时由服务端生成。
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+#[validate(normalize = "Self::dedup_event_types")]
+pub struct CreateWebhookReq {
+    #[validate(trim, url, length(max = MAX_URL_LEN))]
+    pub url: String,
+    #[serde(default)]
+    pub event_types: Vec<WebhookEventType>,
+    #[validate(length(min = MIN_SECRET_LEN, max = MAX_SECRET_LEN))]
+    pub secret: Option<String>,
+}
+
+impl CreateWebhookReq {
+    fn dedup_event_types(mut self) -> Self {
+        self.event_types.sort_by_key(|t| t.as_str());
+        self.event_types.dedup();
+        self
+    }
+}
+
+/// 创建响应：唯一一次返回 secret
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub webhook: WebhookRecord,
+    pub secret: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Path)]
+pub struct WebhookIdPath {
+    #[validate(range(min = 1))]
+    pub id: i64,
+}
+
+/// GET /webhooks/{id}/deliveries 过滤参数；分页由 `?cursor=&limit=` 控制。
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct ListDeliveriesQuery {
+    pub status: Option<DeliveryStatus>,
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookReq,
+    params(("Authorization" = String, Header, description = "Bearer <ADMIN_TOKEN>")),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<CreatedWebhook>),
+        (status = 400, description = "invalid body or non-public url", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "missing or invalid admin token", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "ADMIN_TOKEN is not configured", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    _auth: AdminAuth,
+    ValidatedJson(body): ValidatedJson<CreateWebhookReq>,
+) -> Response {
+    if let Err(msg) = check_target(&body.url).await {
+        return AppError::Validation(ValidationError::field("url", msg))
+            .to_response();
+    }
+    let secret = body.secret.unwrap_or_else(generate_secret);
+    let new = NewWebhook {
+        url: body.url,
+        event_types: body
+            .event_types
+            .iter()
+            .map(|t| t.as_str().to_string())
+            .collect(),
+        secret: secret.clone(),
+    };
+    match state.api.repos.webhooks.insert_webhook(&new).await {
+        Ok(webhook) => ok(CreatedWebhook { webhook, secret }),
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    params(("Authorization" = String, Header, description = "Bearer <ADMIN_TOKEN>")),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<Vec<WebhookRecord>>),
+        (status = 401, description = "missing or invalid admin token", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "ADMIN_TOKEN is not configured", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    _auth: AdminAuth,
+) -> Response {
+    match state.api.repos.webhooks.list_webhooks().await {
+        Ok(recs) => ok(recs),
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        WebhookIdPath,
+        ("Authorization" = String, Header, description = "Bearer <ADMIN_TOKEN>")
+    ),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "webhook not found", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "missing or invalid admin token", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "ADMIN_TOKEN is not configured", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    _auth: AdminAuth,
+    ValidatedPath(p): ValidatedPath<WebhookIdPath>,
+) -> Response {
+    match state.api.repos.webhooks.delete_webhook(p.id).await {
+        Ok(true) => ok(serde_json::json!({"deleted": true, "id": p.id})),
+        Ok(false) => AppError::WebhookNotFound(p.id.to_string()).to_response(),
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
+
+/// 投递日志：按 id 倒序，可按状态过滤。
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(
+        WebhookIdPath,
+        ListDeliveriesQuery,
+        RawCursorParams,
+        ("Authorization" = String, Header, description = "Bearer <ADMIN_TOKEN>")
+    ),
+    responses(
+        (status = 200, description = "ok", body = ApiResponse<Vec<WebhookDeliveryRecord>>),
+        (status = 404, description = "webhook not found", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "missing or invalid admin token", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "ADMIN_TOKEN is not configured", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    _auth: AdminAuth,
+    ValidatedPath(p): ValidatedPath<WebhookIdPath>,
+    ValidatedQuery(q): ValidatedQuery<ListDeliveriesQuery>,
+    CursorQuery(page): CursorQuery<DeliveryCursor>,
+) -> Response {
+    let webhooks = &state.api.repos.webhooks;
+    match webhooks.get_webhook(p.id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return AppError::WebhookNotFound(p.id.to_string()).to_response();
+        }
+        Err(e) => return AppError::from(e).to_response(),
+    }
+    let filter = DeliveryFilter { status: q.status };
+    match webhooks
+        .list_deliveries(
+            p.id,
+            &filter,
+            page.cursor.as_ref(),
+            page.fetch_limit(),
+        )
+        .await
+    {
+        Ok(rows) => {
+            let (items, next) =
+                into_page(rows, page.limit, |r| DeliveryCursor { id: r.id });
+            ok_page(items, next)
+        }
+        Err(e) => AppError::from(e).to_response(),
+    }
+}
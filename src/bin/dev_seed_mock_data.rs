@@ -8,5 +8,5 @@ async fn main() -> Result<()> {
     let cfg = config::init_from_env()?;
     let db = Db::connect(&cfg.database_url, 5).await?;
     let _ = backend::db::run_migrations(db.pool()).await;
-    backend::tools::seed_mock(&db).await
+    backend::tools::seed_mock(&db, cfg.default_chain().chain_id).await
 }
@@ -6,7 +6,7 @@ use eyre::Result;
 #[derive(Parser)]
 #[command(
     name = "dev-tools",
-    about = "Dev utilities: seed DB, update show name"
+    about = "Dev utilities: seed DB, update show name, check config and ABIs"
 )]
 struct Cli {
     #[command(subcommand)]
@@ -26,6 +26,45 @@ enum Commands {
         /// Metadata URI
         metadata_uri: String,
     },
+    /// Encrypt signers from the plaintext signers file into keystores
+    EncryptSigners {
+        /// Output directory (defaults to SIGNER_KEYSTORE_DIR)
+        #[arg(long)]
+        dir: Option<String>,
+    },
+    /// Inspect the layered configuration (config file + env)
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Compare bundled ABIs with the deployed contracts
+    Abi {
+        #[command(subcommand)]
+        command: AbiCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum AbiCommand {
+    /// Call view functions and match recent event signatures; fails on any drift
+    Check {
+        /// Only check this chain (defaults to every configured chain)
+        #[arg(long)]
+        chain: Option<i64>,
+        /// Blocks to scan for events (defaults to ABI_CHECK_BLOCKS)
+        #[arg(long)]
+        blocks: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Validate the configuration and print effective values with secrets redacted
+    Check {
+        /// Config file (defaults to CONFIG_FILE or ./config.toml)
+        #[arg(long)]
+        file: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -41,6 +80,13 @@ async fn main() -> Result<()> {
             name,
             metadata_uri,
         } => update_show(show_id, name, metadata_uri).await,
+        Commands::EncryptSigners { dir } => encrypt_signers(dir),
+        Commands::Config {
+            command: ConfigCommand::Check { file },
+        } => config_check(file),
+        Commands::Abi {
+            command: AbiCommand::Check { chain, blocks },
+        } => abi_check(chain, blocks).await,
     }
 }
 
@@ -50,7 +96,8 @@ async fn seed() -> Result<()> {
     let _ = backend::db::run_migrations(db.pool()).await;
 
     // reuse the seeder logic from shared tools module
-    backend::tools::seed_mock(&db).await
+    let chain_id = cfg.default_chain().chain_id;
+    backend::tools::seed_mock(&db, chain_id).await
 }
 
 async fn update_show(
@@ -75,4 +122,87 @@ async fn update_show(
     Ok(())
 }
 
+fn encrypt_signers(dir: Option<String>) -> Result<()> {
+    let dir = dir
+        .map(std::path::PathBuf::from)
+        .or_else(providers::default_keystore_dir)
+        .ok_or_else(|| eyre::eyre!("pass --dir or set SIGNER_KEYSTORE_DIR"))?;
+    let password = providers::keystore_password_from_env()?
+        .ok_or_else(|| eyre::eyre!("keystore password is not set"))?;
+    let pool = providers::init_signer_pool_from_env()?;
+    pool.load_from_file(providers::default_signers_store_path())?;
+    pool.save_keystore_dir(&dir, &password)?;
+    tracing::info!(dir = %dir.display(), signers = ?pool.names(), "Wrote signer keystores");
+    Ok(())
+}
+
+fn config_check(file: Option<String>) -> Result<()> {
+    let src = match file {
+        Some(path) => config::Sources::from_file(path)?,
+        None => config::Sources::discover()?,
+    };
+    match src.path() {
+        Some(p) => println!("# config file: {}", p.display()),
+        None => println!("# config file: none (env and defaults only)"),
+    }
+    for (setting, value) in src.effective() {
+        match value {
+            Some((v, origin)) => println!(
+                "{:<34} = {:<44} # {} ({})",
+                setting.key,
+                format!("{v:?}"),
+                origin,
+                setting.env
+            ),
+            None => {
+                println!("{:<34}   (unset) # {}", setting.key, setting.env)
+            }
+        }
+    }
+    let cfg = config::Config::from_sources(&src)?;
+    for chain in &cfg.chains {
+        println!(
+            "# chain {} ({}): {}",
+            chain.chain_id, chain.name, chain.ws_rpc_url
+        );
+    }
+    println!("# configuration is valid");
+    Ok(())
+}
+
+async fn abi_check(chain: Option<i64>, blocks: Option<u64>) -> Result<()> {
+    let cfg = config::init_from_env()?;
+    let blocks = blocks.unwrap_or(cfg.abi_check_blocks);
+    let mut clean = true;
+    for c in cfg
+        .chains
+        .iter()
+        .filter(|c| chain.is_none_or(|id| id == c.chain_id))
+    {
+        let provider = providers::ws_public(&c.ws_rpc_url).await?;
+        let reports =
+            backend::contract::abi_check::check_chain(&provider, c, blocks)
+                .await?;
+        for r in &reports {
+            println!(
+                "chain {} {:<16} {}  calls={} logs={}  {}",
+                r.chain_id,
+                r.contract,
+                r.address,
+                r.calls,
+                r.logs,
+                if r.is_clean() { "ok" } else { "DRIFT" }
+            );
+            for d in &r.drift {
+                println!("    - {d}");
+            }
+            clean &= r.is_clean();
+        }
+    }
+    if !clean {
+        eyre::bail!("deployed contracts do not match the bundled ABIs");
+    }
+    Ok(())
+}
+
 // seed_impl removed; logic moved to backend::tools::seed_mock
@@ -1,8 +1,19 @@
-use std::sync::OnceLock;
-use std::{env, str::FromStr};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
 
+use crate::api::rate_limit::{RateLimitConfig, parse_route_budgets};
+use crate::db::cache::CacheBackend;
 use alloy::primitives::Address;
+use axum::http::{HeaderName, HeaderValue};
 use dotenv::dotenv;
+use tracing_subscriber::EnvFilter;
+
+pub mod deployments;
+pub mod sources;
+pub use deployments::{Deployment, Deployments};
+pub use sources::{ChainSource, Origin, Sources};
 
 #[derive(Clone, Debug)]
 pub struct FeatureFlags {
@@ -15,88 +26,666 @@ pub struct FeatureFlags {
 pub struct AddressMap {
     pub did_registry: Address,
     pub show_manager: Address,
+    /// 可选：未配置时不索引 TicketManager 事件
+    pub ticket_manager: Option<Address>,
+    /// 可选：未配置时不索引 Marketplace 挂单
+    pub marketplace: Option<Address>,
+}
+
+impl AddressMap {
+    /// 已配置的合约：(名称, 地址)；名称用于索引检查点与 /status/sync
+    pub fn contracts(&self) -> Vec<(&'static str, Address)> {
+        let mut out = vec![
+            ("show_manager", self.show_manager),
+            ("did_registry", self.did_registry),
+        ];
+        if let Some(a) = self.ticket_manager {
+            out.push(("ticket_manager", a));
+        }
+        if let Some(a) = self.marketplace {
+            out.push(("marketplace", a));
+        }
+        out
+    }
+
+    pub fn contract_name(&self, addr: &Address) -> Option<&'static str> {
+        self.contracts()
+            .into_iter()
+            .find(|(_, a)| a == addr)
+            .map(|(name, _)| name)
+    }
 }
 
+/// 一条被索引的链：RPC 地址与该链上的合约
 #[derive(Clone, Debug)]
-pub struct Config {
+pub struct ChainConfig {
+    pub chain_id: i64,
+    /// 日志中使用的名称
+    pub name: String,
     pub ws_rpc_url: String,
+    pub addresses: AddressMap,
+    /// 各合约的部署区块（来自部署清单）；合约还没有检查点时从这里开始回填
+    pub deploy_blocks: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// HTTP API 监听地址（API_HOST:API_PORT）
+    pub api_addr: SocketAddr,
+    /// 关闭时等待在途请求与索引器收尾的最长时间
+    pub shutdown_timeout_secs: u64,
+    /// 被索引的链，至少一条。第一条为默认链（WS_RPC_URL / CHAIN_ID 与各合约地址），
+    /// 其余来自配置文件的 `[[chains]]`。
+    pub chains: Vec<ChainConfig>,
+    /// 启动回填时每次 eth_getLogs 覆盖的区块数
+    pub backfill_chunk_blocks: u64,
+    /// 启动时检查内置 ABI 与已部署合约是否一致（只告警，不阻止启动）
+    pub abi_check_on_startup: bool,
+    /// ABI 检查时回看的区块数（用于核对事件签名）
+    pub abi_check_blocks: u64,
     pub database_url: String,
     pub flags: FeatureFlags,
-    pub addresses: AddressMap,
+    /// SignerPool 中用于提交 createShow 的 signer 名称
+    pub show_signer: String,
+    /// 远程签名服务单次请求超时（含读取响应）
+    pub remote_signer_timeout_secs: u64,
+    /// 远程签名服务建立连接超时
+    pub remote_signer_connect_timeout_secs: u64,
+    /// 可选：Redis 地址（如 redis://127.0.0.1:6379）
+    pub redis_url: Option<String>,
+    /// 缓存后端；默认配置了 REDIS_URL 用 redis，否则用进程内 LRU
+    pub cache_backend: CacheBackend,
+    pub cache_memory_capacity: usize,
+    /// 单条实体（show/ticket）缓存 TTL
+    pub show_cache_ttl_secs: u64,
+    /// 列表页缓存 TTL（较短，作为代际作废之外的兜底）
+    pub list_cache_ttl_secs: u64,
+    /// show 过期后仍可返回旧值（同时后台刷新）的窗口
+    pub show_cache_stale_secs: u64,
+    /// 不存在的 show 的负缓存 TTL
+    pub show_negative_ttl_secs: u64,
+    /// webhook 投递最大尝试次数（含首次）
+    pub webhook_max_attempts: u32,
+    /// 单次 webhook 请求超时
+    pub webhook_timeout_secs: u64,
+    /// 投递队列为空时的轮询间隔
+    pub webhook_poll_interval_ms: u64,
+    /// GraphQL 查询最大嵌套深度
+    pub graphql_max_depth: usize,
+    /// GraphQL 查询最大复杂度（列表字段按 limit 倍数计）
+    pub graphql_max_complexity: usize,
+    /// /admin/* 接口的 Bearer token；未配置时这些接口一律返回 403
+    pub admin_token: Option<String>,
+    /// 按 IP / 已认证地址限流
+    pub rate_limit: RateLimitConfig,
+    pub cors: CorsConfig,
+    pub logging: LoggingConfig,
 }
 
 impl Config {
+    /// 从默认来源（配置文件 + 环境变量）加载
     pub fn load() -> eyre::Result<Self> {
         // dotenv() is called in main; we don't call here to keep side-effects explicit
-        let ws_rpc_url = env::var("WS_RPC_URL")
-            .unwrap_or_else(|_| "ws://127.0.0.1:8545".to_string());
-        if !(ws_rpc_url.starts_with("ws://")
-            || ws_rpc_url.starts_with("wss://"))
+        Self::from_sources(&Sources::discover()?)
+    }
+
+    /// 从给定来源加载并校验全部配置项
+    pub fn from_sources(src: &Sources) -> eyre::Result<Self> {
+        let database_url = required(src, "DATABASE_URL")?;
+        let chains = chains_from_sources(src)?;
+        let backfill_chunk_blocks = positive(src, "BACKFILL_CHUNK_BLOCKS")?;
+        let abi_check_on_startup = flag(src, "ABI_CHECK_ON_STARTUP")?;
+        let abi_check_blocks = positive(src, "ABI_CHECK_BLOCKS")?;
+
+        let flags = FeatureFlags {
+            print_raw_logs: flag(src, "PRINT_RAW_LOGS")?,
+            print_unknown: flag(src, "PRINT_UNKNOWN_LOGS")?,
+        };
+
+        let show_signer = string(src, "SHOW_SIGNER");
+        let remote_signer_timeout_secs =
+            positive(src, "REMOTE_SIGNER_TIMEOUT_SECS")?;
+        let remote_signer_connect_timeout_secs =
+            positive(src, "REMOTE_SIGNER_CONNECT_TIMEOUT_SECS")?;
+
+        let redis_url = src.var("REDIS_URL").map(|s| s.trim().to_string());
+        if let Some(url) = &redis_url
+            && !(url.starts_with("redis://") || url.starts_with("rediss://"))
         {
             eyre::bail!(
-                "WS_RPC_URL must start with ws:// or wss://, got: {}",
-                ws_rpc_url
+                "REDIS_URL must start with redis:// or rediss://, got: {}",
+                url
             );
         }
-        let database_url = env::var("DATABASE_URL")
-            .map_err(|_| eyre::eyre!("DATABASE_URL is required"))?;
-
-        let did_s = env::var("DID_REGISTRY_ADDRESS")
-            .map_err(|_| eyre::eyre!("DID_REGISTRY_ADDRESS is required"))?;
-        let show_s = env::var("SHOW_MANAGER_ADDRESS")
-            .map_err(|_| eyre::eyre!("SHOW_MANAGER_ADDRESS is required"))?;
-        for (name, val) in [
-            ("DID_REGISTRY_ADDRESS", &did_s),
-            ("SHOW_MANAGER_ADDRESS", &show_s),
-        ] {
-            if !val.starts_with("0x") {
-                eyre::bail!("{} must start with 0x, got: {}", name, val);
+        let cache_backend = match src.var("CACHE_BACKEND") {
+            Some(s) => {
+                CacheBackend::from_str(&s).map_err(|e| eyre::eyre!(e))?
             }
-            if val.len() != 42 {
+            None if redis_url.is_some() => CacheBackend::Redis,
+            None => CacheBackend::Memory,
+        };
+        if cache_backend == CacheBackend::Redis && redis_url.is_none() {
+            eyre::bail!("CACHE_BACKEND=redis requires REDIS_URL");
+        }
+        let cache_memory_capacity =
+            positive(src, "CACHE_MEMORY_CAPACITY")? as usize;
+        let show_cache_ttl_secs = positive(src, "SHOW_CACHE_TTL_SECS")?;
+        let list_cache_ttl_secs = positive(src, "LIST_CACHE_TTL_SECS")?;
+        // 0 表示关闭 stale 窗口
+        let show_cache_stale_secs = non_negative(src, "SHOW_CACHE_STALE_SECS")?;
+        let show_negative_ttl_secs = positive(src, "SHOW_NEGATIVE_TTL_SECS")?;
+        let webhook_max_attempts =
+            u32::try_from(positive(src, "WEBHOOK_MAX_ATTEMPTS")?).map_err(
+                |_| eyre::eyre!("WEBHOOK_MAX_ATTEMPTS is too large"),
+            )?;
+        let webhook_timeout_secs = positive(src, "WEBHOOK_TIMEOUT_SECS")?;
+        let webhook_poll_interval_ms =
+            positive(src, "WEBHOOK_POLL_INTERVAL_MS")?;
+        let api_host = string(src, "API_HOST").trim().to_string();
+        let api_host = IpAddr::from_str(&api_host).map_err(|_| {
+            eyre::eyre!("API_HOST must be an IP address, got: {}", api_host)
+        })?;
+        let api_port = string(src, "API_PORT");
+        let api_port = api_port.trim().parse::<u16>().map_err(|_| {
+            eyre::eyre!("API_PORT must be a port number, got: {}", api_port)
+        })?;
+        let api_addr = SocketAddr::new(api_host, api_port);
+        let shutdown_timeout_secs = positive(src, "SHUTDOWN_TIMEOUT_SECS")?;
+        let graphql_max_depth = positive(src, "GRAPHQL_MAX_DEPTH")? as usize;
+        let graphql_max_complexity =
+            positive(src, "GRAPHQL_MAX_COMPLEXITY")? as usize;
+        let rate_limit = RateLimitConfig {
+            enabled: flag(src, "RATE_LIMIT_ENABLED")?,
+            window_secs: positive(src, "RATE_LIMIT_WINDOW_SECS")?,
+            read_limit: positive(src, "RATE_LIMIT_READ")?,
+            write_limit: positive(src, "RATE_LIMIT_WRITE")?,
+            routes: parse_route_budgets(&string(src, "RATE_LIMIT_ROUTES"))
+                .map_err(|e| eyre::eyre!("Invalid RATE_LIMIT_ROUTES: {}", e))?,
+            trust_forwarded: flag(src, "RATE_LIMIT_TRUST_FORWARDED")?,
+        };
+        let admin_token = src
+            .var("ADMIN_TOKEN")
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let cors = CorsConfig::from_sources(src)?;
+        let logging = LoggingConfig::from_sources(src)?;
+        let request_id_header = string(src, "REQ_ID_HEADER");
+        HeaderName::from_str(&request_id_header).map_err(|_| {
+            eyre::eyre!(
+                "REQ_ID_HEADER must be a valid header name, got: {}",
+                request_id_header
+            )
+        })?;
+
+        Ok(Self {
+            api_addr,
+            shutdown_timeout_secs,
+            chains,
+            backfill_chunk_blocks,
+            abi_check_on_startup,
+            abi_check_blocks,
+            database_url,
+            flags,
+            show_signer,
+            remote_signer_timeout_secs,
+            remote_signer_connect_timeout_secs,
+            redis_url,
+            cache_backend,
+            cache_memory_capacity,
+            show_cache_ttl_secs,
+            list_cache_ttl_secs,
+            show_cache_stale_secs,
+            show_negative_ttl_secs,
+            webhook_max_attempts,
+            webhook_timeout_secs,
+            webhook_poll_interval_ms,
+            graphql_max_depth,
+            graphql_max_complexity,
+            admin_token,
+            rate_limit,
+            cors,
+            logging,
+        })
+    }
+}
+
+impl Config {
+    /// 默认链：未指定 chain_id 的 API 查询、POST /show 提交与签名交易都使用它
+    pub fn default_chain(&self) -> &ChainConfig {
+        &self.chains[0]
+    }
+
+    pub fn chain(&self, chain_id: i64) -> Option<&ChainConfig> {
+        self.chains.iter().find(|c| c.chain_id == chain_id)
+    }
+
+    /// 解析 API 的 `?chain_id=`：省略时为默认链，未配置的链返回错误
+    pub fn resolve_chain(
+        &self,
+        chain_id: Option<i64>,
+    ) -> Result<&ChainConfig, String> {
+        match chain_id {
+            None => Ok(self.default_chain()),
+            Some(id) => self
+                .chain(id)
+                .ok_or_else(|| format!("chain_id {id} is not configured")),
+        }
+    }
+}
+
+/// 默认链来自单项配置（可被环境变量覆盖），附加链来自配置文件的 `[[chains]]`
+fn chains_from_sources(src: &Sources) -> eyre::Result<Vec<ChainConfig>> {
+    let chain_id = string(src, "CHAIN_ID");
+    let chain_id = chain_id
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|v| *v > 0)
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "CHAIN_ID must be a positive integer, got: {}",
+                chain_id
+            )
+        })?;
+    let manifest = load_manifest(
+        "DEPLOYMENTS_FILE",
+        src.var("DEPLOYMENTS_FILE").as_deref(),
+        chain_id,
+    )?;
+    let mut blocks = BTreeMap::new();
+    let mut pick = |contract, explicit| {
+        deployed(contract, explicit, manifest.as_ref(), &mut blocks)
+    };
+    let explicit =
+        |name: &str| src.var(name).map(|v| address(name, v.trim())).transpose();
+    let addresses = AddressMap {
+        did_registry: pick("did_registry", explicit("DID_REGISTRY_ADDRESS")?)
+            .ok_or_else(|| missing_address("DID_REGISTRY_ADDRESS"))?,
+        show_manager: pick("show_manager", explicit("SHOW_MANAGER_ADDRESS")?)
+            .ok_or_else(|| missing_address("SHOW_MANAGER_ADDRESS"))?,
+        ticket_manager: pick(
+            "ticket_manager",
+            optional_address(src, "TICKET_MANAGER_ADDRESS")?,
+        ),
+        marketplace: pick(
+            "marketplace",
+            optional_address(src, "MARKETPLACE_ADDRESS")?,
+        ),
+    };
+    let mut chains = vec![ChainConfig {
+        chain_id,
+        name: string(src, "CHAIN_NAME"),
+        ws_rpc_url: ws_url("WS_RPC_URL", &string(src, "WS_RPC_URL"))?,
+        addresses,
+        deploy_blocks: blocks,
+    }];
+    for c in src.chains() {
+        let field = |name: &str| format!("chains[{}].{}", c.id, name);
+        if c.id <= 0 {
+            eyre::bail!(
+                "chains[].id must be a positive integer, got: {}",
+                c.id
+            );
+        }
+        if chains.iter().any(|existing| existing.chain_id == c.id) {
+            eyre::bail!("chain id {} is configured more than once", c.id);
+        }
+        let manifest = load_manifest(
+            &field("deployments"),
+            c.deployments.as_deref(),
+            c.id,
+        )?;
+        let mut blocks = BTreeMap::new();
+        let mut pick = |contract, explicit| {
+            deployed(contract, explicit, manifest.as_ref(), &mut blocks)
+        };
+        let explicit = |name: &str, v: &Option<String>| {
+            v.as_deref().map(|s| address(&field(name), s)).transpose()
+        };
+        let addresses = AddressMap {
+            did_registry: pick(
+                "did_registry",
+                explicit("did_registry", &c.did_registry)?,
+            )
+            .ok_or_else(|| missing_address(&field("did_registry")))?,
+            show_manager: pick(
+                "show_manager",
+                explicit("show_manager", &c.show_manager)?,
+            )
+            .ok_or_else(|| missing_address(&field("show_manager")))?,
+            ticket_manager: pick(
+                "ticket_manager",
+                explicit("ticket_manager", &c.ticket_manager)?,
+            ),
+            marketplace: pick(
+                "marketplace",
+                explicit("marketplace", &c.marketplace)?,
+            ),
+        };
+        chains.push(ChainConfig {
+            chain_id: c.id,
+            name: c.name.clone().unwrap_or_else(|| c.id.to_string()),
+            ws_rpc_url: ws_url(&field("ws_url"), &c.ws_url)?,
+            addresses,
+            deploy_blocks: blocks,
+        });
+    }
+    Ok(chains)
+}
+
+/// 读取部署清单，并核对清单记录的链与所配置的链一致
+fn load_manifest(
+    name: &str,
+    path: Option<&str>,
+    chain_id: i64,
+) -> eyre::Result<Option<Deployments>> {
+    let Some(path) = path else {
+        return Ok(None);
+    };
+    let manifest = Deployments::from_file(path.trim())?;
+    if let Some(actual) = manifest.chain_id
+        && actual != chain_id
+    {
+        eyre::bail!(
+            "{} {} was deployed on chain {}, expected {}",
+            name,
+            path,
+            actual,
+            chain_id
+        );
+    }
+    Ok(Some(manifest))
+}
+
+/// 合约地址：显式配置优先，否则取部署清单。
+/// 只有地址与清单一致时才记录清单中的部署区块（显式覆盖的地址部署区块未知）。
+fn deployed(
+    contract: &'static str,
+    explicit: Option<Address>,
+    manifest: Option<&Deployments>,
+    blocks: &mut BTreeMap<&'static str, u64>,
+) -> Option<Address> {
+    let entry = manifest.and_then(|m| m.get(contract));
+    let addr = explicit.or(entry.map(|d| d.address))?;
+    if let Some(block) =
+        entry.filter(|d| d.address == addr).and_then(|d| d.block)
+    {
+        blocks.insert(contract, block);
+    }
+    Some(addr)
+}
+
+fn missing_address(name: &str) -> eyre::Report {
+    eyre::eyre!(
+        "{} is required (set it or provide a deployments file)",
+        name
+    )
+}
+
+fn ws_url(name: &str, url: &str) -> eyre::Result<String> {
+    if !(url.starts_with("ws://") || url.starts_with("wss://")) {
+        eyre::bail!("{} must start with ws:// or wss://, got: {}", name, url);
+    }
+    Ok(url.to_string())
+}
+
+/// 必填的合约地址：0x 前缀的 20 字节 hex
+fn address(name: &str, val: &str) -> eyre::Result<Address> {
+    if !val.starts_with("0x") {
+        eyre::bail!("{} must start with 0x, got: {}", name, val);
+    }
+    if val.len() != 42 {
+        eyre::bail!(
+            "{} must be a 20-byte hex address (42 chars with 0x), got len {}: {}",
+            name,
+            val.len(),
+            val
+        );
+    }
+    Address::from_str(val).map_err(|e| eyre::eyre!("Invalid {}: {}", name, e))
+}
+
+/// 允许的跨域来源；包含 `*` 时允许任意来源
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    pub allow_origins: Vec<String>,
+}
+
+impl CorsConfig {
+    pub fn allow_any(&self) -> bool {
+        self.allow_origins.iter().any(|o| o == "*")
+    }
+
+    fn from_sources(src: &Sources) -> eyre::Result<Self> {
+        let allow_origins: Vec<String> = string(src, "CORS_ALLOW_ORIGIN")
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        for o in allow_origins.iter().filter(|o| *o != "*") {
+            if !(o.starts_with("http://") || o.starts_with("https://"))
+                || HeaderValue::from_str(o).is_err()
+            {
                 eyre::bail!(
-                    "{} must be a 20-byte hex address (42 chars with 0x), got len {}: {}",
-                    name,
-                    val.len(),
-                    val
+                    "CORS_ALLOW_ORIGIN entries must be http(s) origins or *, got: {}",
+                    o
                 );
             }
         }
-        let did = Address::from_str(&did_s)
-            .map_err(|e| eyre::eyre!("Invalid DID_REGISTRY_ADDRESS: {}", e))?;
-        let show = Address::from_str(&show_s)
-            .map_err(|e| eyre::eyre!("Invalid SHOW_MANAGER_ADDRESS: {}", e))?;
+        Ok(Self { allow_origins })
+    }
+}
 
-        let flags = FeatureFlags {
-            print_raw_logs: env::var("PRINT_RAW_LOGS").ok().as_deref()
-                == Some("1"),
-            print_unknown: env::var("PRINT_UNKNOWN_LOGS").ok().as_deref()
-                == Some("1"),
-        };
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
 
-        let addresses = AddressMap {
-            did_registry: did,
-            show_manager: show,
-        };
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogRotation {
+    Never,
+    Daily,
+    Hourly,
+    Minutely,
+}
+
+/// 日志配置；`logging::init` 在全局配置就绪前调用，因此可单独加载
+#[derive(Clone, Debug)]
+pub struct LoggingConfig {
+    /// EnvFilter 指令（RUST_LOG），如 `backend=debug,axum=info`
+    pub filter: String,
+    pub format: LogFormat,
+    pub ansi: bool,
+    /// 额外写入的日志文件；不含目录时放在 `dir` 下
+    pub file: Option<String>,
+    pub dir: Option<String>,
+    pub rotation: LogRotation,
+    /// 记录 HTTP 请求头（可能包含敏感信息）
+    pub http_headers: bool,
+    /// 记录 HTTP body chunk
+    pub http_body: bool,
+}
 
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            filter: "info".to_string(),
+            format: LogFormat::Pretty,
+            ansi: true,
+            file: None,
+            dir: None,
+            rotation: LogRotation::Never,
+            http_headers: false,
+            http_body: false,
+        }
+    }
+}
+
+impl LoggingConfig {
+    pub fn from_sources(src: &Sources) -> eyre::Result<Self> {
+        let filter = string(src, "RUST_LOG");
+        EnvFilter::try_new(&filter)
+            .map_err(|e| eyre::eyre!("Invalid RUST_LOG {:?}: {}", filter, e))?;
+        let format =
+            match string(src, "LOG_FORMAT").to_ascii_lowercase().as_str() {
+                "pretty" => LogFormat::Pretty,
+                "json" => LogFormat::Json,
+                other => eyre::bail!(
+                    "LOG_FORMAT must be pretty or json, got: {}",
+                    other
+                ),
+            };
+        let rotation =
+            match string(src, "LOG_ROTATION").to_ascii_lowercase().as_str() {
+                "never" => LogRotation::Never,
+                "daily" => LogRotation::Daily,
+                "hourly" => LogRotation::Hourly,
+                "minutely" => LogRotation::Minutely,
+                other => eyre::bail!(
+                    "LOG_ROTATION must be never|daily|hourly|minutely, got: {}",
+                    other
+                ),
+            };
         Ok(Self {
-            ws_rpc_url,
-            database_url,
-            flags,
-            addresses,
+            filter,
+            format,
+            ansi: flag(src, "LOG_ANSI")?,
+            file: src.var("LOG_FILE"),
+            dir: src.var("LOG_DIR"),
+            rotation,
+            http_headers: flag(src, "LOG_HTTP_HEADERS")?,
+            http_body: flag(src, "LOG_HTTP_BODY")?,
+        })
+    }
+}
+
+/// 读取带默认值的配置项；配置项表中没有默认值时返回空串
+fn string(src: &Sources, name: &str) -> String {
+    src.var(name).unwrap_or_default()
+}
+
+fn required(src: &Sources, name: &str) -> eyre::Result<String> {
+    src.var(name)
+        .ok_or_else(|| eyre::eyre!("{} is required", name))
+}
+
+/// 读取可选的合约地址；未设置时返回 None
+fn optional_address(
+    src: &Sources,
+    name: &str,
+) -> eyre::Result<Option<Address>> {
+    match src.var(name) {
+        Some(s) => Address::from_str(s.trim())
+            .map(Some)
+            .map_err(|e| eyre::eyre!("Invalid {}: {}", name, e)),
+        None => Ok(None),
+    }
+}
+
+/// 读取正整数配置项；默认值来自配置项表
+fn positive(src: &Sources, name: &str) -> eyre::Result<u64> {
+    let s = required(src, name)?;
+    s.trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|v| *v > 0)
+        .ok_or_else(|| {
+            eyre::eyre!("{} must be a positive integer, got: {}", name, s)
         })
+}
+
+/// 读取非负整数配置项（允许 0）
+fn non_negative(src: &Sources, name: &str) -> eyre::Result<u64> {
+    let s = required(src, name)?;
+    s.trim().parse::<u64>().map_err(|_| {
+        eyre::eyre!("{} must be a non-negative integer, got: {}", name, s)
+    })
+}
+
+/// 布尔配置项：1/true/yes/on 或 0/false/no/off
+fn flag(src: &Sources, name: &str) -> eyre::Result<bool> {
+    match string(src, name).trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "" | "0" | "false" | "no" | "off" => Ok(false),
+        other => {
+            eyre::bail!("{} must be a boolean (1/0), got: {}", name, other)
+        }
     }
 }
 
+static SOURCES: OnceLock<Sources> = OnceLock::new();
+
+/// 进程级配置来源。配置文件无法解析时退回只读环境变量，错误由 `init_from_env` 报告。
+pub fn sources() -> &'static Sources {
+    SOURCES.get_or_init(|| {
+        Sources::discover().unwrap_or_else(|_| Sources::env_only())
+    })
+}
+
+/// 读取单个配置项（环境变量优先，其次配置文件与默认值），供全局配置之外的模块使用
+pub fn var(name: &str) -> Option<String> {
+    sources().var(name)
+}
+
 static CONFIG: OnceLock<Config> = OnceLock::new();
 
-/// 初始化全局配置（从 .env 环境变量）。应在程序启动时调用一次。
+/// 初始化全局配置（配置文件 + .env / 环境变量）。应在程序启动时调用一次。
 pub fn init_from_env() -> eyre::Result<&'static Config> {
     dotenv().ok();
-    let cfg = Config::load()?;
+    let src = Sources::discover()?;
+    let cfg = Config::from_sources(&src)?;
+    if let Some(path) = src.path() {
+        tracing::info!(path = %path.display(), "Loaded config file");
+    }
+    let _ = SOURCES.set(src);
     let _ = CONFIG.set(cfg);
     Ok(CONFIG.get().expect("config must be initialized"))
 }
 
+/// 重新加载的结果：`applied` 已即时生效，`restart_required` 需重启进程才会生效
+#[derive(Debug, Default)]
+pub struct ReloadReport {
+    pub applied: Vec<&'static str>,
+    pub restart_required: Vec<&'static str>,
+}
+
+/// 最近一次成功加载的配置来源（初始为启动时的来源）；每次 reload 成功后替换，
+/// 同一处变化只在发生的那次 reload 中报告。
+static LOADED: RwLock<Option<Sources>> = RwLock::new(None);
+
+/// 重新读取配置文件并完整校验；校验失败时保持现有配置不变。
+/// 目前只有日志过滤器（logging.filter / RUST_LOG）支持热加载，其余变化仅在报告中列出。
+pub fn reload() -> eyre::Result<ReloadReport> {
+    reload_from(Sources::discover()?)
+}
+
+/// 以给定来源执行 reload（`reload` 的实现，单独暴露便于测试）
+pub fn reload_from(next: Sources) -> eyre::Result<ReloadReport> {
+    let cfg = Config::from_sources(&next)?;
+    let mut loaded = LOADED.write().unwrap_or_else(|e| e.into_inner());
+    let current = loaded.get_or_insert_with(|| sources().clone());
+    let mut report = ReloadReport::default();
+    for ((setting, old), (_, new)) in
+        current.effective().iter().zip(next.effective())
+    {
+        if old.as_ref().map(|(v, _)| v) == new.as_ref().map(|(v, _)| v) {
+            continue;
+        }
+        if setting.env == "RUST_LOG" {
+            crate::logging::set_filter(&cfg.logging.filter)?;
+            report.applied.push(setting.key);
+        } else {
+            report.restart_required.push(setting.key);
+        }
+    }
+    if current.chains() != next.chains() {
+        report.restart_required.push("chains");
+    }
+    *loaded = Some(next);
+    Ok(report)
+}
+
 /// 获取全局配置的引用；在 init_from_env() 之后使用。
 pub fn get() -> &'static Config {
     CONFIG
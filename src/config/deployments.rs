@@ -0,0 +1,106 @@
+//! 部署清单：从 Foundry broadcast 文件（`broadcast/<Script>.s.sol/<chain>/run-latest.json`）
+//! 读取各合约的地址与部署区块，免去每次部署后手工把地址抄进 `.env`。
+use alloy::primitives::{Address, B256, U64};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// 一个合约的部署信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    pub address: Address,
+    /// 部署交易所在区块；broadcast 中没有对应 receipt 时为空
+    pub block: Option<u64>,
+}
+
+/// 清单中被索引的合约，按 `AddressMap::contracts()` 的名称索引
+#[derive(Debug, Clone, Default)]
+pub struct Deployments {
+    /// broadcast 文件记录的链 ID
+    pub chain_id: Option<i64>,
+    contracts: BTreeMap<&'static str, Deployment>,
+}
+
+#[derive(Deserialize)]
+struct Broadcast {
+    transactions: Vec<BroadcastTx>,
+    #[serde(default)]
+    receipts: Vec<BroadcastReceipt>,
+    chain: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BroadcastTx {
+    hash: Option<B256>,
+    transaction_type: String,
+    contract_name: Option<String>,
+    contract_address: Option<Address>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BroadcastReceipt {
+    transaction_hash: B256,
+    block_number: U64,
+}
+
+/// Solidity 合约名 -> 索引器使用的合约名
+fn contract_key(name: &str) -> Option<&'static str> {
+    match name {
+        "DIDRegistry" | "DidRegistry" => Some("did_registry"),
+        "ShowManager" => Some("show_manager"),
+        "TicketManager" => Some("ticket_manager"),
+        "Marketplace" => Some("marketplace"),
+        _ => None,
+    }
+}
+
+impl Deployments {
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| {
+            eyre::eyre!(
+                "failed to read deployments file {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        Self::from_json(&text).map_err(|e| {
+            eyre::eyre!("invalid deployments file {}: {}", path.display(), e)
+        })
+    }
+
+    /// 解析 broadcast JSON；同一合约部署多次时以最后一次为准，未知合约忽略
+    pub fn from_json(text: &str) -> eyre::Result<Self> {
+        let broadcast: Broadcast = serde_json::from_str(text)?;
+        let blocks: BTreeMap<B256, u64> = broadcast
+            .receipts
+            .iter()
+            .map(|r| (r.transaction_hash, r.block_number.to::<u64>()))
+            .collect();
+        let mut contracts = BTreeMap::new();
+        for tx in broadcast.transactions {
+            if !matches!(tx.transaction_type.as_str(), "CREATE" | "CREATE2") {
+                continue;
+            }
+            let (Some(key), Some(address)) = (
+                tx.contract_name.as_deref().and_then(contract_key),
+                tx.contract_address,
+            ) else {
+                continue;
+            };
+            let block = tx.hash.and_then(|h| blocks.get(&h).copied());
+            contracts.insert(key, Deployment { address, block });
+        }
+        Ok(Self {
+            chain_id: broadcast.chain,
+            contracts,
+        })
+    }
+
+    pub fn get(&self, contract: &str) -> Option<&Deployment> {
+        self.contracts.get(contract)
+    }
+}
@@ -0,0 +1,251 @@
+//! Drift detection between the JSON ABIs bundled under `src/contract/abis/` and the
+//! contracts actually deployed.
+//!
+//! Two signals are checked per contract:
+//! - every zero-argument `view`/`pure` function is called and its return data decoded with
+//!   the bundled output types; a revert or undecodable output means the function changed
+//!   or no longer exists;
+//! - logs emitted in recent blocks are matched against the bundled events; an unknown
+//!   `topic0` or a different number of indexed topics means the contract emits events
+//!   this build cannot decode.
+//!
+//! Either usually means the contracts team redeployed with a newer ABI.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use alloy::{
+    dyn_abi::FunctionExt,
+    json_abi::{Function, JsonAbi},
+    primitives::{Address, B256},
+    providers::Provider,
+    rpc::types::{Filter, Log, TransactionInput, TransactionRequest},
+};
+use eyre::Result;
+
+use crate::config::ChainConfig;
+
+/// Bundled ABI for an indexed contract, by the names used in `AddressMap::contracts()`.
+pub fn bundled_abi(contract: &str) -> Option<JsonAbi> {
+    let json = match contract {
+        "did_registry" => include_str!("abis/DIDRegistry.json"),
+        "show_manager" => include_str!("abis/ShowManager.json"),
+        "ticket_manager" => include_str!("abis/TicketManager.json"),
+        "marketplace" => include_str!("abis/Marketplace.json"),
+        _ => return None,
+    };
+    Some(serde_json::from_str(json).expect("bundled ABI is valid JSON"))
+}
+
+/// One sign that the deployed contract does not match its bundled ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// A zero-argument view function reverted or the call failed.
+    CallFailed { function: String, error: String },
+    /// Return data could not be decoded with the bundled output types.
+    OutputMismatch { function: String, error: String },
+    /// A recent log's topic0 matches no event in the bundled ABI.
+    UnknownEvent { topic0: B256, block: Option<u64> },
+    /// A known event was emitted with a different number of topics.
+    TopicLayout {
+        event: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::CallFailed { function, error } => {
+                write!(f, "{function}() call failed: {error}")
+            }
+            Drift::OutputMismatch { function, error } => {
+                write!(
+                    f,
+                    "{function}() returned data the bundled ABI cannot decode: {error}"
+                )
+            }
+            Drift::UnknownEvent { topic0, block } => match block {
+                Some(b) => write!(f, "unknown event {topic0} (block {b})"),
+                None => write!(f, "unknown event {topic0}"),
+            },
+            Drift::TopicLayout {
+                event,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{event} emitted with {actual} topics, bundled ABI expects {expected}"
+            ),
+        }
+    }
+}
+
+/// Result of checking one deployed contract.
+#[derive(Debug, Clone)]
+pub struct ContractReport {
+    pub chain_id: i64,
+    pub contract: &'static str,
+    pub address: Address,
+    /// View functions called.
+    pub calls: usize,
+    /// Logs inspected in the recent block window.
+    pub logs: usize,
+    pub drift: Vec<Drift>,
+}
+
+impl ContractReport {
+    pub fn is_clean(&self) -> bool {
+        self.drift.is_empty()
+    }
+}
+
+/// Zero-argument `view`/`pure` functions in the ABI, i.e. those that can be called blind.
+pub fn view_functions(abi: &JsonAbi) -> Vec<&Function> {
+    use alloy::json_abi::StateMutability;
+    abi.functions()
+        .filter(|f| {
+            f.inputs.is_empty()
+                && matches!(
+                    f.state_mutability,
+                    StateMutability::View | StateMutability::Pure
+                )
+        })
+        .collect()
+}
+
+/// Decode a view call's return data with the bundled output types.
+pub fn check_output(function: &Function, data: &[u8]) -> Option<Drift> {
+    function
+        .abi_decode_output(data)
+        .err()
+        .map(|e| Drift::OutputMismatch {
+            function: function.name.clone(),
+            error: e.to_string(),
+        })
+}
+
+/// Match a log against the bundled events by topic0 and topic count.
+/// Anonymous events have no topic0 and cannot be matched, so they are not reported.
+pub fn check_log(abi: &JsonAbi, log: &Log) -> Option<Drift> {
+    let topic0 = *log.topics().first()?;
+    let Some(event) = abi
+        .events()
+        .find(|e| !e.anonymous && e.selector() == topic0)
+    else {
+        return Some(Drift::UnknownEvent {
+            topic0,
+            block: log.block_number,
+        });
+    };
+    let expected = 1 + event.inputs.iter().filter(|p| p.indexed).count();
+    let actual = log.topics().len();
+    (expected != actual).then(|| Drift::TopicLayout {
+        event: event.name.clone(),
+        expected,
+        actual,
+    })
+}
+
+/// Check one contract: call its view functions and inspect logs from the last
+/// `recent_blocks` blocks. Only RPC transport errors are returned as `Err`.
+pub async fn check_contract<P: Provider>(
+    provider: &P,
+    chain_id: i64,
+    contract: &'static str,
+    address: Address,
+    recent_blocks: u64,
+) -> Result<ContractReport> {
+    let abi = bundled_abi(contract)
+        .ok_or_else(|| eyre::eyre!("no bundled ABI for {contract}"))?;
+    let mut report = ContractReport {
+        chain_id,
+        contract,
+        address,
+        calls: 0,
+        logs: 0,
+        drift: Vec::new(),
+    };
+
+    for function in view_functions(&abi) {
+        let tx = TransactionRequest::default()
+            .to(address)
+            .input(TransactionInput::new(function.selector().to_vec().into()));
+        report.calls += 1;
+        match crate::metrics::observe_rpc("eth_call", provider.call(tx)).await {
+            Ok(data) => report.drift.extend(check_output(function, &data)),
+            Err(e) => report.drift.push(Drift::CallFailed {
+                function: function.name.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let head = crate::metrics::observe_rpc(
+        "eth_blockNumber",
+        provider.get_block_number(),
+    )
+    .await?;
+    let filter = Filter::new()
+        .address(address)
+        .from_block(head.saturating_sub(recent_blocks))
+        .to_block(head);
+    let logs =
+        crate::metrics::observe_rpc("eth_getLogs", provider.get_logs(&filter))
+            .await?;
+    report.logs = logs.len();
+    // Report each unknown selector / layout once, at its first occurrence.
+    let mut seen = BTreeMap::new();
+    for drift in logs.iter().filter_map(|log| check_log(&abi, log)) {
+        let key = match &drift {
+            Drift::UnknownEvent { topic0, .. } => topic0.to_string(),
+            Drift::TopicLayout { event, actual, .. } => {
+                format!("{event}/{actual}")
+            }
+            _ => continue,
+        };
+        seen.entry(key).or_insert(drift);
+    }
+    report.drift.extend(seen.into_values());
+
+    crate::metrics::get()
+        .abi_drift
+        .with_label_values(&[&chain_id.to_string(), contract])
+        .set(report.drift.len() as i64);
+    Ok(report)
+}
+
+/// Check every configured contract on a chain.
+pub async fn check_chain<P: Provider>(
+    provider: &P,
+    chain: &ChainConfig,
+    recent_blocks: u64,
+) -> Result<Vec<ContractReport>> {
+    let mut reports = Vec::new();
+    for (contract, address) in chain.addresses.contracts() {
+        reports.push(
+            check_contract(
+                provider,
+                chain.chain_id,
+                contract,
+                address,
+                recent_blocks,
+            )
+            .await?,
+        );
+    }
+    Ok(reports)
+}
+
+/// Log each finding as a warning; returns whether every contract was clean.
+pub fn log_reports(reports: &[ContractReport]) -> bool {
+    for r in reports {
+        if r.is_clean() {
+            tracing::info!(chain_id = r.chain_id, contract = r.contract, address = %r.address, calls = r.calls, logs = r.logs, "ABI check passed");
+        }
+        for d in &r.drift {
+            tracing::warn!(chain_id = r.chain_id, contract = r.contract, address = %r.address, drift = %d, "Deployed contract does not match bundled ABI");
+        }
+    }
+    reports.iter().all(ContractReport::is_clean)
+}
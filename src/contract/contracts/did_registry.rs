@@ -0,0 +1,119 @@
+use crate::{
+    contract::{
+        IndexerContext,
+        bindings::DIDRegistry::{
+            DIDBoundToAddress, DIDControllerTransferred, DIDRegistered,
+            DIDRevoked, DIDUnboundFromAddress, DIDUpdated, DIDVerified,
+        },
+        event::UnhandledEvent,
+    },
+    repo::did_repo::DidUpdate,
+};
+use alloy::{
+    primitives::{Address, B256},
+    rpc::types::Log,
+    sol_types::SolEvent,
+};
+use eyre::Result;
+
+fn hex0x(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn hex_address(a: &Address) -> String {
+    hex0x(a.as_slice())
+}
+
+fn hex_hash(h: &B256) -> String {
+    hex0x(h.as_slice())
+}
+
+/// Project DIDRegistry events into `dids` / `did_addresses`.
+pub async fn parse_event(log: &Log, ctx: &IndexerContext) -> Result<()> {
+    let dids = &ctx.repos.dids;
+    let inner = &log.inner;
+    let Some(topic0) = inner.topics().first() else {
+        return Err(UnhandledEvent("DIDRegistry").into());
+    };
+    let (did_hash, update) = match *topic0 {
+        DIDRegistered::SIGNATURE_HASH => {
+            let event = DIDRegistered::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDRegistered event");
+            dids.upsert_did(
+                ctx.chain_id,
+                &hex_hash(&event.didHash),
+                &event.did,
+                &hex_address(&event.controller),
+                &event.cid,
+            )
+            .await?;
+            return Ok(());
+        }
+        DIDBoundToAddress::SIGNATURE_HASH => {
+            let event = DIDBoundToAddress::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDBoundToAddress event");
+            dids.bind_did_address(
+                ctx.chain_id,
+                &hex_hash(&event.didHash),
+                &hex_address(&event.addr),
+            )
+            .await?;
+            return Ok(());
+        }
+        DIDUnboundFromAddress::SIGNATURE_HASH => {
+            let event = DIDUnboundFromAddress::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDUnboundFromAddress event");
+            dids.unbind_did_address(
+                ctx.chain_id,
+                &hex_hash(&event.didHash),
+                &hex_address(&event.addr),
+            )
+            .await?;
+            return Ok(());
+        }
+        DIDUpdated::SIGNATURE_HASH => {
+            let event = DIDUpdated::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDUpdated event");
+            let update = DidUpdate {
+                cid: Some(event.newCid.clone()),
+                ..Default::default()
+            };
+            (event.didHash, update)
+        }
+        DIDVerified::SIGNATURE_HASH => {
+            let event = DIDVerified::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDVerified event");
+            let update = DidUpdate {
+                verified: Some(true),
+                ..Default::default()
+            };
+            (event.didHash, update)
+        }
+        DIDRevoked::SIGNATURE_HASH => {
+            let event = DIDRevoked::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDRevoked event");
+            let update = DidUpdate {
+                revoked: Some(true),
+                ..Default::default()
+            };
+            (event.didHash, update)
+        }
+        DIDControllerTransferred::SIGNATURE_HASH => {
+            let event = DIDControllerTransferred::decode_log(inner)?;
+            tracing::info!(?event, "Parsed DIDControllerTransferred event");
+            let update = DidUpdate {
+                controller: Some(hex_address(&event.newController)),
+                ..Default::default()
+            };
+            (event.didHash, update)
+        }
+        _ => return Err(UnhandledEvent("DIDRegistry").into()),
+    };
+    if !dids
+        .update_did(ctx.chain_id, &hex_hash(&did_hash), &update)
+        .await?
+    {
+        tracing::warn!(did_hash = %hex_hash(&did_hash), "DIDRegistry event for a DID that was never indexed");
+    }
+    Ok(())
+}
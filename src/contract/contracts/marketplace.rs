@@ -0,0 +1,108 @@
+use crate::{
+    contract::{
+        IndexerContext,
+        bindings::Marketplace::{
+            ListingCancelled, ListingCreated, ListingUpdated, TicketSold,
+        },
+        event::UnhandledEvent,
+    },
+    realtime::RealtimeEvent,
+    repo::listing_repo::{ListingStatus, NewListing},
+    utils::uint256::DbU256,
+    webhook::WebhookEventType,
+};
+use alloy::{rpc::types::Log, sol_types::SolEvent};
+use eyre::Result;
+
+/// Project Marketplace listing events into `listings` and push the new listing state.
+/// Auction events are not indexed yet.
+pub async fn parse_event(log: &Log, ctx: &IndexerContext) -> Result<()> {
+    let listings = &ctx.repos.listings;
+    let inner = &log.inner;
+    let Some(topic0) = inner.topics().first() else {
+        return Err(UnhandledEvent("Marketplace").into());
+    };
+    let tx_hash = log
+        .transaction_hash
+        .map(|h| format!("0x{}", hex::encode(h.as_slice())));
+    let listing = match *topic0 {
+        ListingCreated::SIGNATURE_HASH => {
+            let event = ListingCreated::decode_log(inner)?;
+            tracing::info!(?event, "Parsed ListingCreated event");
+            Some(
+                listings
+                    .upsert_listing(&NewListing {
+                        chain_id: ctx.chain_id,
+                        listing_id: DbU256(event.listingId),
+                        token_id: DbU256(event.tokenId),
+                        seller: format!(
+                            "0x{}",
+                            hex::encode(event.seller.as_slice())
+                        ),
+                        price: DbU256(event.price),
+                        eth_price: DbU256(event.ethPrice),
+                        expires_at: DbU256(event.expiresAt),
+                        tx_hash,
+                    })
+                    .await?,
+            )
+        }
+        ListingUpdated::SIGNATURE_HASH => {
+            let event = ListingUpdated::decode_log(inner)?;
+            tracing::info!(?event, "Parsed ListingUpdated event");
+            listings
+                .update_listing_price(
+                    ctx.chain_id,
+                    &DbU256(event.listingId),
+                    &DbU256(event.newPrice),
+                    &DbU256(event.newEthPrice),
+                    &DbU256(event.newExpiresAt),
+                )
+                .await?
+        }
+        ListingCancelled::SIGNATURE_HASH => {
+            let event = ListingCancelled::decode_log(inner)?;
+            tracing::info!(?event, "Parsed ListingCancelled event");
+            listings
+                .set_listing_status(
+                    ctx.chain_id,
+                    &DbU256(event.listingId),
+                    ListingStatus::Cancelled,
+                    None,
+                )
+                .await?
+        }
+        TicketSold::SIGNATURE_HASH => {
+            let event = TicketSold::decode_log(inner)?;
+            tracing::info!(?event, "Parsed Marketplace TicketSold event");
+            let buyer = format!("0x{}", hex::encode(event.buyer.as_slice()));
+            listings
+                .set_listing_status(
+                    ctx.chain_id,
+                    &DbU256(event.listingId),
+                    ListingStatus::Sold,
+                    Some(&buyer),
+                )
+                .await?
+        }
+        _ => return Err(UnhandledEvent("Marketplace").into()),
+    };
+    let Some(listing) = listing else {
+        tracing::warn!(
+            "Marketplace event for a listing that was never indexed"
+        );
+        return Ok(());
+    };
+    let show_id = ctx
+        .repos
+        .tickets
+        .get_ticket(ctx.chain_id, &listing.token_id)
+        .await?
+        .map(|t| t.event_id);
+    ctx.emit(
+        WebhookEventType::ListingChanged,
+        RealtimeEvent::ListingChanged { show_id, listing },
+    )
+    .await;
+    Ok(())
+}
@@ -1,2 +1,4 @@
+pub mod did_registry;
+pub mod marketplace;
 pub mod show_manager;
 pub mod ticket_manager;
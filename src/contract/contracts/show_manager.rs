@@ -1,37 +1,43 @@
 use crate::{
-    contract::bindings::ShowManager::{
-        Show as OnchainShow, ShowCreated, ShowManagerInstance,
+    contract::{
+        IndexerContext,
+        bindings::ShowManager::{
+            Show as OnchainShow, ShowActivated, ShowCancelled, ShowCreated,
+            ShowEnded, ShowManagerInstance, ShowUpdated,
+        },
+        event::UnhandledEvent,
     },
-    db::Db,
+    realtime::RealtimeEvent,
+    repo::show_job_repo::ShowCreateJobRecord,
     repo::show_repo::{
         ShowCreatedDetailRecord, ShowCreatedRecord, ShowDataRecord,
         ShowStatus::{Active, Cancelled, Ended, Upcoming},
-        upsert_show_all,
     },
+    repo::traits::ShowRepo,
     utils::uint256::DbU256,
+    webhook::WebhookEventType,
+};
+use alloy::{
+    consensus::TxEnvelope,
+    network::{Ethereum, EthereumWallet, NetworkWallet, TransactionBuilder},
+    primitives::{Address, TxHash},
+    providers::Provider,
+    rpc::types::Log,
+    sol_types::SolEvent,
 };
-use alloy::{providers::Provider, rpc::types::Log, sol_types::SolEvent};
 use eyre::{Result, bail};
 
-async fn insert_show_data_value(
-    tx_hash: Option<String>,
-    block_number: Option<DbU256>,
-    address: String,
-    log_index: Option<DbU256>,
-    db: &Db,
+/// Map an on-chain Show struct to the detail and `shows` rows.
+fn show_records(
+    chain_id: i64,
     show_data: OnchainShow,
-) -> Result<()> {
+) -> (ShowCreatedDetailRecord, ShowDataRecord) {
     let show_id = show_data.id;
-    // First insert basic event row
-    let basic = ShowCreatedRecord {
-        show_id: DbU256(show_id),
-        tx_hash,
-        block_number,
-        organizer: address.clone(),
-        log_index,
-        created_at: chrono::Utc::now(),
-    };
+    // organizer 取链上 Show.organizer（createShow 的 msg.sender），而非日志的合约地址
+    let organizer =
+        format!("0x{}", hex::encode(show_data.organizer.as_slice()));
     let detail = ShowCreatedDetailRecord {
+        chain_id,
         show_id: DbU256(show_id),
         start_time: DbU256(show_data.startTime),
         end_time: DbU256(show_data.endTime),
@@ -39,7 +45,7 @@ async fn insert_show_data_value(
         ticket_price: DbU256(show_data.ticketPrice),
         decimal: 18_i64, // Ethereum standard
         ticket_sold: DbU256(show_data.ticketsSold),
-        organizer: address.clone(),
+        organizer: organizer.clone(),
         location: show_data.location.clone(),
         name: show_data.name.clone(),
         description: show_data.description.clone(),
@@ -58,6 +64,7 @@ async fn insert_show_data_value(
         created_at: chrono::Utc::now(),
     };
     let data = ShowDataRecord {
+        chain_id,
         id: DbU256(show_data.id),
         name: show_data.name.clone(),
         description: show_data.description.clone(),
@@ -65,72 +72,195 @@ async fn insert_show_data_value(
         event_time: DbU256(show_data.startTime),
         ticket_price: DbU256(show_data.ticketPrice),
         max_tickets: DbU256(show_data.totalTickets),
+        // Ignored on write: the stored count is derived from indexed tickets only.
         sold_tickets: DbU256(show_data.ticketsSold),
         is_active: matches!(show_data.status, 1), // Active status
-        organizer: address,
+        organizer,
         created_at: chrono::Utc::now(),
+        version: 0,
     };
-    // 将事务聚合到 repo 层统一管理
-    upsert_show_all(db.pool(), &basic, &detail, &data).await?;
+    (detail, data)
+}
 
-    Ok(())
+async fn insert_show_data_value(
+    tx_hash: Option<String>,
+    block_number: Option<DbU256>,
+    log_index: Option<DbU256>,
+    shows: &dyn ShowRepo,
+    chain_id: i64,
+    show_data: OnchainShow,
+) -> Result<ShowDataRecord> {
+    let (detail, data) = show_records(chain_id, show_data);
+    // First insert basic event row
+    let basic = ShowCreatedRecord {
+        chain_id,
+        show_id: detail.show_id.clone(),
+        tx_hash,
+        block_number,
+        organizer: detail.organizer.clone(),
+        log_index,
+        created_at: chrono::Utc::now(),
+    };
+    // 将事务聚合到 repo 层统一管理
+    shows.upsert_show_all(&basic, &detail, &data).await
 }
 
+/// Read a show from the ShowManager that emitted the log (so each chain reads its own deployment).
 async fn get_show_data<P: Provider + Clone + Send + Sync + 'static>(
     provider: P,
+    show_manager: Address,
     show_id: alloy::primitives::U256,
 ) -> Result<OnchainShow> {
-    let addr = crate::config::get().addresses.show_manager;
-    let inst = ShowManagerInstance::new(addr, provider);
-    let res = inst.getShow(show_id).call().await?;
+    let inst = ShowManagerInstance::new(show_manager, provider);
+    let res =
+        crate::metrics::observe_rpc("getShow", inst.getShow(show_id).call())
+            .await?;
     let show: OnchainShow = res;
     Ok(show)
 }
 
+/// Sign ShowManager.createShow for a queued job without broadcasting it.
+/// Nonce, gas and fees are filled from `provider`; the returned envelope fixes the
+/// transaction hash, so the job can be claimed before anything leaves the process.
+pub async fn sign_create_show<P: Provider + Clone + 'static>(
+    provider: P,
+    wallet: &EthereumWallet,
+    show_manager: Address,
+    job: &ShowCreateJobRecord,
+) -> Result<TxEnvelope> {
+    let from = NetworkWallet::<Ethereum>::default_signer_address(wallet);
+    let inst = ShowManagerInstance::new(show_manager, provider.clone());
+    let mut tx = inst
+        .createShow(
+            job.name.clone(),
+            job.description.clone(),
+            job.event_time.0,
+            job.end_time.0,
+            job.location.clone(),
+            job.max_tickets.0,
+            job.ticket_price.0,
+            job.metadata_uri.clone(),
+        )
+        .into_transaction_request()
+        .with_from(from);
+    tx.set_chain_id(provider.get_chain_id().await?);
+    tx.set_nonce(provider.get_transaction_count(from).pending().await?);
+    let gas = crate::metrics::observe_rpc(
+        "estimateGas",
+        provider.estimate_gas(tx.clone()),
+    )
+    .await?;
+    tx.set_gas_limit(gas);
+    let fees = provider.estimate_eip1559_fees().await?;
+    tx.set_max_fee_per_gas(fees.max_fee_per_gas);
+    tx.set_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+    Ok(tx.build(wallet).await?)
+}
+
+/// Broadcast a transaction produced by [`sign_create_show`].
+/// The show row itself is written later by the indexer when ShowCreated is observed.
+pub async fn broadcast_create_show<P: Provider + Clone + 'static>(
+    provider: P,
+    tx: TxEnvelope,
+) -> Result<TxHash> {
+    let pending = crate::metrics::observe_rpc(
+        "createShow",
+        provider.send_tx_envelope(tx),
+    )
+    .await?;
+    Ok(*pending.tx_hash())
+}
+
+/// Cache invalidation, realtime push and webhook fan-out after a show row changed.
+async fn after_show_write(
+    ctx: &IndexerContext,
+    kind: WebhookEventType,
+    show: ShowDataRecord,
+) {
+    if let Some(c) = &ctx.cache {
+        c.invalidate_show(show.chain_id, &show.id).await;
+    }
+    ctx.emit(kind, RealtimeEvent::ShowChanged { show }).await;
+}
+
 pub async fn parse_event<P: Provider + Clone + Send + Sync + 'static>(
     log: &Log,
     provider: P,
-    db: &Db,
+    ctx: &IndexerContext,
 ) -> Result<()> {
     let inner = &log.inner; // primitives::Log
-    if let Some(topic0) = inner.topics().first() {
-        if *topic0 == ShowCreated::SIGNATURE_HASH {
-            let event = ShowCreated::decode_log(inner)?;
-            tracing::info!(?event, "Parsed ShowCreated event");
+    let Some(topic0) = inner.topics().first() else {
+        return Err(UnhandledEvent("ShowManager").into());
+    };
+    if *topic0 == ShowCreated::SIGNATURE_HASH {
+        let event = ShowCreated::decode_log(inner)?;
+        tracing::info!(?event, "Parsed ShowCreated event");
 
-            let tx_hash = log
-                .transaction_hash
-                .map(|h| format!("0x{}", hex::encode(h.as_slice())));
-            let block_number: Option<DbU256> = log
-                .block_number
-                .map(|b| DbU256(alloy::primitives::U256::from(b)));
-            let address =
-                format!("0x{}", hex::encode(inner.address.as_slice()));
-            let log_index: Option<DbU256> = log
-                .log_index
-                .map(|i| DbU256(alloy::primitives::U256::from(i)));
+        let tx_hash = log
+            .transaction_hash
+            .map(|h| format!("0x{}", hex::encode(h.as_slice())));
+        let block_number: Option<DbU256> = log
+            .block_number
+            .map(|b| DbU256(alloy::primitives::U256::from(b)));
+        let log_index: Option<DbU256> = log
+            .log_index
+            .map(|i| DbU256(alloy::primitives::U256::from(i)));
 
-            // Fetch on-chain show detail (simple version), ignore errors to avoid blocking ingestion
-            if let Ok(show) = get_show_data(provider, event.showId).await {
-                tracing::debug!(?show, "On-chain Show detail fetched");
-                insert_show_data_value(
-                    tx_hash,
-                    block_number,
-                    address,
-                    log_index,
-                    db,
-                    show,
-                )
-                .await?;
-            } else {
-                bail!(
-                    "failed to fetch on-chain show data for showId {:?}",
-                    event.showId
-                );
+        // Fetch on-chain show detail (simple version), ignore errors to avoid blocking ingestion
+        let Ok(show) =
+            get_show_data(provider, log.address(), event.showId).await
+        else {
+            bail!(
+                "failed to fetch on-chain show data for showId {:?}",
+                event.showId
+            );
+        };
+        tracing::debug!(?show, "On-chain Show detail fetched");
+        let data = insert_show_data_value(
+            tx_hash.clone(),
+            block_number,
+            log_index,
+            ctx.repos.shows.as_ref(),
+            ctx.chain_id,
+            show,
+        )
+        .await?;
+        // 链上数据已覆盖 shows 行：作废缓存并推送
+        after_show_write(ctx, WebhookEventType::ShowCreated, data).await;
+        // 若该交易来自 POST /show 提交的 job，则标记为已确认
+        if let Some(h) = tx_hash.as_deref() {
+            let show_id = DbU256(event.showId);
+            if ctx.repos.jobs.confirm_by_tx(h, &show_id).await? {
+                tracing::info!(tx_hash = h, "Confirmed show create job");
             }
-
-            return Ok(());
         }
+        return Ok(());
     }
-    eyre::bail!("not show created")
+
+    // Updates and status transitions: re-read the show from chain and refresh the projection.
+    let (kind, show_id) = match *topic0 {
+        ShowUpdated::SIGNATURE_HASH => (
+            WebhookEventType::ShowUpdated,
+            ShowUpdated::decode_log(inner)?.showId,
+        ),
+        ShowActivated::SIGNATURE_HASH => (
+            WebhookEventType::ShowActivated,
+            ShowActivated::decode_log(inner)?.showId,
+        ),
+        ShowCancelled::SIGNATURE_HASH => (
+            WebhookEventType::ShowCancelled,
+            ShowCancelled::decode_log(inner)?.showId,
+        ),
+        ShowEnded::SIGNATURE_HASH => (
+            WebhookEventType::ShowEnded,
+            ShowEnded::decode_log(inner)?.showId,
+        ),
+        _ => return Err(UnhandledEvent("ShowManager").into()),
+    };
+    tracing::info!(show_id = %show_id, "Parsed ShowManager status/update event");
+    let show = get_show_data(provider, log.address(), show_id).await?;
+    let (detail, data) = show_records(ctx.chain_id, show);
+    let stored = ctx.repos.shows.upsert_show_snapshot(&detail, &data).await?;
+    after_show_write(ctx, kind, stored).await;
+    Ok(())
 }
@@ -1,8 +1,22 @@
-use crate::contract::{
-    AddressMap, bindings::TicketManager::TicketManagerInstance,
+use crate::{
+    contract::{
+        AddressMap, IndexerContext,
+        bindings::TicketManager::{
+            TicketCancelled, TicketManagerInstance, TicketMinted, TicketUsed,
+            Transfer,
+        },
+        event::UnhandledEvent,
+    },
+    realtime::RealtimeEvent,
+    repo::ticket_repo::{NewTicket, NewTicketTransfer, TicketStatus},
+    utils::uint256::DbU256,
+    webhook::WebhookEventType,
 };
-use alloy::{primitives::Address, providers::Provider};
-use eyre::Result;
+use alloy::{
+    primitives::Address, providers::Provider, rpc::types::Log,
+    sol_types::SolEvent,
+};
+use eyre::{Result, bail};
 
 pub fn get_ticket_manager_instance_with_address<P: Provider>(
     provider: P,
@@ -15,5 +29,120 @@ pub fn get_ticket_manager_instance_from_map<P: Provider>(
     provider: P,
     addresses: &AddressMap,
 ) -> Result<TicketManagerInstance<P>> {
-    get_ticket_manager_instance_with_address(provider, addresses.show_manager)
+    let Some(address) = addresses.ticket_manager else {
+        bail!("TICKET_MANAGER_ADDRESS is not configured");
+    };
+    get_ticket_manager_instance_with_address(provider, address)
+}
+
+fn hex_address(a: &Address) -> String {
+    format!("0x{}", hex::encode(a.as_slice()))
+}
+
+/// Project TicketManager events into `tickets` / `ticket_transfers`, then invalidate the
+/// cached ticket and list pages and emit sales / check-ins to realtime and webhook subscribers.
+pub async fn parse_event(log: &Log, ctx: &IndexerContext) -> Result<()> {
+    let tickets = &ctx.repos.tickets;
+    let inner = &log.inner;
+    let Some(topic0) = inner.topics().first() else {
+        return Err(UnhandledEvent("TicketManager").into());
+    };
+    let tx_hash = log
+        .transaction_hash
+        .map(|h| format!("0x{}", hex::encode(h.as_slice())));
+    let (token_id, push) = match *topic0 {
+        TicketMinted::SIGNATURE_HASH => {
+            let event = TicketMinted::decode_log(inner)?;
+            tracing::info!(?event, "Parsed TicketMinted event");
+            let inserted = tickets
+                .upsert_minted_ticket(&NewTicket {
+                    chain_id: ctx.chain_id,
+                    token_id: DbU256(event.tokenId),
+                    event_id: DbU256(event.eventId),
+                    owner: hex_address(&event.buyer),
+                    seat_number: DbU256(event.seatNumber),
+                    price: DbU256(event.price),
+                    minted_tx_hash: tx_hash,
+                })
+                .await?;
+            // 重放的旧事件不再推送
+            let push = inserted.then(|| {
+                let sold = RealtimeEvent::TicketSold {
+                    chain_id: ctx.chain_id,
+                    show_id: DbU256(event.eventId),
+                    token_id: DbU256(event.tokenId),
+                    owner: hex_address(&event.buyer),
+                    price: DbU256(event.price),
+                };
+                (WebhookEventType::TicketSold, sold)
+            });
+            (event.tokenId, push)
+        }
+        Transfer::SIGNATURE_HASH => {
+            let event = Transfer::decode_log(inner)?;
+            tracing::debug!(?event, "Parsed Transfer event");
+            let (Some(tx_hash), Some(block_number), Some(log_index)) =
+                (tx_hash, log.block_number, log.log_index)
+            else {
+                bail!("Transfer log is missing tx/block position (pending?)");
+            };
+            tickets
+                .record_transfer(&NewTicketTransfer {
+                    chain_id: ctx.chain_id,
+                    tx_hash,
+                    log_index: i64::try_from(log_index)?,
+                    block_number: i64::try_from(block_number)?,
+                    token_id: DbU256(event.tokenId),
+                    from_address: hex_address(&event.from),
+                    to_address: hex_address(&event.to),
+                })
+                .await?;
+            (event.tokenId, None)
+        }
+        TicketUsed::SIGNATURE_HASH => {
+            let event = TicketUsed::decode_log(inner)?;
+            tracing::info!(?event, "Parsed TicketUsed event");
+            tickets
+                .update_status(
+                    ctx.chain_id,
+                    &DbU256(event.tokenId),
+                    TicketStatus::Used,
+                )
+                .await?;
+            let push = RealtimeEvent::TicketCheckedIn {
+                chain_id: ctx.chain_id,
+                show_id: DbU256(event.eventId),
+                token_id: DbU256(event.tokenId),
+                verifier: hex_address(&event.verifier),
+            };
+            (
+                event.tokenId,
+                Some((WebhookEventType::TicketCheckedIn, push)),
+            )
+        }
+        TicketCancelled::SIGNATURE_HASH => {
+            let event = TicketCancelled::decode_log(inner)?;
+            tracing::info!(?event, "Parsed TicketCancelled event");
+            tickets
+                .update_status(
+                    ctx.chain_id,
+                    &DbU256(event.tokenId),
+                    TicketStatus::Cancelled,
+                )
+                .await?;
+            (event.tokenId, None)
+        }
+        _ => return Err(UnhandledEvent("TicketManager").into()),
+    };
+    if let Some(c) = &ctx.cache {
+        c.invalidate_ticket(ctx.chain_id, &DbU256(token_id)).await;
+        // 新售出的票改变了 shows.sold_tickets
+        if let Some((_, RealtimeEvent::TicketSold { show_id, .. })) = &push {
+            c.invalidate_show(ctx.chain_id, show_id).await;
+        }
+    }
+    if let Some((kind, ev)) = push {
+        ctx.emit(kind, ev).await;
+    }
+    Ok(())
 }
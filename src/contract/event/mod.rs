@@ -1 +1,7 @@
+pub mod names;
 pub mod router;
+
+/// 合约发出了索引器不处理的事件（或匿名日志）。不算处理失败：检查点照常推进。
+#[derive(Debug, thiserror::Error)]
+#[error("unhandled {0} event")]
+pub struct UnhandledEvent(pub &'static str);
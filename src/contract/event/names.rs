@@ -0,0 +1,37 @@
+use alloy::{json_abi::JsonAbi, primitives::B256};
+use std::{collections::HashMap, sync::OnceLock};
+
+type EventNames = HashMap<&'static str, HashMap<B256, String>>;
+
+/// Event selectors (topic0) of each indexed contract, read from the ABI files the
+/// bindings are generated from.
+fn table() -> &'static EventNames {
+    static TABLE: OnceLock<EventNames> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let abis = [
+            ("show_manager", include_str!("../abis/ShowManager.json")),
+            ("did_registry", include_str!("../abis/DIDRegistry.json")),
+            ("ticket_manager", include_str!("../abis/TicketManager.json")),
+            ("marketplace", include_str!("../abis/Marketplace.json")),
+        ];
+        abis.into_iter()
+            .map(|(contract, json)| {
+                let abi: JsonAbi =
+                    serde_json::from_str(json).expect("bundled ABI is valid");
+                let names = abi
+                    .events()
+                    .map(|e| (e.selector(), e.name.clone()))
+                    .collect();
+                (contract, names)
+            })
+            .collect()
+    })
+}
+
+/// Name of the event with the given topic0 on `contract`, or "unknown".
+pub fn event_name(contract: &str, topic0: Option<&B256>) -> &'static str {
+    topic0
+        .and_then(|t| table().get(contract)?.get(t))
+        .map(String::as_str)
+        .unwrap_or("unknown")
+}
@@ -1,33 +1,81 @@
 use crate::{
-    contract::contracts::show_manager::parse_event as parse_show_created,
-    db::Db,
+    contract::IndexerContext,
+    contract::contracts::{
+        did_registry::parse_event as parse_did_event,
+        marketplace::parse_event as parse_marketplace_event,
+        show_manager::parse_event as parse_show_created,
+        ticket_manager::parse_event as parse_ticket_event,
+    },
+    contract::event::{UnhandledEvent, names::event_name},
 };
 use alloy::{providers::Provider, rpc::types::Log};
 
+/// Count a handled log in `indexer_events_total`, labelled by chain and ABI event name,
+/// and report whether the checkpoint may move past it: events the indexer does not
+/// project count as handled, real failures do not.
+fn finish(
+    ctx: &IndexerContext,
+    log: &Log,
+    contract: &'static str,
+    res: eyre::Result<()>,
+    flags: &crate::contract::FeatureFlags,
+) -> bool {
+    let event = event_name(contract, log.topics().first());
+    crate::metrics::record_indexer_event(
+        ctx.chain_id,
+        contract,
+        event,
+        res.is_ok(),
+    );
+    match res {
+        Ok(()) => true,
+        Err(e) if e.is::<UnhandledEvent>() => {
+            if flags.print_unknown {
+                tracing::warn!(contract, error = %e, "Unknown event");
+            }
+            true
+        }
+        Err(e) => {
+            tracing::error!(contract, event, error = ?e, "Failed to process log");
+            false
+        }
+    }
+}
+
+/// Dispatch a log to the parser of the contract that emitted it. Returns false when
+/// processing failed, in which case the contract's checkpoint must not move past it.
 pub async fn route_log<P: Provider + Clone + Send + Sync + 'static>(
     log: Log,
     provider: P,
     addr_map: &crate::contract::AddressMap,
     flags: &crate::contract::FeatureFlags,
-    db: &Db,
-) {
-    // raw log debug is handled below per-address when enabled
+    ctx: &IndexerContext,
+) -> bool {
+    if flags.print_raw_logs {
+        tracing::debug!(?log, "RAW LOG");
+    }
     match log.address() {
         addr if *addr == *addr_map.show_manager => {
-            if flags.print_raw_logs {
-                tracing::debug!(?log, "RAW LOG");
-            }
-            if let Err(e) = parse_show_created(&log, provider.clone(), db).await
-            {
-                if flags.print_unknown {
-                    tracing::warn!(error = ?e, "Unknown ShowManager event");
-                }
-            }
+            let res = parse_show_created(&log, provider.clone(), ctx).await;
+            finish(ctx, &log, "show_manager", res, flags)
+        }
+        addr if addr == addr_map.did_registry => {
+            let res = parse_did_event(&log, ctx).await;
+            finish(ctx, &log, "did_registry", res, flags)
+        }
+        addr if Some(addr) == addr_map.ticket_manager => {
+            let res = parse_ticket_event(&log, ctx).await;
+            finish(ctx, &log, "ticket_manager", res, flags)
+        }
+        addr if Some(addr) == addr_map.marketplace => {
+            let res = parse_marketplace_event(&log, ctx).await;
+            finish(ctx, &log, "marketplace", res, flags)
         }
         _ => {
             if flags.print_unknown {
                 tracing::debug!(addr = %format!("0x{}", hex::encode(log.address().as_slice())), "Log from unknown address");
             }
+            true
         }
     }
 }
@@ -1,36 +1,423 @@
+pub mod abi_check;
 pub mod bindings;
 pub mod contracts;
 pub mod event;
 pub mod providers;
+pub mod signers;
+pub mod status;
 use alloy::{
+    primitives::Address,
     providers::Provider,
-    rpc::types::{BlockNumberOrTag, Filter},
+    rpc::types::{BlockNumberOrTag, Filter, Log},
 };
 use eyre::Result;
 use futures_util::stream::StreamExt;
+use std::collections::{HashMap, HashSet};
 
-use crate::{config::Config, db::Db};
+use status::SubscriptionState;
 
-/// 监听链上日志并路由到对应模块。
+use crate::{
+    config::{ChainConfig, Config},
+    db::{Db, cache::AppCache},
+    realtime::{EventHub, RealtimeEvent},
+    repo::{checkpoint_repo::CheckpointRecord, traits::Repos},
+    shutdown::Shutdown,
+    webhook::WebhookEventType,
+};
+
+/// 索引器的写入目标：数据库，以及写库后需要通知的缓存与实时事件总线。
+/// 每条链一个 listener，各持有一份 chain_id 不同的上下文。
+#[derive(Debug, Clone)]
+pub struct IndexerContext {
+    /// 当前 listener 所在链；写入的每一行都带上它
+    pub chain_id: i64,
+    pub db: Db,
+    /// shows / tickets 投影与检查点、webhook 排队；测试中可替换为 `Repos::memory()`
+    pub repos: Repos,
+    pub cache: Option<AppCache>,
+    pub events: EventHub,
+    /// 各链订阅状态与各合约进度，供 /status/sync 读取（所有链共享）
+    pub status: status::IndexerStatus,
+    /// 从部署区块导入历史日志时为 true：只写投影，不排队 webhook、不推送实时事件
+    pub replaying: bool,
+}
+
+impl IndexerContext {
+    /// Fan out an event that was just persisted: queue webhook deliveries for matching
+    /// subscriptions, then push it to realtime subscribers. Failures are logged, not
+    /// propagated, so notification problems never block ingestion. Nothing is sent while
+    /// replaying history, so importing a contract from its deploy block stays silent.
+    pub async fn emit(&self, kind: WebhookEventType, event: RealtimeEvent) {
+        if self.replaying {
+            return;
+        }
+        let queued = match serde_json::to_value(&event) {
+            Ok(payload) => {
+                self.repos
+                    .events
+                    .enqueue_webhook_event(kind.as_str(), &payload)
+                    .await
+            }
+            Err(e) => Err(e.into()),
+        };
+        match queued {
+            Ok(n) if n > 0 => {
+                tracing::debug!(%kind, deliveries = n, "Queued webhook deliveries")
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(%kind, error = ?e, "Failed to queue webhook deliveries")
+            }
+        }
+        self.events.publish(event).await;
+    }
+
+    /// 日志处理成功后把对应合约的检查点推进到该日志。处理失败时该合约的检查点停在失败之前
+    /// （`held`），本次运行不再处理该合约的后续日志（见 [`Progress::halted`]），
+    /// 重启后从失败的日志重新开始，其后的日志只在那时处理、通知一次。
+    async fn checkpoint(
+        &self,
+        addresses: &AddressMap,
+        log: &Log,
+        ok: bool,
+        held: &mut HashSet<&'static str>,
+    ) {
+        let (Some(contract), Some(block), Some(log_index)) = (
+            addresses.contract_name(&log.address()),
+            log.block_number,
+            log.log_index,
+        ) else {
+            return;
+        };
+        if !ok {
+            if held.insert(contract) {
+                tracing::error!(
+                    contract,
+                    block,
+                    log_index,
+                    "Stopping contract at failed log until restart"
+                );
+            }
+            return;
+        }
+        if held.contains(contract) {
+            return;
+        }
+        self.status.record_log(self.chain_id, contract, block);
+        let address = format!("0x{}", hex::encode(log.address().as_slice()));
+        if let Err(e) = self
+            .repos
+            .events
+            .record_checkpoint(
+                self.chain_id,
+                contract,
+                &address,
+                block as i64,
+                log_index as i64,
+            )
+            .await
+        {
+            tracing::error!(contract, block, error = ?e, "Failed to record checkpoint");
+        }
+    }
+}
+
+/// 一个合约的回填起点。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resume {
+    /// 从该区块开始拉取日志
+    pub block: u64,
+    /// 已处理的最后一条日志 (block, log_index)：不晚于它的日志跳过
+    pub after: Option<(u64, u64)>,
+    /// 没有检查点、从部署区块导入历史：处理时不发送通知（见 [`IndexerContext::replaying`]）
+    pub replay: bool,
+}
+
+impl Resume {
+    /// 有同一地址的检查点时从检查点所在区块继续，否则从部署区块开始；两者都没有时不回填。
+    /// 旧检查点没有 log_index，表示整个区块已处理，从下一个区块继续。
+    pub fn from_checkpoint(
+        checkpoint: Option<&CheckpointRecord>,
+        deploy_block: Option<u64>,
+    ) -> Option<Self> {
+        let Some(c) = checkpoint else {
+            return deploy_block.map(|block| Self {
+                block,
+                after: None,
+                replay: true,
+            });
+        };
+        let last = c.last_block as u64;
+        Some(match c.last_log_index {
+            Some(i) => Self {
+                block: last,
+                after: Some((last, i as u64)),
+                replay: false,
+            },
+            None => Self {
+                block: last + 1,
+                after: None,
+                replay: false,
+            },
+        })
+    }
+
+    /// 位于 (block, log_index) 的日志是否早于起点或已处理过
+    pub fn skips(&self, block: u64, log_index: u64) -> bool {
+        block < self.block
+            || self.after.is_some_and(|p| (block, log_index) <= p)
+    }
+}
+
+/// 本次运行中各合约的进度：回填起点，以及因处理失败而停止推进检查点的合约
+#[derive(Debug, Default)]
+struct Progress {
+    starts: HashMap<Address, Resume>,
+    held: HashSet<&'static str>,
+}
+
+impl Progress {
+    /// 该日志所属合约此前有日志处理失败：跳过，不写投影、不发送通知。
+    /// 否则这些日志在重启重放时会再次通知（重启后的起点是失败的日志）。
+    fn halted(&self, addresses: &AddressMap, log: &Log) -> bool {
+        addresses
+            .contract_name(&log.address())
+            .is_some_and(|c| self.held.contains(c))
+    }
+}
+
+/// 各合约的回填起点（见 [`Resume::from_checkpoint`]）；不回填的合约只跟随新日志。
+async fn backfill_starts(
+    ctx: &IndexerContext,
+    chain: &ChainConfig,
+) -> Result<HashMap<Address, Resume>> {
+    let checkpoints = ctx.repos.events.list_checkpoints().await?;
+    let mut starts = HashMap::new();
+    for (contract, address) in chain.addresses.contracts() {
+        let hex = format!("0x{}", hex::encode(address.as_slice()));
+        let checkpoint = checkpoints.iter().find(|c| {
+            c.chain_id == ctx.chain_id
+                && c.contract == contract
+                && c.address == hex
+        });
+        let deploy_block = chain.deploy_blocks.get(contract).copied();
+        if let Some(resume) = Resume::from_checkpoint(checkpoint, deploy_block)
+        {
+            starts.insert(address, resume);
+        }
+    }
+    Ok(starts)
+}
+
+/// 按 BACKFILL_CHUNK_BLOCKS 分段拉取 `starts` 中各合约到 `head`（含）为止的日志并依次处理。
+/// 关闭信号在两条日志之间检查；被打断时返回 false。
+async fn backfill(
+    config: &Config,
+    chain: &ChainConfig,
+    ctx: &IndexerContext,
+    provider: &providers::WsProvider,
+    progress: &mut Progress,
+    head: u64,
+    shutdown: &Shutdown,
+) -> Result<bool> {
+    let Some(mut from) = progress.starts.values().map(|r| r.block).min() else {
+        return Ok(true);
+    };
+    let quiet = IndexerContext {
+        replaying: true,
+        ..ctx.clone()
+    };
+    let chunk = config.backfill_chunk_blocks;
+    while from <= head {
+        if shutdown.is_triggered() {
+            return Ok(false);
+        }
+        let to = from.saturating_add(chunk - 1).min(head);
+        let addresses: Vec<Address> = progress
+            .starts
+            .iter()
+            .filter(|(_, start)| start.block <= to)
+            .map(|(a, _)| *a)
+            .collect();
+        let filter = Filter::new()
+            .address(addresses)
+            .from_block(from)
+            .to_block(to);
+        let logs = crate::metrics::observe_rpc(
+            "eth_getLogs",
+            provider.get_logs(&filter),
+        )
+        .await?;
+        tracing::info!(from, to, logs = logs.len(), "Backfilled block range");
+        for log in logs {
+            if shutdown.is_triggered() {
+                return Ok(false);
+            }
+            let (Some(start), Some(block), Some(log_index)) = (
+                progress.starts.get(&log.address()),
+                log.block_number,
+                log.log_index,
+            ) else {
+                continue;
+            };
+            if start.skips(block, log_index)
+                || progress.halted(&chain.addresses, &log)
+            {
+                continue;
+            }
+            let log_ctx = if start.replay { &quiet } else { ctx };
+            let ok = event::router::route_log(
+                log.clone(),
+                provider.clone(),
+                &chain.addresses,
+                &config.flags,
+                log_ctx,
+            )
+            .await;
+            ctx.checkpoint(&chain.addresses, &log, ok, &mut progress.held)
+                .await;
+        }
+        from = to + 1;
+    }
+    Ok(true)
+}
+
+async fn block_number(provider: &providers::WsProvider) -> Result<u64> {
+    Ok(crate::metrics::observe_rpc(
+        "eth_blockNumber",
+        provider.get_block_number(),
+    )
+    .await?)
+}
+
+/// 监听 `ctx.chain_id` 对应链上的日志并路由到对应模块，直到 `shutdown` 触发或订阅结束。
+/// 订阅前先回填各合约缺失的历史日志（见 [`backfill_starts`]），订阅建立后再补上其间的区块，
+/// 订阅流中已回填区块的日志会被跳过。
+/// 关闭只在两条日志之间检查：正在处理的日志（及其数据库事务）总会完成后才退出。
 pub async fn listen_chain(
     config: &Config,
-    db: Db,
+    ctx: IndexerContext,
     pool: &providers::ProviderPool,
+    shutdown: Shutdown,
 ) -> Result<()> {
-    let provider = pool.ws_listener();
+    let chain_id = ctx.chain_id;
+    let (Some(chain), Some(provider)) =
+        (config.chain(chain_id), pool.ws_listener_for(chain_id))
+    else {
+        eyre::bail!("chain {chain_id} is not configured");
+    };
+    ctx.status
+        .set_state(chain_id, SubscriptionState::Connecting, None);
+    let mut progress = Progress {
+        starts: backfill_starts(&ctx, chain).await?,
+        ..Default::default()
+    };
+    let mut backfilled_to = None;
+    if !progress.starts.is_empty() {
+        ctx.status
+            .set_state(chain_id, SubscriptionState::Backfilling, None);
+        let head = block_number(&provider).await?;
+        if !backfill(
+            config,
+            chain,
+            &ctx,
+            &provider,
+            &mut progress,
+            head,
+            &shutdown,
+        )
+        .await?
+        {
+            tracing::info!("Indexer stopped on shutdown during backfill");
+            ctx.status
+                .set_state(chain_id, SubscriptionState::Stopped, None);
+            return Ok(());
+        }
+        backfilled_to = Some(head);
+    }
     let filter = Filter::new().from_block(BlockNumberOrTag::Latest);
-    let sub = provider.subscribe_logs(&filter).await?;
+    let subscribe = provider.subscribe_logs(&filter);
+    let sub =
+        match crate::metrics::observe_rpc("eth_subscribe", subscribe).await {
+            Ok(sub) => sub,
+            Err(e) => {
+                ctx.status.set_state(
+                    chain_id,
+                    SubscriptionState::Failed,
+                    Some(e.to_string()),
+                );
+                return Err(e.into());
+            }
+        };
+    // 回填期间产生的区块：订阅建立后补齐，之后订阅流只处理更新的区块
+    if let Some(head) = backfilled_to {
+        let latest = block_number(&provider).await?;
+        // 这段区块是订阅期间的新日志，照常发送通知
+        progress.starts.values_mut().for_each(|s| {
+            *s = Resume {
+                block: head + 1,
+                after: None,
+                replay: false,
+            }
+        });
+        if !backfill(
+            config,
+            chain,
+            &ctx,
+            &provider,
+            &mut progress,
+            latest,
+            &shutdown,
+        )
+        .await?
+        {
+            tracing::info!("Indexer stopped on shutdown during backfill");
+            ctx.status
+                .set_state(chain_id, SubscriptionState::Stopped, None);
+            return Ok(());
+        }
+        backfilled_to = Some(latest);
+    }
+    ctx.status
+        .set_state(chain_id, SubscriptionState::Subscribed, None);
     let mut stream = sub.into_stream();
 
-    while let Some(log) = stream.next().await {
-        event::router::route_log(
-            log,
+    loop {
+        let log = tokio::select! {
+            biased;
+            _ = shutdown.wait() => {
+                tracing::info!("Indexer stopped on shutdown");
+                ctx.status.set_state(chain_id, SubscriptionState::Stopped, None);
+                break;
+            }
+            next = stream.next() => match next {
+                Some(log) => log,
+                None => {
+                    tracing::warn!("Log subscription ended");
+                    ctx.status.set_state(chain_id, SubscriptionState::Ended, None);
+                    break;
+                }
+            },
+        };
+        if let (Some(to), Some(block)) = (backfilled_to, log.block_number)
+            && block <= to
+            && progress.starts.contains_key(&log.address())
+        {
+            continue;
+        }
+        if progress.halted(&chain.addresses, &log) {
+            continue;
+        }
+        let ok = event::router::route_log(
+            log.clone(),
             provider.clone(),
-            &config.addresses,
+            &chain.addresses,
             &config.flags,
-            &db,
+            &ctx,
         )
         .await;
+        ctx.checkpoint(&chain.addresses, &log, ok, &mut progress.held)
+            .await;
     }
     Ok(())
 }
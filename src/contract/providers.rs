@@ -1,11 +1,17 @@
+use alloy::network::EthereumWallet;
+use alloy::primitives::Address;
 use alloy::providers::{ProviderBuilder, RootProvider, WsConnect};
 use alloy::signers::local::PrivateKeySigner;
 use eyre::Result;
 use std::str::FromStr;
 
+use super::signers::{
+    BackendTxSigner, LocalKeyBackend, RemoteSignerBackend,
+    RemoteSignerSettings, SignerBackend,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{OnceLock, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -27,34 +33,98 @@ pub async fn ws_public(url: &str) -> Result<WsProvider> {
 pub async fn ws_with_private_key(
     url: String,
     pk_hex: String,
-) -> Result<impl alloy::providers::Provider + Clone + Send + Sync + 'static> {
-    let signer = PrivateKeySigner::from_str(&pk_hex)?;
+) -> Result<impl alloy::providers::Provider + Clone + 'static> {
+    let backend = LocalKeyBackend::from_private_key(&pk_hex)?;
+    ws_with_signer_backend(url, Arc::new(backend)).await
+}
+
+/// Build a WebSocket provider that signs transactions through any SignerBackend.
+pub async fn ws_with_signer_backend(
+    url: String,
+    backend: Arc<dyn SignerBackend>,
+) -> Result<impl alloy::providers::Provider + Clone + 'static> {
+    let wallet = EthereumWallet::new(BackendTxSigner(backend));
     let ws = WsConnect::new(url);
-    let provider = ProviderBuilder::new()
-        .disable_recommended_fillers()
-        .wallet(signer)
-        .connect_ws(ws)
-        .await?;
+    // Keep recommended fillers (nonce, gas, chain id): transactions must be complete before signing.
+    let provider = ProviderBuilder::new().wallet(wallet).connect_ws(ws).await?;
     Ok(provider)
 }
 
-pub struct ProviderPool {
+struct ChainProviders {
     ws_listener: WsProvider,
     ws_reader: WsProvider,
 }
 
+/// Read-only providers for every configured chain.
+pub struct ProviderPool {
+    default_chain: i64,
+    chains: HashMap<i64, ChainProviders>,
+}
+
 static POOL: OnceLock<ProviderPool> = OnceLock::new();
 
-/// Initialize global provider pool from current config.
+/// Verify every configured contract address has deployed code (eth_getCode), catching
+/// stale addresses after a redeploy or a node that was reset.
+async fn check_code(
+    reader: &WsProvider,
+    chain: &crate::config::ChainConfig,
+) -> Result<()> {
+    for (contract, address) in chain.addresses.contracts() {
+        let code = crate::metrics::observe_rpc(
+            "eth_getCode",
+            alloy::providers::Provider::get_code_at(reader, address),
+        )
+        .await?;
+        if code.is_empty() {
+            eyre::bail!(
+                "chain {} ({}): {} at {} has no code",
+                chain.chain_id,
+                chain.name,
+                contract,
+                address
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Initialize global provider pool from current config: one listener and one reader per
+/// chain. Fails if an RPC endpoint reports a different chain id than configured, so rows
+/// are never written under the wrong chain, or if a configured contract has no code.
 pub async fn init_pool() -> Result<&'static ProviderPool> {
-    let url = crate::config::get().ws_rpc_url.clone();
-    let listener = ws_public(&url).await?;
-    let reader = ws_public(&url).await?;
+    let cfg = crate::config::get();
+    let mut chains = HashMap::new();
+    for chain in &cfg.chains {
+        let listener = ws_public(&chain.ws_rpc_url).await?;
+        let reader = ws_public(&chain.ws_rpc_url).await?;
+        let actual = crate::metrics::observe_rpc(
+            "eth_chainId",
+            alloy::providers::Provider::get_chain_id(&reader),
+        )
+        .await?;
+        if i64::try_from(actual).ok() != Some(chain.chain_id) {
+            eyre::bail!(
+                "chain {} ({}): RPC {} reports chain id {}",
+                chain.chain_id,
+                chain.name,
+                chain.ws_rpc_url,
+                actual
+            );
+        }
+        check_code(&reader, chain).await?;
+        tracing::info!(chain_id = chain.chain_id, chain = %chain.name, ws_rpc_url = %chain.ws_rpc_url, "Connected chain providers");
+        chains.insert(
+            chain.chain_id,
+            ChainProviders {
+                ws_listener: listener,
+                ws_reader: reader,
+            },
+        );
+    }
     let pool = ProviderPool {
-        ws_listener: listener,
-        ws_reader: reader,
+        default_chain: cfg.default_chain().chain_id,
+        chains,
     };
-    tracing::info!("Initialized ProviderPool with ws_rpc_url={}", url);
     let _ = POOL.set(pool);
     Ok(POOL.get().expect("provider pool must be initialized"))
 }
@@ -64,12 +134,31 @@ pub fn get_pool() -> &'static ProviderPool {
         .expect("provider pool is not initialized; call init_pool() first")
 }
 
+/// 未初始化时返回 None（例如只启动 API 的测试环境）
+pub fn try_get_pool() -> Option<&'static ProviderPool> {
+    POOL.get()
+}
+
 impl ProviderPool {
+    fn default_providers(&self) -> &ChainProviders {
+        self.chains
+            .get(&self.default_chain)
+            .expect("default chain providers must exist")
+    }
+
+    /// Listener of the default chain.
     pub fn ws_listener(&self) -> WsProvider {
-        self.ws_listener.clone()
+        self.default_providers().ws_listener.clone()
     }
+    /// Reader of the default chain.
     pub fn ws_reader(&self) -> WsProvider {
-        self.ws_reader.clone()
+        self.default_providers().ws_reader.clone()
+    }
+    pub fn ws_listener_for(&self, chain_id: i64) -> Option<WsProvider> {
+        self.chains.get(&chain_id).map(|p| p.ws_listener.clone())
+    }
+    pub fn ws_reader_for(&self, chain_id: i64) -> Option<WsProvider> {
+        self.chains.get(&chain_id).map(|p| p.ws_reader.clone())
     }
 }
 
@@ -83,7 +172,7 @@ pub async fn demo_update_show_name<
     name: String,
     metadata_uri: String,
 ) -> Result<()> {
-    let addr = crate::config::get().addresses.show_manager;
+    let addr = crate::config::get().default_chain().addresses.show_manager;
     let inst = crate::contract::bindings::ShowManager::ShowManagerInstance::new(
         addr, provider,
     );
@@ -91,10 +180,10 @@ pub async fn demo_update_show_name<
     Ok(())
 }
 
-/// SignerPool: manage named private keys and build signer-enabled providers on demand.
+/// SignerPool: manage named signer backends and build signer-enabled providers on demand.
 pub struct SignerPool {
-    // name -> private key hex (can be 0x-prefixed)
-    keys: RwLock<HashMap<String, String>>,
+    // name -> backend (local key, decrypted keystore or remote signer)
+    keys: RwLock<HashMap<String, Arc<dyn SignerBackend>>>,
 }
 
 static SIGNER_POOL: OnceLock<SignerPool> = OnceLock::new();
@@ -112,15 +201,34 @@ impl SignerPool {
         name: impl Into<String>,
         pk_hex: impl Into<String>,
     ) -> Result<()> {
-        let name = name.into();
-        let pk_hex = pk_hex.into();
-        // Validate by attempting to parse
-        let _ = PrivateKeySigner::from_str(&pk_hex)?;
+        let backend = LocalKeyBackend::from_private_key(&pk_hex.into())?;
+        self.register_backend(name, Arc::new(backend));
+        Ok(())
+    }
+
+    /// Register any signer backend under a name (override if exists).
+    pub fn register_backend(
+        &self,
+        name: impl Into<String>,
+        backend: Arc<dyn SignerBackend>,
+    ) {
         let mut map = self
             .keys
             .write()
             .expect("SignerPool RwLock poisoned while registering");
-        map.insert(name, pk_hex);
+        map.insert(name.into(), backend);
+    }
+
+    /// Register a remote HTTP signer that signs for `address`.
+    pub fn register_remote(
+        &self,
+        name: impl Into<String>,
+        endpoint: impl Into<String>,
+        address: Address,
+        settings: &RemoteSignerSettings,
+    ) -> Result<()> {
+        let backend = RemoteSignerBackend::new(endpoint, address, settings)?;
+        self.register_backend(name, Arc::new(backend));
         Ok(())
     }
 
@@ -152,33 +260,57 @@ impl SignerPool {
         map.clear();
     }
 
-    fn get_pk(&self, name: &str) -> Result<String> {
+    /// Look up the backend registered under `name`.
+    pub fn backend(&self, name: &str) -> Result<Arc<dyn SignerBackend>> {
         let map = self
             .keys
             .read()
-            .expect("SignerPool RwLock poisoned while getting pk");
-        let pk = map.get(name).cloned().ok_or_else(|| {
+            .expect("SignerPool RwLock poisoned while getting backend");
+        let backend = map.get(name).cloned().ok_or_else(|| {
             eyre::eyre!(format!("no signer named '{}'", name))
         })?;
-        Ok(pk)
+        Ok(backend)
+    }
+
+    /// Address of the account registered under `name`.
+    pub fn address_of(&self, name: &str) -> Result<Address> {
+        Ok(self.backend(name)?.address())
+    }
+
+    /// Wallet for the named account, for signing a transaction before it is broadcast.
+    pub fn wallet_for(&self, name: &str) -> Result<EthereumWallet> {
+        Ok(EthereumWallet::new(BackendTxSigner(self.backend(name)?)))
     }
 
     /// Build a signer-enabled provider for the given named account on demand.
     pub async fn provider_for(
         &self,
         name: &str,
-    ) -> Result<impl alloy::providers::Provider + Clone + Send + Sync + 'static>
-    {
-        let pk_hex: String = self.get_pk(name)?;
-        let url: String = crate::config::get().ws_rpc_url.clone();
-        ws_with_private_key(url, pk_hex).await
+    ) -> Result<impl alloy::providers::Provider + Clone + 'static> {
+        let backend = self.backend(name)?;
+        // Transactions are always sent on the default chain.
+        let url = crate::config::get().default_chain().ws_rpc_url.clone();
+        tracing::debug!(signer = name, kind = backend.kind(), address = %backend.address(), "Building signer provider");
+        ws_with_signer_backend(url, backend).await
+    }
+
+    /// Private keys of signers held in process, as 0x-hex (remote signers are skipped).
+    fn exportable_keys(&self) -> HashMap<String, String> {
+        self.keys
+            .read()
+            .expect("SignerPool RwLock poisoned while exporting")
+            .iter()
+            .filter_map(|(name, b)| {
+                b.export_key()
+                    .map(|k| (name.clone(), format!("0x{}", hex::encode(k))))
+            })
+            .collect()
     }
 
     /// Convenience: build provider for the "default" signer.
     pub async fn default_provider(
         &self,
-    ) -> Result<impl alloy::providers::Provider + Clone + Send + Sync + 'static>
-    {
+    ) -> Result<impl alloy::providers::Provider + Clone + 'static> {
         self.provider_for("default").await
     }
 }
@@ -186,23 +318,65 @@ impl SignerPool {
 /// Initialize global SignerPool and load PRIVATE_KEY from env as "default" signer (if present).
 pub fn init_signer_pool_from_env() -> Result<&'static SignerPool> {
     let pool = SIGNER_POOL.get_or_init(|| SignerPool::new());
-    if let Ok(pk) = std::env::var("PRIVATE_KEY") {
+    register_env_signers(pool)?;
+    Ok(pool)
+}
+
+/// Register signers described by env:
+/// - PRIVATE_KEY: "default" local signer
+/// - REMOTE_SIGNER_URL + REMOTE_SIGNERS ("name=0xaddr,..."): remote HTTP signers
+fn register_env_signers(pool: &SignerPool) -> Result<()> {
+    if let Some(pk) = crate::config::var("PRIVATE_KEY") {
         pool.register("default", pk)?;
     }
-    Ok(pool)
+    let Some(url) = crate::config::var("REMOTE_SIGNER_URL") else {
+        return Ok(());
+    };
+    let accounts = crate::config::var("REMOTE_SIGNERS").unwrap_or_default();
+    let settings = RemoteSignerSettings::from_config();
+    for entry in accounts.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (name, addr) = entry.split_once('=').ok_or_else(|| {
+            eyre::eyre!(
+                "REMOTE_SIGNERS entry must be name=0xaddress, got: {}",
+                entry
+            )
+        })?;
+        let address = Address::from_str(addr.trim()).map_err(|e| {
+            eyre::eyre!("Invalid address in REMOTE_SIGNERS for {}: {}", name, e)
+        })?;
+        pool.register_remote(name.trim(), url.clone(), address, &settings)?;
+    }
+    Ok(())
 }
 
 /// Initialize global SignerPool by loading from disk first, then env.
+///
+/// When SIGNER_KEYSTORE_DIR is set, signers are loaded from encrypted keystores in that
+/// directory (password from SIGNER_KEYSTORE_PASSWORD / SIGNER_KEYSTORE_PASSWORD_FILE) and
+/// the plaintext signers file is not read.
 pub fn init_signer_pool_from_env_and_disk() -> Result<&'static SignerPool> {
     let pool = SIGNER_POOL.get_or_init(|| SignerPool::new());
-    let path = default_signers_store_path();
-    let _ = pool.load_from_file(&path);
-    if let Ok(pk) = std::env::var("PRIVATE_KEY") {
-        pool.register("default", pk)?;
+    if let Some(dir) = default_keystore_dir() {
+        let password = keystore_password_from_env()?.ok_or_else(|| {
+            eyre::eyre!(
+                "SIGNER_KEYSTORE_DIR is set but no keystore password was provided"
+            )
+        })?;
+        let names = pool.load_keystore_dir(&dir, &password)?;
+        tracing::info!(dir = %dir.display(), count = names.len(), "Loaded signer keystores");
+    } else {
+        let path = default_signers_store_path();
+        let _ = pool.load_from_file(&path);
     }
+    register_env_signers(pool)?;
     Ok(pool)
 }
 
+/// Get the global SignerPool if it has been initialized.
+pub fn try_signer_pool() -> Option<&'static SignerPool> {
+    SIGNER_POOL.get()
+}
+
 /// Get the global SignerPool (call init_signer_pool_from_env() first in your bootstrap).
 pub fn signer_pool() -> &'static SignerPool {
     SIGNER_POOL
@@ -215,12 +389,9 @@ struct SignersFile(HashMap<String, String>);
 
 impl SignerPool {
     /// Save current signers to a JSON file. WARNING: stores raw private keys; for local development only.
+    /// Remote signers are not written.
     pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
-        let map = self
-            .keys
-            .read()
-            .expect("SignerPool RwLock poisoned while saving")
-            .clone();
+        let map = self.exportable_keys();
         let data = serde_json::to_vec_pretty(&SignersFile(map))?;
         if let Some(parent) = path.as_ref().parent() {
             if !parent.as_os_str().is_empty() {
@@ -240,12 +411,7 @@ impl SignerPool {
         let bytes = fs::read(p)?;
         let SignersFile(map): SignersFile = serde_json::from_slice(&bytes)?;
         for (name, pk) in map.into_iter() {
-            let _ = PrivateKeySigner::from_str(&pk)?;
-            let mut w = self
-                .keys
-                .write()
-                .expect("SignerPool RwLock poisoned while loading");
-            w.insert(name, pk);
+            self.register(name, pk)?;
         }
         Ok(())
     }
@@ -253,10 +419,155 @@ impl SignerPool {
 
 /// Resolve default signers store path: env SIGNERS_FILE or ".signers.json" in current dir.
 pub fn default_signers_store_path() -> PathBuf {
-    if let Ok(p) = std::env::var("SIGNERS_FILE") {
+    if let Some(p) = crate::config::var("SIGNERS_FILE") {
         return PathBuf::from(p);
     }
     std::env::current_dir()
         .unwrap_or_else(|_| PathBuf::from("."))
         .join(".signers.json")
 }
+
+impl SignerPool {
+    /// Decrypt a Web3 Secret Storage keystore (scrypt or pbkdf2) and register it under `name`.
+    pub fn register_keystore(
+        &self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<()> {
+        let backend = LocalKeyBackend::from_keystore(path, password)?;
+        self.register_backend(name, Arc::new(backend));
+        Ok(())
+    }
+
+    /// Load every `*.json` keystore in `dir`, using the file stem as the signer name.
+    /// Returns the loaded names; entries override existing signers with the same name.
+    /// A missing directory is an error: a configured keystore dir that does not exist is
+    /// almost always a typo or an unmounted volume, not an empty signer set.
+    pub fn load_keystore_dir(
+        &self,
+        dir: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<Vec<String>> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            eyre::bail!("keystore directory {} does not exist", dir.display());
+        }
+        let mut loaded = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            self.register_keystore(name, &path, password).map_err(|e| {
+                eyre::eyre!(
+                    "failed to decrypt keystore {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            loaded.push(name.to_string());
+        }
+        loaded.sort();
+        Ok(loaded)
+    }
+
+    /// Save every in-process signer as an encrypted keystore `<dir>/<name>.json` (scrypt KDF).
+    pub fn save_keystore_dir(
+        &self,
+        dir: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let map = self.exportable_keys();
+        let mut rng = rand::thread_rng();
+        for (name, pk) in map.iter() {
+            let signer = PrivateKeySigner::from_str(pk)?;
+            let file_name = format!("{}.json", name);
+            PrivateKeySigner::encrypt_keystore(
+                dir,
+                &mut rng,
+                signer.to_bytes(),
+                password,
+                Some(&file_name),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolve keystore directory from env SIGNER_KEYSTORE_DIR (unset/empty means disabled).
+pub fn default_keystore_dir() -> Option<PathBuf> {
+    crate::config::var("SIGNER_KEYSTORE_DIR").map(PathBuf::from)
+}
+
+/// Resolve keystore password: env SIGNER_KEYSTORE_PASSWORD, otherwise the contents of
+/// the file named by SIGNER_KEYSTORE_PASSWORD_FILE (trailing newline trimmed).
+pub fn keystore_password_from_env() -> Result<Option<String>> {
+    if let Some(pw) = crate::config::var("SIGNER_KEYSTORE_PASSWORD") {
+        return Ok(Some(pw));
+    }
+    if let Some(p) = crate::config::var("SIGNER_KEYSTORE_PASSWORD_FILE") {
+        let raw = fs::read_to_string(&p).map_err(|e| {
+            eyre::eyre!(
+                "failed to read SIGNER_KEYSTORE_PASSWORD_FILE {}: {}",
+                p,
+                e
+            )
+        })?;
+        return Ok(Some(raw.trim_end_matches(['\r', '\n']).to_string()));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANVIL_PK0: &str =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "signer-pool-{}-{}",
+            tag,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_keystore_dir_roundtrip() {
+        let dir = temp_dir("roundtrip");
+        let pool = SignerPool::new();
+        pool.register("deployer", ANVIL_PK0).unwrap();
+        pool.save_keystore_dir(&dir, "secret").unwrap();
+
+        let raw = fs::read_to_string(dir.join("deployer.json")).unwrap();
+        assert!(!raw.contains(&ANVIL_PK0[2..]));
+
+        let loaded = SignerPool::new();
+        let names = loaded.load_keystore_dir(&dir, "secret").unwrap();
+        assert_eq!(names, vec!["deployer".to_string()]);
+        let backend = loaded.backend("deployer").unwrap();
+        assert_eq!(backend.kind(), "keystore");
+        assert_eq!(
+            backend
+                .export_key()
+                .map(|k| format!("0x{}", hex::encode(k))),
+            Some(ANVIL_PK0.to_string())
+        );
+
+        let wrong = SignerPool::new();
+        assert!(wrong.load_keystore_dir(&dir, "nope").is_err());
+        let _ = fs::remove_dir_all(&dir);
+
+        let err = SignerPool::new().load_keystore_dir(&dir, "secret");
+        assert!(err.unwrap_err().to_string().contains("does not exist"));
+    }
+}
@@ -0,0 +1,218 @@
+use alloy::{
+    consensus::SignableTransaction,
+    network::TxSigner,
+    primitives::{Address, B256, Signature},
+    signers::{SignerSync, local::PrivateKeySigner},
+    transports::http::reqwest,
+};
+use async_trait::async_trait;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
+
+/// A named account SignerPool can sign with: a key held in process, a decrypted keystore,
+/// or an external signing service.
+#[async_trait]
+pub trait SignerBackend: Send + Sync + fmt::Debug {
+    /// Address of the account this backend signs for.
+    fn address(&self) -> Address;
+
+    /// Short label for logs: "local", "keystore" or "remote".
+    fn kind(&self) -> &'static str;
+
+    /// Sign a 32-byte prehash (e.g. a transaction signature hash).
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature>;
+
+    /// Raw private key, only for backends that hold the key in process (used when persisting).
+    fn export_key(&self) -> Option<B256> {
+        None
+    }
+}
+
+/// Private key held in memory, either given directly or decrypted from a keystore file.
+#[derive(Debug, Clone)]
+pub struct LocalKeyBackend {
+    signer: PrivateKeySigner,
+    keystore: Option<PathBuf>,
+}
+
+impl LocalKeyBackend {
+    pub fn from_private_key(pk_hex: &str) -> Result<Self> {
+        Ok(Self {
+            signer: PrivateKeySigner::from_str(pk_hex)?,
+            keystore: None,
+        })
+    }
+
+    /// Decrypt a Web3 Secret Storage keystore (scrypt or pbkdf2).
+    pub fn from_keystore(
+        path: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        Ok(Self {
+            signer: PrivateKeySigner::decrypt_keystore(path, password)?,
+            keystore: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Path of the keystore this key was decrypted from, if any.
+    pub fn keystore_path(&self) -> Option<&Path> {
+        self.keystore.as_deref()
+    }
+}
+
+#[async_trait]
+impl SignerBackend for LocalKeyBackend {
+    fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    fn kind(&self) -> &'static str {
+        if self.keystore.is_some() {
+            "keystore"
+        } else {
+            "local"
+        }
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+        Ok(self.signer.sign_hash_sync(hash)?)
+    }
+
+    fn export_key(&self) -> Option<B256> {
+        Some(self.signer.to_bytes())
+    }
+}
+
+/// Request body sent to a remote signer: `POST {endpoint}/sign`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteSignRequest {
+    pub address: Address,
+    pub hash: B256,
+}
+
+/// Response body expected from a remote signer: 65-byte r||s||v hex signature.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoteSignResponse {
+    pub signature: String,
+}
+
+/// HTTP timeouts for a remote signer, so a hung service fails the signing call instead of
+/// stalling the show job worker.
+#[derive(Debug, Clone)]
+pub struct RemoteSignerSettings {
+    /// Whole request, including reading the response
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+}
+
+impl RemoteSignerSettings {
+    pub fn from_config() -> Self {
+        let cfg = crate::config::get();
+        Self {
+            timeout: Duration::from_secs(cfg.remote_signer_timeout_secs),
+            connect_timeout: Duration::from_secs(
+                cfg.remote_signer_connect_timeout_secs,
+            ),
+        }
+    }
+}
+
+/// External signing service over HTTP. The key never enters this process; the returned
+/// signature is checked to recover to the configured address.
+#[derive(Debug, Clone)]
+pub struct RemoteSignerBackend {
+    endpoint: String,
+    address: Address,
+    client: reqwest::Client,
+}
+
+impl RemoteSignerBackend {
+    pub fn new(
+        endpoint: impl Into<String>,
+        address: Address,
+        settings: &RemoteSignerSettings,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(settings.timeout)
+            .connect_timeout(settings.connect_timeout)
+            .build()?;
+        Ok(Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            address,
+            client,
+        })
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+#[async_trait]
+impl SignerBackend for RemoteSignerBackend {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn kind(&self) -> &'static str {
+        "remote"
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature> {
+        let body = serde_json::to_vec(&RemoteSignRequest {
+            address: self.address,
+            hash: *hash,
+        })?;
+        let bytes = self
+            .client
+            .post(format!("{}/sign", self.endpoint))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let res: RemoteSignResponse = serde_json::from_slice(&bytes)?;
+        let sig = Signature::from_str(&res.signature)?;
+        let recovered = sig.recover_address_from_prehash(hash)?;
+        if recovered != self.address {
+            eyre::bail!(
+                "remote signer returned signature for {}, expected {}",
+                recovered,
+                self.address
+            );
+        }
+        Ok(sig)
+    }
+}
+
+/// Adapter so any SignerBackend can be used as an alloy transaction signer / wallet.
+#[derive(Debug, Clone)]
+pub struct BackendTxSigner(pub Arc<dyn SignerBackend>);
+
+#[async_trait]
+impl TxSigner<Signature> for BackendTxSigner {
+    fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        let hash = tx.signature_hash();
+        self.0
+            .sign_hash(&hash)
+            .await
+            .map_err(alloy::signers::Error::other)
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use utoipa::ToSchema;
+
+/// State of the indexer's log subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionState {
+    /// Not started yet, or (re)subscribing.
+    Connecting,
+    /// Replaying logs from the last checkpoint (or deployment block) up to the chain head.
+    Backfilling,
+    Subscribed,
+    /// The RPC closed the subscription stream.
+    Ended,
+    /// Subscribing failed; see `last_error`.
+    Failed,
+    /// Stopped on shutdown.
+    Stopped,
+}
+
+/// Point-in-time view of the indexer, served by /status/sync.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IndexerSnapshot {
+    pub subscription: SubscriptionState,
+    /// When `subscription` last changed.
+    pub since: DateTime<Utc>,
+    /// When the last log from a watched contract was processed.
+    pub last_log_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug)]
+struct ChainStatus {
+    snapshot: IndexerSnapshot,
+    /// Highest block processed per contract in this process; avoids rewriting the
+    /// checkpoint for every log of the same block.
+    blocks: HashMap<&'static str, u64>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    /// When this status was created; reported as `since` for chains whose
+    /// listener has not reported yet.
+    started_at: DateTime<Utc>,
+    chains: HashMap<i64, ChainStatus>,
+}
+
+impl Inner {
+    fn chain(&mut self, chain_id: i64) -> &mut ChainStatus {
+        let since = self.started_at;
+        self.chains.entry(chain_id).or_insert_with(|| ChainStatus {
+            snapshot: connecting(since),
+            blocks: HashMap::new(),
+        })
+    }
+}
+
+fn connecting(since: DateTime<Utc>) -> IndexerSnapshot {
+    IndexerSnapshot {
+        subscription: SubscriptionState::Connecting,
+        since,
+        last_log_at: None,
+        last_error: None,
+    }
+}
+
+/// In-memory indexer status shared by the per-chain listeners (writers) and the
+/// API (reader).
+#[derive(Debug, Clone)]
+pub struct IndexerStatus {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl Default for IndexerStatus {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Inner {
+                started_at: Utc::now(),
+                chains: HashMap::new(),
+            })),
+        }
+    }
+}
+
+impl IndexerStatus {
+    pub fn set_state(
+        &self,
+        chain_id: i64,
+        state: SubscriptionState,
+        error: Option<String>,
+    ) {
+        let mut inner = self.inner.write().expect("indexer status poisoned");
+        let snapshot = &mut inner.chain(chain_id).snapshot;
+        snapshot.subscription = state;
+        snapshot.since = Utc::now();
+        snapshot.last_error = error;
+    }
+
+    /// Record a processed log; returns true when `block` advances the contract's
+    /// progress on that chain.
+    pub fn record_log(
+        &self,
+        chain_id: i64,
+        contract: &'static str,
+        block: u64,
+    ) -> bool {
+        let mut inner = self.inner.write().expect("indexer status poisoned");
+        let chain = inner.chain(chain_id);
+        chain.snapshot.last_log_at = Some(Utc::now());
+        let last = chain.blocks.entry(contract).or_insert(0);
+        if block > *last {
+            *last = block;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Status of one chain's listener; chains that have not reported yet are
+    /// `connecting`.
+    pub fn snapshot(&self, chain_id: i64) -> IndexerSnapshot {
+        let inner = self.inner.read().expect("indexer status poisoned");
+        inner
+            .chains
+            .get(&chain_id)
+            .map(|c| c.snapshot.clone())
+            .unwrap_or_else(|| connecting(inner.started_at))
+    }
+}
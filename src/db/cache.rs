@@ -0,0 +1,540 @@
+use crate::{
+    db::{
+        redis_cache::{RedisCache, show_cache_key},
+        single_flight::SingleFlight,
+    },
+    repo::{
+        show_repo::ShowDataRecord, ticket_repo::TicketRecord, traits::ShowRepo,
+    },
+    utils::uint256::DbU256,
+};
+use async_trait::async_trait;
+use eyre::Result;
+use lru::LruCache;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    fmt,
+    future::Future,
+    num::NonZeroUsize,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// 缓存后端：redis 适合多实例部署；memory 为进程内 LRU（单节点/测试）；none 关闭缓存。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheBackend {
+    Redis,
+    Memory,
+    None,
+}
+
+impl FromStr for CacheBackend {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "redis" => Ok(Self::Redis),
+            "memory" | "lru" => Ok(Self::Memory),
+            "none" | "off" => Ok(Self::None),
+            other => Err(format!(
+                "unknown cache backend {other:?} (expected redis|memory|none)"
+            )),
+        }
+    }
+}
+
+/// 字符串 KV 缓存的最小接口；序列化与 key 规则由 AppCache 统一处理。
+#[async_trait]
+pub trait Cache: Send + Sync + fmt::Debug {
+    fn backend(&self) -> &'static str;
+    async fn get(&self, key: &str) -> Result<Option<String>>;
+    async fn set(&self, key: &str, value: String, ttl_secs: u64) -> Result<()>;
+    async fn del(&self, key: &str) -> Result<()>;
+    /// 原子自增计数器（不存在或已过期时从 1 开始），每次自增把过期时间续为 ttl_secs；
+    /// 用于列表与 show 的代际号
+    async fn incr(&self, key: &str, ttl_secs: u64) -> Result<i64>;
+}
+
+/// 进程内 LRU 缓存，条目带过期时间；计数器与普通条目一起存放，同样受容量与 TTL 约束。
+pub struct MemoryCache {
+    entries: Mutex<LruCache<String, (String, Instant)>>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        let cap = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(cap)),
+        }
+    }
+}
+
+impl fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.lock().map(|e| e.len()).unwrap_or(0);
+        f.debug_struct("MemoryCache")
+            .field("entries", &entries)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Cache for MemoryCache {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let mut entries = self.entries.lock().expect("cache lock");
+        match entries.get(key) {
+            Some((v, expires)) if *expires > Instant::now() => {
+                Ok(Some(v.clone()))
+            }
+            Some(_) => {
+                entries.pop(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: String, ttl_secs: u64) -> Result<()> {
+        let expires = Instant::now() + Duration::from_secs(ttl_secs);
+        self.entries
+            .lock()
+            .expect("cache lock")
+            .put(key.to_string(), (value, expires));
+        Ok(())
+    }
+
+    async fn del(&self, key: &str) -> Result<()> {
+        self.entries.lock().expect("cache lock").pop(key);
+        Ok(())
+    }
+
+    async fn incr(&self, key: &str, ttl_secs: u64) -> Result<i64> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("cache lock");
+        let next = match entries.get(key) {
+            Some((v, expires)) if *expires > now => v.parse::<i64>()? + 1,
+            _ => 1,
+        };
+        entries.put(
+            key.to_string(),
+            (next.to_string(), now + Duration::from_secs(ttl_secs)),
+        );
+        Ok(next)
+    }
+}
+
+/// 列表接口缓存的一页结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// 列表页的缓存 key，由 [`AppCache::list_key`] 生成
+#[derive(Debug, Clone)]
+pub struct ListKey(String);
+
+/// 列表缓存的命名空间；实体变更时整体作废（代际号 +1）。
+#[derive(Debug, Clone, Copy)]
+pub enum ListNamespace {
+    Shows,
+    Tickets,
+    Transfers,
+}
+
+impl ListNamespace {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Shows => "shows",
+            Self::Tickets => "tickets",
+            Self::Transfers => "transfers",
+        }
+    }
+}
+
+pub fn ticket_cache_key(chain_id: i64, token_id: &DbU256) -> String {
+    format!("ticket:{chain_id}:{}", token_id.to_hex0x())
+}
+
+/// 代际号比依赖它的最长条目多保留的时间：覆盖进行中的回源，
+/// 代际号过期重置时依赖旧值的条目都已过期，不会被重新命中
+const GENERATION_TTL_MARGIN_SECS: u64 = 3600;
+
+fn list_generation_key(ns: ListNamespace) -> String {
+    format!("list:{}:gen", ns.as_str())
+}
+
+/// 单个 show 的代际号：每次作废 +1，回源结果只有在代际号未变时才写回
+fn show_generation_key(chain_id: i64, show_id: &DbU256) -> String {
+    format!("{}:gen", show_cache_key(chain_id, show_id))
+}
+
+/// `show:{chain_id}:{hex}` 中存放的内容：show 为 None 表示数据库中不存在（负缓存）。
+/// fresh_until 之后进入 stale 窗口：仍可返回，但会触发后台刷新。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedShow {
+    pub show: Option<ShowDataRecord>,
+    pub fresh_until: i64,
+}
+
+impl CachedShow {
+    pub fn is_fresh(&self) -> bool {
+        chrono::Utc::now().timestamp_millis() < self.fresh_until
+    }
+}
+
+/// show 读取策略：fresh TTL 之后的 stale 窗口、负缓存 TTL
+#[derive(Debug, Clone, Copy)]
+pub struct ShowCachePolicy {
+    pub stale_secs: u64,
+    pub negative_ttl_secs: u64,
+}
+
+impl Default for ShowCachePolicy {
+    fn default() -> Self {
+        Self {
+            stale_secs: 60,
+            negative_ttl_secs: 10,
+        }
+    }
+}
+
+fn hit_or_miss<T>(v: &Option<T>) -> &'static str {
+    if v.is_some() { "hit" } else { "miss" }
+}
+
+type ShowLoad = std::result::Result<Option<ShowDataRecord>, String>;
+
+/// API 与索引器共用的类型化缓存句柄。缓存只是加速层：后端出错时记录日志并回落到数据库，
+/// 不会让请求或事件处理失败。
+#[derive(Debug, Clone)]
+pub struct AppCache {
+    inner: Arc<dyn Cache>,
+    entity_ttl_secs: u64,
+    list_ttl_secs: u64,
+    show_policy: ShowCachePolicy,
+    show_loads: SingleFlight<ShowLoad>,
+}
+
+impl AppCache {
+    pub fn new(
+        inner: Arc<dyn Cache>,
+        entity_ttl_secs: u64,
+        list_ttl_secs: u64,
+    ) -> Self {
+        Self {
+            inner,
+            entity_ttl_secs,
+            list_ttl_secs,
+            show_policy: ShowCachePolicy::default(),
+            show_loads: SingleFlight::new(),
+        }
+    }
+
+    pub fn with_show_policy(mut self, policy: ShowCachePolicy) -> Self {
+        self.show_policy = policy;
+        self
+    }
+
+    pub fn memory(capacity: usize, entity_ttl_secs: u64) -> Self {
+        Self::new(
+            Arc::new(MemoryCache::new(capacity)),
+            entity_ttl_secs,
+            entity_ttl_secs,
+        )
+    }
+
+    /// 按全局配置构造；CACHE_BACKEND=none 时返回 None。Redis 连接失败时退回进程内 LRU。
+    pub async fn from_config() -> Option<Self> {
+        let cfg = crate::config::get();
+        let memory = || -> Arc<dyn Cache> {
+            Arc::new(MemoryCache::new(cfg.cache_memory_capacity))
+        };
+        let inner = match cfg.cache_backend {
+            CacheBackend::None => return None,
+            CacheBackend::Memory => memory(),
+            CacheBackend::Redis => {
+                let url = cfg.redis_url.as_deref().unwrap_or_default();
+                match RedisCache::connect(url).await {
+                    Ok(c) => Arc::new(c),
+                    Err(e) => {
+                        tracing::warn!(error = ?e, "Redis unavailable, falling back to in-memory cache");
+                        memory()
+                    }
+                }
+            }
+        };
+        tracing::info!(backend = inner.backend(), "Cache enabled");
+        Some(
+            Self::new(inner, cfg.show_cache_ttl_secs, cfg.list_cache_ttl_secs)
+                .with_show_policy(ShowCachePolicy {
+                    stale_secs: cfg.show_cache_stale_secs,
+                    negative_ttl_secs: cfg.show_negative_ttl_secs,
+                }),
+        )
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.inner.backend()
+    }
+
+    /// 代际号的 TTL：长于 show（fresh + stale）与列表页的 TTL
+    fn generation_ttl_secs(&self) -> u64 {
+        (self.entity_ttl_secs + self.show_policy.stale_secs)
+            .max(self.list_ttl_secs)
+            + GENERATION_TTL_MARGIN_SECS
+    }
+
+    /// 命中率指标：cache_lookups_total{backend, kind, result}
+    fn record_lookup(&self, kind: &str, result: &str) {
+        crate::metrics::record_cache_lookup(self.backend(), kind, result);
+    }
+
+    pub async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        match self.inner.get(key).await {
+            Ok(Some(s)) => match serde_json::from_str(&s) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    tracing::warn!(error = ?e, key, "Discarding undecodable cache entry");
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(error = ?e, key, "Cache read failed");
+                None
+            }
+        }
+    }
+
+    pub async fn set_json<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl_secs: u64,
+    ) {
+        let res = match serde_json::to_string(value) {
+            Ok(s) => self.inner.set(key, s, ttl_secs).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = res {
+            tracing::warn!(error = ?e, key, "Cache write failed");
+        }
+    }
+
+    pub async fn delete(&self, key: &str) {
+        if let Err(e) = self.inner.del(key).await {
+            tracing::warn!(error = ?e, key, "Cache invalidation failed");
+        }
+    }
+
+    /// 读取缓存中的 show（不区分 fresh/stale，负缓存视为未命中）
+    pub async fn get_show(
+        &self,
+        chain_id: i64,
+        show_id: &DbU256,
+    ) -> Option<ShowDataRecord> {
+        self.get_json::<CachedShow>(&show_cache_key(chain_id, show_id))
+            .await
+            .and_then(|c| c.show)
+    }
+
+    pub async fn put_show(&self, show: &ShowDataRecord) {
+        self.store_show(show.chain_id, &show.id, Some(show.clone()))
+            .await;
+    }
+
+    /// 写入 show 或负缓存；Redis TTL 覆盖 fresh + stale 窗口，负缓存只保留短 TTL 且不进入 stale。
+    async fn store_show(
+        &self,
+        chain_id: i64,
+        show_id: &DbU256,
+        show: Option<ShowDataRecord>,
+    ) {
+        let (fresh, ttl) = match show {
+            Some(_) => (
+                self.entity_ttl_secs,
+                self.entity_ttl_secs + self.show_policy.stale_secs,
+            ),
+            None => (
+                self.show_policy.negative_ttl_secs,
+                self.show_policy.negative_ttl_secs,
+            ),
+        };
+        let entry = CachedShow {
+            show,
+            fresh_until: chrono::Utc::now().timestamp_millis()
+                + (fresh as i64) * 1000,
+        };
+        self.set_json(&show_cache_key(chain_id, show_id), &entry, ttl)
+            .await;
+    }
+
+    /// 读穿缓存：fresh 直接返回（含负缓存）；stale 返回旧值并在后台刷新；
+    /// 未命中时同一 key 的并发请求只触发一次 `load`。
+    pub async fn get_show_with<F, Fut>(
+        &self,
+        chain_id: i64,
+        show_id: &DbU256,
+        load: F,
+    ) -> Result<Option<ShowDataRecord>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<ShowDataRecord>>> + Send + 'static,
+    {
+        let key = show_cache_key(chain_id, show_id);
+        match self.get_json::<CachedShow>(&key).await {
+            Some(c) if c.is_fresh() => {
+                self.record_lookup("show", "hit");
+                return Ok(c.show);
+            }
+            Some(CachedShow {
+                show: Some(stale), ..
+            }) => {
+                self.record_lookup("show", "stale");
+                let this = self.clone();
+                let id = show_id.clone();
+                let fut = load();
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        this.load_show_once(chain_id, &id, fut).await
+                    {
+                        tracing::warn!(error = %e, show_id = %id, "Background show refresh failed");
+                    }
+                });
+                return Ok(Some(stale));
+            }
+            _ => self.record_lookup("show", "miss"),
+        }
+        self.load_show_once(chain_id, show_id, load())
+            .await
+            .map_err(|e| eyre::eyre!(e))
+    }
+
+    /// 回源前读取代际号；回源期间若有 `invalidate_show`（代际号变化），结果只返回、不写回，
+    /// 避免旧值覆盖作废或把刚创建的 show 记成负缓存。
+    async fn load_show_once<Fut>(
+        &self,
+        chain_id: i64,
+        show_id: &DbU256,
+        fut: Fut,
+    ) -> ShowLoad
+    where
+        Fut: Future<Output = Result<Option<ShowDataRecord>>> + Send + 'static,
+    {
+        let this = self.clone();
+        let id = show_id.clone();
+        self.show_loads
+            .run(&show_cache_key(chain_id, show_id), async move {
+                let generation = this.show_generation(chain_id, &id).await;
+                let loaded = fut.await.map_err(|e| e.to_string())?;
+                if this.show_generation(chain_id, &id).await == generation {
+                    this.store_show(chain_id, &id, loaded.clone()).await;
+                } else {
+                    tracing::debug!(show_id = %id, "Show invalidated during load; not caching");
+                }
+                Ok(loaded)
+            })
+            .await
+    }
+
+    /// 读取失败时返回 None：前后两次都失败会被视为未变化，与缓存出错时回落的策略一致
+    async fn show_generation(
+        &self,
+        chain_id: i64,
+        show_id: &DbU256,
+    ) -> Option<i64> {
+        self.get_json(&show_generation_key(chain_id, show_id)).await
+    }
+
+    /// GET /show/{id} 的读路径：缓存 + single-flight 回源仓储
+    pub async fn show_read_through(
+        &self,
+        shows: &Arc<dyn ShowRepo>,
+        chain_id: i64,
+        show_id: &DbU256,
+    ) -> Result<Option<ShowDataRecord>> {
+        let shows = shows.clone();
+        let id = show_id.clone();
+        self.get_show_with(chain_id, show_id, move || async move {
+            shows.get_show(chain_id, &id).await
+        })
+        .await
+    }
+
+    /// show 变更：先递增代际号（使进行中的回源不再写回），再删除单条缓存并作废 shows 列表页
+    pub async fn invalidate_show(&self, chain_id: i64, show_id: &DbU256) {
+        let key = show_generation_key(chain_id, show_id);
+        if let Err(e) = self.inner.incr(&key, self.generation_ttl_secs()).await
+        {
+            tracing::warn!(error = ?e, key, "Cache show invalidation failed");
+        }
+        self.delete(&show_cache_key(chain_id, show_id)).await;
+        self.bump_list(ListNamespace::Shows).await;
+    }
+
+    pub async fn get_ticket(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+    ) -> Option<TicketRecord> {
+        let ticket = self.get_json(&ticket_cache_key(chain_id, token_id)).await;
+        self.record_lookup("ticket", hit_or_miss(&ticket));
+        ticket
+    }
+
+    pub async fn put_ticket(&self, ticket: &TicketRecord) {
+        self.set_json(
+            &ticket_cache_key(ticket.chain_id, &ticket.token_id),
+            ticket,
+            self.entity_ttl_secs,
+        )
+        .await;
+    }
+
+    /// ticket 变更：删除单条缓存并作废 tickets/transfers 列表页
+    pub async fn invalidate_ticket(&self, chain_id: i64, token_id: &DbU256) {
+        self.delete(&ticket_cache_key(chain_id, token_id)).await;
+        self.bump_list(ListNamespace::Tickets).await;
+        self.bump_list(ListNamespace::Transfers).await;
+    }
+
+    /// 查询前生成一次 key（含当前代际号），读写都用它：查询期间发生的作废会使写入落在旧代际下，不会被读到
+    pub async fn list_key(&self, ns: ListNamespace, query: &str) -> ListKey {
+        let generation: i64 = self
+            .get_json(&list_generation_key(ns))
+            .await
+            .unwrap_or_default();
+        ListKey(format!("list:{}:{}:{}", ns.as_str(), generation, query))
+    }
+
+    /// 读取列表页
+    pub async fn get_list_page<T: DeserializeOwned>(
+        &self,
+        key: &ListKey,
+    ) -> Option<CachedPage<T>> {
+        let page = self.get_json(&key.0).await;
+        self.record_lookup("list", hit_or_miss(&page));
+        page
+    }
+
+    pub async fn put_list_page<T: Serialize>(
+        &self,
+        key: &ListKey,
+        page: &CachedPage<T>,
+    ) {
+        self.set_json(&key.0, page, self.list_ttl_secs).await;
+    }
+
+    pub async fn bump_list(&self, ns: ListNamespace) {
+        let key = list_generation_key(ns);
+        if let Err(e) = self.inner.incr(&key, self.generation_ttl_secs()).await
+        {
+            tracing::warn!(error = ?e, key, "Cache list invalidation failed");
+        }
+    }
+}
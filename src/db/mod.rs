@@ -1,4 +1,6 @@
+pub mod cache;
 pub mod redis_cache;
+pub mod single_flight;
 use eyre::Result;
 
 use sqlx::{PgPool, postgres::PgPoolOptions};
@@ -28,11 +30,52 @@ impl Db {
         Ok(Self(pool))
     }
 
+    /// 不立即建立连接，首次查询时才连接；供只走 [`crate::repo::traits::Repos`] 的测试构造上下文
+    pub fn connect_lazy(database_url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_lazy(database_url)?;
+        Ok(Self(pool))
+    }
+
     pub fn pool(&self) -> &PgPool {
         &self.0
     }
 }
 
+/// 迁移 0009 以 chain_id = 0 占位的索引表；dids 的更新经外键级联到 did_addresses
+const LEGACY_CHAIN_TABLES: [&str; 8] = [
+    "show_created_events",
+    "show_created_events_detail",
+    "shows",
+    "tickets",
+    "ticket_transfers",
+    "listings",
+    "dids",
+    "indexer_checkpoints",
+];
+
+/// 把迁移 0009 之前写入的数据（chain_id = 0）归到配置的默认链。
+/// 启动时在一个事务中执行，没有待回填的行时只是几次主键索引查询；返回更新的行数。
+pub async fn backfill_legacy_chain_id(
+    pool: &PgPool,
+    chain_id: i64,
+) -> Result<u64> {
+    let mut tx = pool.begin().await?;
+    let mut updated = 0;
+    for table in LEGACY_CHAIN_TABLES {
+        updated += sqlx::query(&format!(
+            "UPDATE {table} SET chain_id = $1 WHERE chain_id = 0;"
+        ))
+        .bind(chain_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+    tx.commit().await?;
+    Ok(updated)
+}
+
 // Migration helper (expects sqlx-cli or external migration runner). For now a stub.
 #[allow(unused)]
 pub async fn run_migrations(pool: &PgPool) -> Result<()> {
@@ -1,10 +1,20 @@
-use crate::{repo::show_repo::ShowDataRecord, utils::uint256::DbU256};
+use crate::{
+    db::cache::{Cache, CachedShow},
+    repo::show_repo::ShowDataRecord,
+    utils::uint256::DbU256,
+};
+use async_trait::async_trait;
 use eyre::Result;
 use redis::AsyncCommands;
+use std::fmt;
 
-/// 统一构造 Redis 缓存 key（使用 0x 十六进制规范化形式，避免十进制/十六进制混用导致重复）
-pub fn show_cache_key(show_id: &DbU256) -> String {
-    format!("show:{}", show_id.to_hex0x())
+/// 默认缓存 TTL（秒），可通过 SHOW_CACHE_TTL_SECS 覆盖
+pub const DEFAULT_SHOW_CACHE_TTL_SECS: u64 = 3600;
+
+/// 统一构造 Redis 缓存 key（使用 0x 十六进制规范化形式，避免十进制/十六进制混用导致重复）；
+/// 不同链上的同一 show_id 是不同的 show，key 中带上 chain_id。
+pub fn show_cache_key(chain_id: i64, show_id: &DbU256) -> String {
+    format!("show:{chain_id}:{}", show_id.to_hex0x())
 }
 
 pub async fn get_redis_connection(
@@ -15,38 +25,102 @@ pub async fn get_redis_connection(
     Ok(conn)
 }
 
+/// 与 AppCache 相同的 `show:{chain_id}:{hex}` 格式（CachedShow 包装），写入的条目在 ttl 内视为 fresh。
 pub async fn cache_show(
     conn: &mut redis::aio::MultiplexedConnection,
     show_id: DbU256,
     show: &ShowDataRecord,
+    ttl_secs: u64,
 ) -> Result<()> {
-    let key = show_cache_key(&show_id);
-    let value = serde_json::to_string(show)?;
-    let _: () = conn.set_ex(key, value, 3600).await?;
+    let key = show_cache_key(show.chain_id, &show_id);
+    let value = serde_json::to_string(&CachedShow {
+        show: Some(show.clone()),
+        fresh_until: chrono::Utc::now().timestamp_millis()
+            + (ttl_secs as i64) * 1000,
+    })?;
+    let _: () = conn.set_ex(key, value, ttl_secs).await?;
     Ok(())
 }
 pub async fn get_cached_show(
     conn: &mut redis::aio::MultiplexedConnection,
+    chain_id: i64,
     show_id: DbU256,
 ) -> Result<Option<ShowDataRecord>> {
-    let key = show_cache_key(&show_id);
+    let key = show_cache_key(chain_id, &show_id);
     let value: Option<String> = conn.get(key).await?;
     if let Some(json) = value {
-        let show: ShowDataRecord = serde_json::from_str(&json)?;
-        Ok(Some(show))
+        let cached: CachedShow = serde_json::from_str(&json)?;
+        Ok(cached.show)
     } else {
         Ok(None)
     }
 }
 pub async fn delete_cached_show(
     conn: &mut redis::aio::MultiplexedConnection,
+    chain_id: i64,
     show_id: DbU256,
 ) -> Result<()> {
-    let key = show_cache_key(&show_id);
+    let key = show_cache_key(chain_id, &show_id);
     let _: () = conn.del(key).await?;
     Ok(())
 }
 
+/// Redis 实现的 Cache；MultiplexedConnection 可廉价 clone，并发请求共享同一连接。
+#[derive(Clone)]
+pub struct RedisCache {
+    conn: redis::aio::MultiplexedConnection,
+}
+
+impl fmt::Debug for RedisCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisCache").finish_non_exhaustive()
+    }
+}
+
+impl RedisCache {
+    pub async fn connect(redis_url: &str) -> Result<Self> {
+        Ok(Self {
+            conn: get_redis_connection(redis_url).await?,
+        })
+    }
+}
+
+#[async_trait]
+impl Cache for RedisCache {
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let mut conn = self.conn.clone();
+        Ok(conn.get(key).await?)
+    }
+
+    async fn set(&self, key: &str, value: String, ttl_secs: u64) -> Result<()> {
+        let mut conn = self.conn.clone();
+        let _: () = conn.set_ex(key, value, ttl_secs).await?;
+        Ok(())
+    }
+
+    async fn del(&self, key: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        let _: () = conn.del(key).await?;
+        Ok(())
+    }
+
+    async fn incr(&self, key: &str, ttl_secs: u64) -> Result<i64> {
+        let mut conn = self.conn.clone();
+        let (v,): (i64,) = redis::pipe()
+            .atomic()
+            .incr(key, 1)
+            .expire(key, i64::try_from(ttl_secs)?)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(v)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,13 +131,15 @@ mod tests {
     fn test_show_cache_key_normalizes_input() {
         let dec = DbU256::from_str("123456789").unwrap();
         let hex = DbU256::from_str("0x075bcd15").unwrap();
-        assert_eq!(show_cache_key(&dec), show_cache_key(&hex));
-        assert!(show_cache_key(&dec).starts_with("show:"));
+        assert_eq!(show_cache_key(1, &dec), show_cache_key(1, &hex));
+        assert!(show_cache_key(1, &dec).starts_with("show:1:"));
+        assert_ne!(show_cache_key(1, &dec), show_cache_key(10, &dec));
     }
 
     #[test]
     fn test_showdata_json_roundtrip() {
         let rec = ShowDataRecord {
+            chain_id: 31337,
             id: DbU256::from_str("0x1").unwrap(),
             name: "Concert".to_string(),
             description: "A big show".to_string(),
@@ -75,11 +151,13 @@ mod tests {
             is_active: true,
             organizer: "alice".to_string(),
             created_at: Utc::now(),
+            version: 0,
         };
 
         let json = serde_json::to_string(&rec).unwrap();
         let back: ShowDataRecord = serde_json::from_str(&json).unwrap();
 
+        assert_eq!(rec.chain_id, back.chain_id);
         assert_eq!(rec.id.to_string(), back.id.to_string());
         assert_eq!(rec.name, back.name);
         assert_eq!(rec.location, back.location);
@@ -89,4 +167,20 @@ mod tests {
         assert_eq!(rec.is_active, back.is_active);
         assert_eq!(rec.organizer, back.organizer);
     }
+
+    // 需要本地 Redis（REDIS_URL），默认忽略
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_cache_set_get_del_incr() {
+        let url = std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+        let cache = RedisCache::connect(&url).await.unwrap();
+        cache.set("test:k", "v".into(), 5).await.unwrap();
+        assert_eq!(cache.get("test:k").await.unwrap().as_deref(), Some("v"));
+        cache.del("test:k").await.unwrap();
+        assert!(cache.get("test:k").await.unwrap().is_none());
+        let a = cache.incr("test:gen", 60).await.unwrap();
+        assert_eq!(cache.incr("test:gen", 60).await.unwrap(), a + 1);
+        cache.del("test:gen").await.unwrap();
+    }
 }
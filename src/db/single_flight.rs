@@ -0,0 +1,79 @@
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+/// 按 key 合并并发加载：同一 key 同时只有一个加载在执行，其余调用方等待并共享结果。
+/// 加载 future 在首个调用方被取消后仍由其余等待者驱动，完成后自动从表中移除。
+pub struct SingleFlight<T: Clone> {
+    inflight: Arc<Mutex<HashMap<String, Shared<BoxFuture<'static, T>>>>>,
+}
+
+impl<T: Clone> Clone for SingleFlight<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inflight: self.inflight.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            inflight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T: Clone> fmt::Debug for SingleFlight<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.inflight.lock().map(|m| m.len()).unwrap_or(0);
+        f.debug_struct("SingleFlight")
+            .field("inflight", &n)
+            .finish()
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SingleFlight<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 若 key 已有加载在执行则等待其结果，否则执行 `load`。
+    pub async fn run<F>(&self, key: &str, load: F) -> T
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let fut = {
+            let mut map = self.inflight.lock().expect("single-flight lock");
+            match map.get(key) {
+                Some(f) => f.clone(),
+                None => {
+                    let inflight = self.inflight.clone();
+                    let owned_key = key.to_string();
+                    let fut = async move {
+                        let out = load.await;
+                        inflight
+                            .lock()
+                            .expect("single-flight lock")
+                            .remove(&owned_key);
+                        out
+                    }
+                    .boxed()
+                    .shared();
+                    map.insert(key.to_string(), fut.clone());
+                    fut
+                }
+            }
+        };
+        fut.await
+    }
+
+    /// 当前正在执行的加载数（用于测试与调试）
+    pub fn inflight(&self) -> usize {
+        self.inflight.lock().map(|m| m.len()).unwrap_or(0)
+    }
+}
@@ -1,8 +1,15 @@
+// `#[derive(Validate)]` 展开为 `::backend::…` 路径，crate 内部使用时也需要能解析
+extern crate self as backend;
+
 pub mod api;
 pub mod config;
 pub mod contract;
 pub mod db;
 pub mod logging;
+pub mod metrics;
+pub mod realtime;
 pub mod repo;
+pub mod shutdown;
 pub mod tools;
 pub mod utils;
+pub mod webhook;
@@ -1,52 +1,47 @@
-use std::{env, path::Path, sync::OnceLock};
+use crate::config::{self, LogFormat, LogRotation, LoggingConfig};
+use std::{path::Path, sync::OnceLock};
 use tracing_subscriber::{
-    EnvFilter, Layer, Registry, fmt, layer::SubscriberExt,
+    EnvFilter, Layer, Registry, fmt, layer::SubscriberExt, reload,
     util::SubscriberInitExt,
 };
 
 static INITED: OnceLock<()> = OnceLock::new();
-
-fn env_filter() -> EnvFilter {
-    // Default to info if RUST_LOG unset
-    let default_directives = "info";
-    match EnvFilter::try_from_default_env() {
-        Ok(f) => f,
-        Err(_) => EnvFilter::new(default_directives),
-    }
-}
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
 
 /// Initialize global tracing subscriber exactly once.
 ///
-/// Env vars:
-/// - RUST_LOG: env filter (e.g. "backend=debug,axum=info")
-/// - LOG_FORMAT: "pretty" (default in dev) or "json"
-/// - LOG_FILE: if set, also write logs to this file with rotation by size (daily not included)
-/// - LOG_ANSI: "0" to disable ANSI colors
+/// Settings come from the `[logging]` section of the config file or the env vars
+/// RUST_LOG, LOG_FORMAT, LOG_ANSI, LOG_FILE, LOG_DIR and LOG_ROTATION. Runs before the
+/// global config is validated, so an invalid logging section falls back to defaults
+/// here and is reported by `config::init_from_env`.
 pub fn init() {
     if INITED.get().is_some() {
         return;
     }
+    let cfg =
+        LoggingConfig::from_sources(config::sources()).unwrap_or_default();
 
-    let filter = env_filter();
+    let filter = EnvFilter::try_new(&cfg.filter)
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = FILTER.set(handle);
     let fmt_layer_builder = fmt::layer()
         .with_target(true)
         .with_thread_ids(false)
         .with_thread_names(false)
         .with_file(true)
         .with_line_number(true)
-        .with_ansi(env::var("LOG_ANSI").map_or(true, |v| v != "0"));
-
-    let format = env::var("LOG_FORMAT").unwrap_or_else(|_| "pretty".into());
+        .with_ansi(cfg.ansi);
 
     // Console formatting
-    let console_layer = if format.eq_ignore_ascii_case("json") {
+    let console_layer = if cfg.format == LogFormat::Json {
         fmt_layer_builder.json().boxed()
     } else {
         fmt_layer_builder.pretty().boxed()
     };
 
     let base = Registry::default().with(filter).with(console_layer);
-    if let Ok(file_var) = env::var("LOG_FILE") {
+    if let Some(file_var) = cfg.file.clone() {
         if !file_var.is_empty() {
             // Derive dir/file from LOG_FILE and optional LOG_DIR
             let p = Path::new(&file_var);
@@ -61,22 +56,28 @@ pub fn init() {
                     }
                 });
                 let dir = parent
-                    .or_else(|| env::var("LOG_DIR").ok())
+                    .or_else(|| cfg.dir.clone())
                     .unwrap_or_else(|| ".".into());
                 (dir, fname.to_string())
             } else {
-                let dir = env::var("LOG_DIR").unwrap_or_else(|_| ".".into());
+                let dir = cfg.dir.clone().unwrap_or_else(|| ".".into());
                 (dir, file_var)
             };
 
             // Time-based rotation: never|daily|hourly|minutely
-            let rotation =
-                env::var("LOG_ROTATION").unwrap_or_else(|_| "never".into());
-            let file_appender = match rotation.to_lowercase().as_str() {
-                "daily" => tracing_appender::rolling::daily(&dir, &file),
-                "hourly" => tracing_appender::rolling::hourly(&dir, &file),
-                "minutely" => tracing_appender::rolling::minutely(&dir, &file),
-                _ => tracing_appender::rolling::never(&dir, &file),
+            let file_appender = match cfg.rotation {
+                LogRotation::Daily => {
+                    tracing_appender::rolling::daily(&dir, &file)
+                }
+                LogRotation::Hourly => {
+                    tracing_appender::rolling::hourly(&dir, &file)
+                }
+                LogRotation::Minutely => {
+                    tracing_appender::rolling::minutely(&dir, &file)
+                }
+                LogRotation::Never => {
+                    tracing_appender::rolling::never(&dir, &file)
+                }
             };
 
             let (non_blocking, _guard) =
@@ -103,5 +104,15 @@ pub fn init() {
     let _ = INITED.set(());
 }
 
+/// Replace the active log filter (RUST_LOG directives) without restarting.
+pub fn set_filter(directives: &str) -> eyre::Result<()> {
+    let filter = EnvFilter::try_new(directives)?;
+    let handle = FILTER
+        .get()
+        .ok_or_else(|| eyre::eyre!("logging is not initialized"))?;
+    handle.reload(filter)?;
+    Ok(())
+}
+
 /// Convenience re-export of common macros
 pub use tracing::{debug, error, info, instrument, trace, warn};
@@ -1,10 +1,19 @@
 use backend::{
     api::listen_app,
-    contract::{listen_chain, providers},
-    db::Db,
+    contract::{
+        IndexerContext, abi_check, listen_chain, providers,
+        status::IndexerStatus,
+    },
+    db::{Db, cache::AppCache},
+    realtime::EventHub,
+    repo::traits::Repos,
+    shutdown,
+    webhook::worker::{WebhookWorker, WorkerSettings},
 };
-use eyre::{Ok, Result};
-use tokio::signal;
+use eyre::Result;
+use std::time::Duration;
+use tokio::task::{JoinError, JoinSet};
+use tracing::Instrument;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -13,12 +22,158 @@ async fn main() -> Result<()> {
     backend::logging::init();
     let config = backend::config::init_from_env()?;
     let db = Db::connect(&config.database_url, 5).await?;
+    backend::metrics::register_pool("indexer", db.pool().clone());
     let pool: &'static providers::ProviderPool = providers::init_pool().await?;
+    // init_pool 已用 eth_chainId 核对过默认链，再把升级前的数据归到这条链
+    let chain_id = config.default_chain().chain_id;
+    let backfilled =
+        backend::db::backfill_legacy_chain_id(db.pool(), chain_id).await?;
+    if backfilled > 0 {
+        tracing::info!(
+            chain_id,
+            rows = backfilled,
+            "Assigned pre-multi-chain rows to the default chain"
+        );
+    }
+    providers::init_signer_pool_from_env_and_disk()?;
+    if config.abi_check_on_startup {
+        tokio::spawn(check_abis(config, pool));
+    }
+    let cache = AppCache::from_config().await;
+    let events = EventHub::from_config().await;
+    // 索引器只负责落投递记录，发送由独立 worker 完成
+    let repos = Repos::postgres(db.pool().clone());
+    let webhooks = WebhookWorker::new(
+        repos.webhooks.clone(),
+        WorkerSettings::from_config(),
+    )?;
+    // 各链共用一份状态，按 chain_id 区分
+    let status = IndexerStatus::default();
+
+    let (trigger, shutdown) = shutdown::channel();
+    let mut tasks = JoinSet::new();
+    tasks.spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            webhooks.run(shutdown).await;
+            ("webhook_worker", Ok(()))
+        }
+    });
+    tasks.spawn({
+        let shutdown = shutdown.clone();
+        let status = status.clone();
+        let cache = cache.clone();
+        let events = events.clone();
+        async move {
+            let res = listen_app(cache, events, status, shutdown).await;
+            ("listen_app", res)
+        }
+    });
+    // 每条配置的链一个监听任务
+    for chain in &config.chains {
+        let indexer = IndexerContext {
+            chain_id: chain.chain_id,
+            db: db.clone(),
+            repos: repos.clone(),
+            cache: cache.clone(),
+            events: events.clone(),
+            status: status.clone(),
+            replaying: false,
+        };
+        let span = tracing::info_span!(
+            "chain",
+            chain_id = chain.chain_id,
+            name = %chain.name
+        );
+        let shutdown = shutdown.clone();
+        tasks.spawn(
+            async move {
+                let res = listen_chain(config, indexer, pool, shutdown).await;
+                ("listen_chain", res)
+            }
+            .instrument(span),
+        );
+    }
 
+    #[cfg(unix)]
+    tokio::spawn(reload_on_sighup());
+
+    // 收到信号或任一任务提前退出，都通知全部任务收尾
     tokio::select! {
-        res = listen_app() => { if let Err(e) = res { tracing::error!(error = ?e, "Error in listen_app"); } }
-        res = listen_chain(&config, db.clone(), pool) => { if let Err(e) = res { tracing::error!(error = ?e, "Error in listen_chain"); } }
-        _ = signal::ctrl_c() => { tracing::info!("Received Ctrl+C, shutting down."); }
+        _ = shutdown::signal() => {}
+        Some(res) = tasks.join_next() => log_exit(res),
+    }
+    trigger.trigger();
+
+    let drain = Duration::from_secs(config.shutdown_timeout_secs);
+    let drained = tokio::time::timeout(drain, async {
+        while let Some(res) = tasks.join_next().await {
+            log_exit(res);
+        }
+    })
+    .await;
+    if drained.is_err() {
+        tracing::warn!(
+            timeout_secs = config.shutdown_timeout_secs,
+            "Shutdown drain timed out, aborting remaining tasks"
+        );
+        tasks.shutdown().await;
     }
+    tracing::info!("Shutdown complete");
     Ok(())
 }
+
+/// 核对内置 ABI 与各链上已部署的合约；发现不一致只记录告警
+async fn check_abis(
+    config: &'static backend::config::Config,
+    pool: &'static providers::ProviderPool,
+) {
+    for chain in &config.chains {
+        let Some(reader) = pool.ws_reader_for(chain.chain_id) else {
+            continue;
+        };
+        match abi_check::check_chain(&reader, chain, config.abi_check_blocks)
+            .await
+        {
+            Ok(reports) => {
+                abi_check::log_reports(&reports);
+            }
+            Err(e) => {
+                tracing::warn!(chain_id = chain.chain_id, error = ?e, "ABI check failed")
+            }
+        }
+    }
+}
+
+/// SIGHUP 时重新加载配置文件
+#[cfg(unix)]
+async fn reload_on_sighup() {
+    use tokio::signal::unix::{SignalKind, signal};
+    let Ok(mut hup) = signal(SignalKind::hangup()) else {
+        return;
+    };
+    while hup.recv().await.is_some() {
+        match backend::config::reload() {
+            Ok(report) => tracing::info!(
+                applied = ?report.applied,
+                restart_required = ?report.restart_required,
+                "Config reloaded"
+            ),
+            Err(e) => {
+                tracing::error!(error = ?e, "Config reload rejected, keeping current settings")
+            }
+        }
+    }
+}
+
+fn log_exit(res: std::result::Result<(&'static str, Result<()>), JoinError>) {
+    match res {
+        Ok((name, Ok(()))) => {
+            tracing::info!(task = name, "Task exited")
+        }
+        Ok((name, Err(e))) => {
+            tracing::error!(task = name, error = ?e, "Task failed")
+        }
+        Err(e) => tracing::error!(error = ?e, "Task panicked"),
+    }
+}
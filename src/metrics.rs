@@ -0,0 +1,237 @@
+//! Prometheus 指标：进程内全局 Registry，由 GET /metrics 以文本格式导出。
+//! 计数/直方图在事件发生处记录；连接池、链头与索引落后等“状态类”指标在抓取时采集。
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use sqlx::PgPool;
+use std::{
+    future::IntoFuture,
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+
+pub struct Metrics {
+    registry: Registry,
+    /// method, route（路由模板）, status
+    pub http_requests: IntCounterVec,
+    pub http_duration: HistogramVec,
+    /// pool, state（idle / in_use）
+    pub db_pool_connections: IntGaugeVec,
+    /// pool
+    pub db_pool_max_connections: IntGaugeVec,
+    /// chain_id, contract, event, outcome（processed / error）
+    pub indexer_events: IntCounterVec,
+    /// chain_id, contract
+    pub indexer_last_block: IntGaugeVec,
+    pub indexer_lag_blocks: IntGaugeVec,
+    /// chain_id
+    pub chain_head_block: IntGaugeVec,
+    /// chain_id, contract：最近一次 ABI 一致性检查发现的问题数
+    pub abi_drift: IntGaugeVec,
+    /// method, outcome（ok / error）
+    pub rpc_duration: HistogramVec,
+    /// backend（redis / memory）, kind（show / ticket / list）, result（hit / stale / miss）
+    pub cache_lookups: IntCounterVec,
+    pools: Mutex<Vec<(&'static str, PgPool)>>,
+}
+
+impl std::fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metrics").finish_non_exhaustive()
+    }
+}
+
+fn counter(
+    registry: &Registry,
+    name: &str,
+    help: &str,
+    labels: &[&str],
+) -> IntCounterVec {
+    let c = IntCounterVec::new(Opts::new(name, help), labels)
+        .expect("valid counter");
+    registry
+        .register(Box::new(c.clone()))
+        .expect("unique metric");
+    c
+}
+
+fn gauge(
+    registry: &Registry,
+    name: &str,
+    help: &str,
+    labels: &[&str],
+) -> IntGaugeVec {
+    let g =
+        IntGaugeVec::new(Opts::new(name, help), labels).expect("valid gauge");
+    registry
+        .register(Box::new(g.clone()))
+        .expect("unique metric");
+    g
+}
+
+fn histogram(
+    registry: &Registry,
+    name: &str,
+    help: &str,
+    labels: &[&str],
+) -> HistogramVec {
+    let h = HistogramVec::new(HistogramOpts::new(name, help), labels)
+        .expect("valid histogram");
+    registry
+        .register(Box::new(h.clone()))
+        .expect("unique metric");
+    h
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        Self {
+            http_requests: counter(
+                &registry,
+                "http_requests_total",
+                "HTTP requests by method, route template and status",
+                &["method", "route", "status"],
+            ),
+            http_duration: histogram(
+                &registry,
+                "http_request_duration_seconds",
+                "HTTP request latency by method, route template and status",
+                &["method", "route", "status"],
+            ),
+            db_pool_connections: gauge(
+                &registry,
+                "db_pool_connections",
+                "Postgres pool connections by state",
+                &["pool", "state"],
+            ),
+            db_pool_max_connections: gauge(
+                &registry,
+                "db_pool_max_connections",
+                "Postgres pool size limit",
+                &["pool"],
+            ),
+            indexer_events: counter(
+                &registry,
+                "indexer_events_total",
+                "Contract logs handled by the indexer by chain, contract, event and outcome",
+                &["chain_id", "contract", "event", "outcome"],
+            ),
+            indexer_last_block: gauge(
+                &registry,
+                "indexer_last_block",
+                "Last block with a processed log per chain and contract",
+                &["chain_id", "contract"],
+            ),
+            indexer_lag_blocks: gauge(
+                &registry,
+                "indexer_lag_blocks",
+                "Blocks between the chain head and the contract's last processed log",
+                &["chain_id", "contract"],
+            ),
+            chain_head_block: gauge(
+                &registry,
+                "chain_head_block",
+                "Latest block number reported by each chain's RPC node (sampled on scrape)",
+                &["chain_id"],
+            ),
+            abi_drift: gauge(
+                &registry,
+                "contract_abi_drift",
+                "Findings from the last check of bundled ABIs against deployed contracts",
+                &["chain_id", "contract"],
+            ),
+            rpc_duration: histogram(
+                &registry,
+                "rpc_request_duration_seconds",
+                "Ethereum RPC call latency by method and outcome",
+                &["method", "outcome"],
+            ),
+            cache_lookups: counter(
+                &registry,
+                "cache_lookups_total",
+                "Cache lookups by backend, kind and result",
+                &["backend", "kind", "result"],
+            ),
+            pools: Mutex::new(Vec::new()),
+            registry,
+        }
+    }
+
+    /// 以 Prometheus 文本格式导出（调用前应先刷新抓取时采集的指标）
+    pub fn render(&self) -> String {
+        self.sample_pools();
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buf)
+            .expect("text encoding cannot fail");
+        String::from_utf8(buf).expect("prometheus text is utf-8")
+    }
+
+    fn sample_pools(&self) {
+        let pools = self.pools.lock().expect("metrics pools poisoned");
+        for (name, pool) in pools.iter() {
+            let idle = pool.num_idle() as i64;
+            let size = i64::from(pool.size());
+            self.db_pool_connections
+                .with_label_values(&[name, "idle"])
+                .set(idle);
+            self.db_pool_connections
+                .with_label_values(&[name, "in_use"])
+                .set((size - idle).max(0));
+            self.db_pool_max_connections
+                .with_label_values(&[name])
+                .set(i64::from(pool.options().get_max_connections()));
+        }
+    }
+}
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+pub fn get() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// 注册需要在抓取时采集用量的连接池（API 与索引器各自持有一个）
+pub fn register_pool(name: &'static str, pool: PgPool) {
+    let mut pools = get().pools.lock().expect("metrics pools poisoned");
+    pools.retain(|(n, _)| *n != name);
+    pools.push((name, pool));
+}
+
+/// 统计一次 RPC 调用的耗时与结果
+pub async fn observe_rpc<T, E, F>(method: &'static str, fut: F) -> Result<T, E>
+where
+    F: IntoFuture<Output = Result<T, E>>,
+{
+    let started = Instant::now();
+    let res = fut.await;
+    let outcome = if res.is_ok() { "ok" } else { "error" };
+    get()
+        .rpc_duration
+        .with_label_values(&[method, outcome])
+        .observe(started.elapsed().as_secs_f64());
+    res
+}
+
+/// 索引器处理完一条日志
+pub fn record_indexer_event(
+    chain_id: i64,
+    contract: &str,
+    event: &str,
+    ok: bool,
+) {
+    let outcome = if ok { "processed" } else { "error" };
+    get()
+        .indexer_events
+        .with_label_values(&[&chain_id.to_string(), contract, event, outcome])
+        .inc();
+}
+
+pub fn record_cache_lookup(backend: &str, kind: &str, result: &str) {
+    get()
+        .cache_lookups
+        .with_label_values(&[backend, kind, result])
+        .inc();
+}
@@ -0,0 +1,206 @@
+pub mod redis_bus;
+
+use crate::{
+    repo::{listing_repo::ListingRecord, show_repo::ShowDataRecord},
+    utils::uint256::DbU256,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, sync::Arc};
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+
+/// 客户端可订阅的主题。id 统一规范化为十进制，`show:0x1` 与 `show:1` 等价。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// 所有 show 的变更
+    Shows,
+    /// 某个 show 相关的全部事件（show 变更、售票、挂单、检票）
+    Show(DbU256),
+    /// 单张票的事件
+    Ticket(DbU256),
+    /// 所有挂单变更
+    Listings,
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::Shows => write!(f, "shows"),
+            Topic::Show(id) => write!(f, "show:{id}"),
+            Topic::Ticket(id) => write!(f, "ticket:{id}"),
+            Topic::Listings => write!(f, "listings"),
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().split_once(':') {
+            None if s.trim() == "shows" => Ok(Topic::Shows),
+            None if s.trim() == "listings" => Ok(Topic::Listings),
+            Some(("show", id)) => DbU256::from_str(id)
+                .map(Topic::Show)
+                .map_err(|e| format!("invalid show id in topic: {e}")),
+            Some(("ticket", id)) => DbU256::from_str(id)
+                .map(Topic::Ticket)
+                .map_err(|e| format!("invalid ticket id in topic: {e}")),
+            _ => Err(format!(
+                "unknown topic {s:?} (expected shows, listings, show:<id>, ticket:<id>)"
+            )),
+        }
+    }
+}
+
+impl Serialize for Topic {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Topic {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        d: D,
+    ) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Topic::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// 索引器落库后推送给客户端的事件
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeEvent {
+    /// show 创建或链上状态变化（含最新 sold_tickets）
+    ShowChanged { show: ShowDataRecord },
+    /// 一级市场售出（TicketMinted）
+    TicketSold {
+        chain_id: i64,
+        show_id: DbU256,
+        token_id: DbU256,
+        owner: String,
+        price: DbU256,
+    },
+    /// 二级市场挂单新建/改价/取消/成交；show_id 由 tickets 表反查，未索引到时为空
+    ListingChanged {
+        show_id: Option<DbU256>,
+        listing: ListingRecord,
+    },
+    /// 检票（TicketUsed）
+    TicketCheckedIn {
+        chain_id: i64,
+        show_id: DbU256,
+        token_id: DbU256,
+        verifier: String,
+    },
+}
+
+impl RealtimeEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RealtimeEvent::ShowChanged { .. } => "show_changed",
+            RealtimeEvent::TicketSold { .. } => "ticket_sold",
+            RealtimeEvent::ListingChanged { .. } => "listing_changed",
+            RealtimeEvent::TicketCheckedIn { .. } => "ticket_checked_in",
+        }
+    }
+
+    /// 事件所在链；主题中的 id 只在同一条链内唯一，订阅端需先按链过滤
+    pub fn chain_id(&self) -> i64 {
+        match self {
+            RealtimeEvent::ShowChanged { show } => show.chain_id,
+            RealtimeEvent::TicketSold { chain_id, .. }
+            | RealtimeEvent::TicketCheckedIn { chain_id, .. } => *chain_id,
+            RealtimeEvent::ListingChanged { listing, .. } => listing.chain_id,
+        }
+    }
+
+    /// 事件会投递到的所有主题
+    pub fn topics(&self) -> Vec<Topic> {
+        match self {
+            RealtimeEvent::ShowChanged { show } => {
+                vec![Topic::Shows, Topic::Show(show.id.clone())]
+            }
+            RealtimeEvent::TicketSold {
+                show_id, token_id, ..
+            }
+            | RealtimeEvent::TicketCheckedIn {
+                show_id, token_id, ..
+            } => vec![
+                Topic::Show(show_id.clone()),
+                Topic::Ticket(token_id.clone()),
+            ],
+            RealtimeEvent::ListingChanged { show_id, listing } => {
+                let mut t = vec![
+                    Topic::Listings,
+                    Topic::Ticket(listing.token_id.clone()),
+                ];
+                if let Some(id) = show_id {
+                    t.push(Topic::Show(id.clone()));
+                }
+                t
+            }
+        }
+    }
+
+    pub fn matches(&self, topic: &Topic) -> bool {
+        self.topics().contains(topic)
+    }
+}
+
+/// 进程内事件总线。配置 Redis 时发布走 Redis pub/sub，由每个实例的订阅任务回灌本地总线，
+/// 这样任意实例上的 SSE/WebSocket 连接都能收到其他实例索引到的事件。
+#[derive(Debug, Clone)]
+pub struct EventHub {
+    local: broadcast::Sender<Arc<RealtimeEvent>>,
+    redis: Option<redis_bus::RedisPublisher>,
+}
+
+impl EventHub {
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// 仅进程内广播（单实例或测试）
+    pub fn local(capacity: usize) -> Self {
+        let (local, _) = broadcast::channel(capacity.max(1));
+        Self { local, redis: None }
+    }
+
+    /// 配置了 REDIS_URL 时启用跨实例 fan-out；连接失败时退回进程内广播。
+    pub async fn from_config() -> Self {
+        let hub = Self::local(Self::DEFAULT_CAPACITY);
+        let Some(url) = crate::config::get().redis_url.as_deref() else {
+            return hub;
+        };
+        match redis_bus::connect(url, hub.local.clone()).await {
+            Ok(publisher) => {
+                tracing::info!("Realtime events fan out via Redis pub/sub");
+                Self {
+                    redis: Some(publisher),
+                    ..hub
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = ?e, "Redis pub/sub unavailable, realtime events stay in-process");
+                hub
+            }
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<RealtimeEvent>> {
+        self.local.subscribe()
+    }
+
+    /// 发布事件；失败只记录日志，不影响索引。
+    pub async fn publish(&self, event: RealtimeEvent) {
+        if let Some(p) = &self.redis {
+            match p.publish(&event).await {
+                Ok(()) => return,
+                Err(e) => {
+                    tracing::warn!(error = ?e, kind = event.kind(), "Redis publish failed, delivering locally");
+                }
+            }
+        }
+        // 没有订阅者时 send 返回 Err，属正常情况
+        let _ = self.local.send(Arc::new(event));
+    }
+}
@@ -0,0 +1,93 @@
+use super::RealtimeEvent;
+use eyre::Result;
+use futures_util::StreamExt;
+use redis::AsyncCommands;
+use std::{fmt, sync::Arc, time::Duration};
+use tokio::sync::broadcast;
+
+/// 所有实例共用的 Redis 频道
+pub const CHANNEL: &str = "ticket:realtime";
+
+#[derive(Clone)]
+pub struct RedisPublisher {
+    conn: redis::aio::MultiplexedConnection,
+}
+
+impl fmt::Debug for RedisPublisher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisPublisher").finish_non_exhaustive()
+    }
+}
+
+impl RedisPublisher {
+    pub async fn publish(&self, event: &RealtimeEvent) -> Result<()> {
+        let payload = serde_json::to_string(event)?;
+        let mut conn = self.conn.clone();
+        let _: i64 = conn.publish(CHANNEL, payload).await?;
+        Ok(())
+    }
+}
+
+/// 建立发布连接并启动订阅任务：收到的消息转发到本地 broadcast。
+pub async fn connect(
+    redis_url: &str,
+    local: broadcast::Sender<Arc<RealtimeEvent>>,
+) -> Result<RedisPublisher> {
+    let client = redis::Client::open(redis_url)?;
+    let conn = client.get_multiplexed_async_connection().await?;
+    // 先确认能订阅，再交给后台任务（断线后自动重连）
+    let pubsub = subscribe(&client).await?;
+    tokio::spawn(forward_loop(client, pubsub, local));
+    Ok(RedisPublisher { conn })
+}
+
+async fn subscribe(client: &redis::Client) -> Result<redis::aio::PubSub> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(CHANNEL).await?;
+    Ok(pubsub)
+}
+
+async fn forward_loop(
+    client: redis::Client,
+    mut pubsub: redis::aio::PubSub,
+    local: broadcast::Sender<Arc<RealtimeEvent>>,
+) {
+    let mut backoff = Duration::from_millis(500);
+    loop {
+        {
+            let mut stream = pubsub.on_message();
+            while let Some(msg) = stream.next().await {
+                let payload: String = match msg.get_payload() {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::warn!(error = ?e, "Bad realtime payload");
+                        continue;
+                    }
+                };
+                match serde_json::from_str::<RealtimeEvent>(&payload) {
+                    Ok(ev) => {
+                        let _ = local.send(Arc::new(ev));
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = ?e, "Undecodable realtime event")
+                    }
+                }
+            }
+        }
+        tracing::warn!("Redis pub/sub connection closed, reconnecting");
+        loop {
+            tokio::time::sleep(backoff).await;
+            match subscribe(&client).await {
+                Ok(p) => {
+                    pubsub = p;
+                    backoff = Duration::from_millis(500);
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Redis pub/sub reconnect failed");
+                    backoff = (backoff * 2).min(Duration::from_secs(30));
+                }
+            }
+        }
+    }
+}
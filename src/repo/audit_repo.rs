@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::{
+    Executor, PgPool, Postgres, QueryBuilder, Transaction, prelude::FromRow,
+};
+use utoipa::ToSchema;
+
+/// audit_log 表：通过 API 发起的一次写操作。
+/// before/after 只包含发生变化的顶层字段；创建时 before 为空，删除时 after 为空。
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AuditRecord {
+    pub id: i64,
+    pub chain_id: Option<i64>,
+    pub resource: String,
+    pub resource_id: String,
+    pub action: String,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 写操作的发起方：调用方地址与 request id，由 handler 从请求中取得
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// 待写入的一条审计记录（id/时间戳由数据库生成）
+#[derive(Debug, Clone)]
+pub struct NewAuditEntry {
+    pub chain_id: Option<i64>,
+    pub resource: &'static str,
+    pub resource_id: String,
+    pub action: &'static str,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl NewAuditEntry {
+    /// 由写操作前后的记录生成审计条目：两者都存在时只保留变化的字段
+    pub fn new<T: Serialize>(
+        chain_id: Option<i64>,
+        resource: &'static str,
+        resource_id: String,
+        action: &'static str,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Self {
+        let before = before.and_then(|v| serde_json::to_value(v).ok());
+        let after = after.and_then(|v| serde_json::to_value(v).ok());
+        let (before, after) = match (before, after) {
+            (Some(b), Some(a)) => {
+                let (b, a) = json_diff(&b, &a);
+                (Some(b), Some(a))
+            }
+            other => other,
+        };
+        Self {
+            chain_id,
+            resource,
+            resource_id,
+            action,
+            before,
+            after,
+        }
+    }
+}
+
+/// 比较两个 JSON 对象，返回 (变化前, 变化后) 中不同的顶层字段；非对象整体比较
+pub fn json_diff(before: &Value, after: &Value) -> (Value, Value) {
+    let (Value::Object(b), Value::Object(a)) = (before, after) else {
+        return (before.clone(), after.clone());
+    };
+    let mut old = Map::new();
+    let mut new = Map::new();
+    for (k, v) in b {
+        match a.get(k) {
+            Some(av) if av == v => {}
+            Some(av) => {
+                old.insert(k.clone(), v.clone());
+                new.insert(k.clone(), av.clone());
+            }
+            None => {
+                old.insert(k.clone(), v.clone());
+            }
+        }
+    }
+    for (k, v) in a {
+        if !b.contains_key(k) {
+            new.insert(k.clone(), v.clone());
+        }
+    }
+    (Value::Object(old), Value::Object(new))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub resource: Option<String>,
+    pub resource_id: Option<String>,
+    pub actor: Option<String>,
+}
+
+/// 审计日志的 keyset 游标：按 id 倒序
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditCursor {
+    pub id: i64,
+}
+
+/// 会写审计日志的资源类型
+pub const AUDIT_RESOURCES: &[&str] = &["show", "show_job"];
+
+const AUDIT_COLUMNS: &str = "id, chain_id, resource, resource_id, action, actor, request_id, before, after, created_at";
+
+/// 在写操作所在的事务中追加审计记录，保证两者同时提交
+pub async fn insert_audit_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    ctx: &AuditContext,
+    entry: &NewAuditEntry,
+) -> Result<()> {
+    let query = sqlx::query(
+        r#"
+        INSERT INTO audit_log (chain_id, resource, resource_id, action, actor, request_id, before, after)
+        VALUES ($1, $2, $3, $4, lower($5), $6, $7, $8);
+        "#,
+    )
+    .bind(entry.chain_id)
+    .bind(entry.resource)
+    .bind(&entry.resource_id)
+    .bind(entry.action)
+    .bind(&ctx.actor)
+    .bind(&ctx.request_id)
+    .bind(&entry.before)
+    .bind(&entry.after);
+    tx.execute(query).await?;
+    tracing::debug!(
+        resource = entry.resource,
+        resource_id = %entry.resource_id,
+        action = entry.action,
+        "Inserted audit_log (tx)"
+    );
+    Ok(())
+}
+
+/// 构造审计日志查询（单独暴露便于在无数据库时检查 SQL）
+pub fn build_audit_list<'a>(
+    filter: &'a AuditFilter,
+    after: Option<&AuditCursor>,
+    limit: i64,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb = QueryBuilder::new(format!(
+        "SELECT {AUDIT_COLUMNS} FROM audit_log WHERE TRUE"
+    ));
+    if let Some(resource) = &filter.resource {
+        qb.push(" AND resource = ").push_bind(resource);
+    }
+    if let Some(id) = &filter.resource_id {
+        qb.push(" AND resource_id = ").push_bind(id);
+    }
+    if let Some(actor) = &filter.actor {
+        qb.push(" AND actor = lower(").push_bind(actor).push(")");
+    }
+    if let Some(c) = after {
+        qb.push(" AND id < ").push_bind(c.id);
+    }
+    qb.push(" ORDER BY id DESC LIMIT ").push_bind(limit);
+    qb
+}
+
+pub async fn list_audit(
+    pool: &PgPool,
+    filter: &AuditFilter,
+    after: Option<&AuditCursor>,
+    limit: i64,
+) -> Result<Vec<AuditRecord>> {
+    let recs = build_audit_list(filter, after, limit)
+        .build_query_as::<AuditRecord>()
+        .fetch_all(pool)
+        .await?;
+    tracing::debug!(count = recs.len(), "Listed audit_log");
+    Ok(recs)
+}
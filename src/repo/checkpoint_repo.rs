@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::Serialize;
+use sqlx::{PgPool, prelude::FromRow};
+
+/// indexer_checkpoints 表：每条链上每个合约最后成功处理的日志位置
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CheckpointRecord {
+    pub chain_id: i64,
+    pub contract: String,
+    pub address: String,
+    pub last_block: i64,
+    /// last_block 中最后处理的日志；None（旧数据）表示整个区块已处理完
+    pub last_log_index: Option<i64>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 推进某合约的检查点到 (block, log_index)；同一地址只前进不后退，地址变化（重新部署）时直接覆盖。
+pub async fn record_checkpoint(
+    pool: &PgPool,
+    chain_id: i64,
+    contract: &str,
+    address: &str,
+    block: i64,
+    log_index: i64,
+) -> Result<()> {
+    // 旧数据的 last_log_index 为 NULL（整块已处理），比较时视为区块内最大位置
+    sqlx::query(
+        r#"
+        INSERT INTO indexer_checkpoints (chain_id, contract, address, last_block, last_log_index)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (chain_id, contract) DO UPDATE
+        SET address = EXCLUDED.address,
+            last_block = EXCLUDED.last_block,
+            last_log_index = EXCLUDED.last_log_index,
+            updated_at = NOW()
+        WHERE indexer_checkpoints.address <> EXCLUDED.address
+           OR (EXCLUDED.last_block, EXCLUDED.last_log_index)
+              > (indexer_checkpoints.last_block,
+                 COALESCE(indexer_checkpoints.last_log_index, 9223372036854775807));
+        "#,
+    )
+    .bind(chain_id)
+    .bind(contract)
+    .bind(address)
+    .bind(block)
+    .bind(log_index)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn list_checkpoints(pool: &PgPool) -> Result<Vec<CheckpointRecord>> {
+    let recs = sqlx::query_as::<_, CheckpointRecord>(
+        "SELECT chain_id, contract, address, last_block, last_log_index, updated_at FROM indexer_checkpoints ORDER BY chain_id, contract;",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
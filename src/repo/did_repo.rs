@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::Serialize;
+use sqlx::{PgPool, prelude::FromRow};
+
+/// dids 表：DID 当前状态（由 DIDRegistry 事件投影）
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct DidRecord {
+    pub chain_id: i64,
+    /// keccak256(did)，0x 前缀 hex
+    pub did_hash: String,
+    pub did: String,
+    pub controller: String,
+    pub cid: String,
+    pub verified: bool,
+    pub revoked: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 按地址批量查询时的结果行：绑定地址 + DID
+#[derive(Debug, Clone, FromRow)]
+pub struct AddressDidRecord {
+    pub address: String,
+    #[sqlx(flatten)]
+    pub did: DidRecord,
+}
+
+const DID_COLUMNS: &str = "chain_id, did_hash, did, controller, cid, verified, revoked, created_at, updated_at";
+
+/// DIDRegistered：写入 DID，并把 controller 绑定到该 DID（重放安全）。
+pub async fn upsert_did(
+    pool: &PgPool,
+    chain_id: i64,
+    did_hash: &str,
+    did: &str,
+    controller: &str,
+    cid: &str,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        INSERT INTO dids (chain_id, did_hash, did, controller, cid)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (chain_id, did_hash) DO UPDATE
+        SET did = EXCLUDED.did, controller = EXCLUDED.controller, cid = EXCLUDED.cid, updated_at = NOW();
+        "#,
+    )
+    .bind(chain_id)
+    .bind(did_hash)
+    .bind(did)
+    .bind(controller)
+    .bind(cid)
+    .execute(&mut *tx)
+    .await?;
+    bind_address_tx(&mut tx, chain_id, did_hash, controller).await?;
+    tx.commit().await?;
+    tracing::debug!(did_hash, "Inserted/Updated dids");
+    Ok(())
+}
+
+async fn bind_address_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    chain_id: i64,
+    did_hash: &str,
+    address: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO did_addresses (chain_id, address, did_hash)
+        VALUES ($1, lower($2), $3)
+        ON CONFLICT (chain_id, address) DO UPDATE SET did_hash = EXCLUDED.did_hash;
+        "#,
+    )
+    .bind(chain_id)
+    .bind(address)
+    .bind(did_hash)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// DIDBoundToAddress
+pub async fn bind_did_address(
+    pool: &PgPool,
+    chain_id: i64,
+    did_hash: &str,
+    address: &str,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    bind_address_tx(&mut tx, chain_id, did_hash, address).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// DIDUnboundFromAddress
+pub async fn unbind_did_address(
+    pool: &PgPool,
+    chain_id: i64,
+    did_hash: &str,
+    address: &str,
+) -> Result<()> {
+    sqlx::query(
+        "DELETE FROM did_addresses WHERE chain_id = $1 AND address = lower($2) AND did_hash = $3;",
+    )
+    .bind(chain_id)
+    .bind(address)
+    .bind(did_hash)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 局部更新（cid / verified / revoked / controller）；未知 DID 时返回 false。
+#[derive(Debug, Clone, Default)]
+pub struct DidUpdate {
+    pub cid: Option<String>,
+    pub verified: Option<bool>,
+    pub revoked: Option<bool>,
+    pub controller: Option<String>,
+}
+
+pub async fn update_did(
+    pool: &PgPool,
+    chain_id: i64,
+    did_hash: &str,
+    update: &DidUpdate,
+) -> Result<bool> {
+    let res = sqlx::query(
+        r#"
+        UPDATE dids
+        SET cid = COALESCE($3, cid),
+            verified = COALESCE($4, verified),
+            revoked = COALESCE($5, revoked),
+            controller = COALESCE($6, controller),
+            updated_at = NOW()
+        WHERE chain_id = $1 AND did_hash = $2;
+        "#,
+    )
+    .bind(chain_id)
+    .bind(did_hash)
+    .bind(&update.cid)
+    .bind(update.verified)
+    .bind(update.revoked)
+    .bind(&update.controller)
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected() > 0)
+}
+
+pub async fn get_dids_by_hashes(
+    pool: &PgPool,
+    chain_id: i64,
+    hashes: &[String],
+) -> Result<Vec<DidRecord>> {
+    let recs = sqlx::query_as::<_, DidRecord>(&format!(
+        "SELECT {DID_COLUMNS} FROM dids WHERE chain_id = $1 AND did_hash = ANY($2);"
+    ))
+    .bind(chain_id)
+    .bind(hashes)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
+
+/// 按地址批量解析 DID（地址不区分大小写，返回行中的 address 为小写）
+pub async fn get_dids_by_addresses(
+    pool: &PgPool,
+    chain_id: i64,
+    addresses: &[String],
+) -> Result<Vec<AddressDidRecord>> {
+    let lowered: Vec<String> =
+        addresses.iter().map(|a| a.to_lowercase()).collect();
+    let recs = sqlx::query_as::<_, AddressDidRecord>(
+        r#"
+        SELECT a.address, d.chain_id, d.did_hash, d.did, d.controller, d.cid, d.verified, d.revoked, d.created_at, d.updated_at
+        FROM did_addresses a JOIN dids d ON d.chain_id = a.chain_id AND d.did_hash = a.did_hash
+        WHERE a.chain_id = $1 AND a.address = ANY($2);
+        "#,
+    )
+    .bind(chain_id)
+    .bind(&lowered)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
@@ -0,0 +1,263 @@
+use crate::utils::uint256::DbU256;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, QueryBuilder, prelude::FromRow};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    ToSchema,
+)]
+#[sqlx(type_name = "listing_status", rename_all = "UPPERCASE")]
+#[serde(rename_all = "snake_case")]
+pub enum ListingStatus {
+    Active,
+    Sold,
+    Cancelled,
+}
+
+/// listings 表：二级市场挂单的当前状态
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ListingRecord {
+    pub chain_id: i64,
+    pub listing_id: DbU256,
+    pub token_id: DbU256,
+    pub seller: String,
+    pub price: DbU256,
+    pub eth_price: DbU256,
+    pub expires_at: DbU256,
+    pub status: ListingStatus,
+    pub buyer: Option<String>,
+    pub tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewListing {
+    pub chain_id: i64,
+    pub listing_id: DbU256,
+    pub token_id: DbU256,
+    pub seller: String,
+    pub price: DbU256,
+    pub eth_price: DbU256,
+    pub expires_at: DbU256,
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ListingFilter {
+    pub chain_id: Option<i64>,
+    pub status: Option<ListingStatus>,
+    pub seller: Option<String>,
+    pub token_id: Option<DbU256>,
+}
+
+/// listings 的 keyset 游标：按 listing_id 升序
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListingCursor {
+    pub listing_id: DbU256,
+}
+
+const LISTING_COLUMNS: &str = "chain_id, listing_id, token_id, seller, price, eth_price, expires_at, status, buyer, tx_hash, created_at, updated_at";
+
+/// ListingCreated：插入或以链上值覆盖（重放安全），返回最新行。
+pub async fn upsert_listing(
+    pool: &PgPool,
+    rec: &NewListing,
+) -> Result<ListingRecord> {
+    let row = sqlx::query_as::<_, ListingRecord>(&format!(
+        r#"
+        INSERT INTO listings (chain_id, listing_id, token_id, seller, price, eth_price, expires_at, status, tx_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, 'ACTIVE', $8)
+        ON CONFLICT (chain_id, listing_id) DO UPDATE
+        SET token_id = EXCLUDED.token_id,
+            seller = EXCLUDED.seller,
+            price = EXCLUDED.price,
+            eth_price = EXCLUDED.eth_price,
+            expires_at = EXCLUDED.expires_at,
+            tx_hash = EXCLUDED.tx_hash,
+            updated_at = NOW()
+        RETURNING {LISTING_COLUMNS};
+        "#
+    ))
+    .bind(rec.chain_id)
+    .bind(rec.listing_id.clone())
+    .bind(rec.token_id.clone())
+    .bind(&rec.seller)
+    .bind(rec.price.clone())
+    .bind(rec.eth_price.clone())
+    .bind(rec.expires_at.clone())
+    .bind(&rec.tx_hash)
+    .fetch_one(pool)
+    .await?;
+    tracing::debug!(listing_id = %row.listing_id, "Inserted/Updated listings");
+    Ok(row)
+}
+
+/// ListingUpdated：更新价格与过期时间；挂单不存在时返回 None。
+pub async fn update_listing_price(
+    pool: &PgPool,
+    chain_id: i64,
+    listing_id: DbU256,
+    price: DbU256,
+    eth_price: DbU256,
+    expires_at: DbU256,
+) -> Result<Option<ListingRecord>> {
+    let row = sqlx::query_as::<_, ListingRecord>(&format!(
+        r#"
+        UPDATE listings
+        SET price = $3, eth_price = $4, expires_at = $5, updated_at = NOW()
+        WHERE chain_id = $1 AND listing_id = $2
+        RETURNING {LISTING_COLUMNS};
+        "#
+    ))
+    .bind(chain_id)
+    .bind(listing_id)
+    .bind(price)
+    .bind(eth_price)
+    .bind(expires_at)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row)
+}
+
+/// ListingCancelled / TicketSold：变更状态（成交时记录买家）。
+pub async fn set_listing_status(
+    pool: &PgPool,
+    chain_id: i64,
+    listing_id: DbU256,
+    status: ListingStatus,
+    buyer: Option<&str>,
+) -> Result<Option<ListingRecord>> {
+    let row = sqlx::query_as::<_, ListingRecord>(&format!(
+        r#"
+        UPDATE listings
+        SET status = $3, buyer = COALESCE($4, buyer), updated_at = NOW()
+        WHERE chain_id = $1 AND listing_id = $2
+        RETURNING {LISTING_COLUMNS};
+        "#
+    ))
+    .bind(chain_id)
+    .bind(listing_id)
+    .bind(status)
+    .bind(buyer)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row)
+}
+
+pub async fn get_listing_by_id(
+    pool: &PgPool,
+    chain_id: i64,
+    listing_id: DbU256,
+) -> Result<Option<ListingRecord>> {
+    let row = sqlx::query_as::<_, ListingRecord>(&format!(
+        "SELECT {LISTING_COLUMNS} FROM listings WHERE chain_id = $1 AND listing_id = $2;"
+    ))
+    .bind(chain_id)
+    .bind(listing_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row)
+}
+
+/// 构造挂单列表查询（listing_id 升序 keyset 分页）。
+pub fn build_listing_list<'a>(
+    filter: &'a ListingFilter,
+    after: Option<&ListingCursor>,
+    limit: i64,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb = QueryBuilder::new(format!(
+        "SELECT {LISTING_COLUMNS} FROM listings WHERE TRUE"
+    ));
+    if let Some(chain_id) = filter.chain_id {
+        qb.push(" AND chain_id = ").push_bind(chain_id);
+    }
+    if let Some(status) = filter.status {
+        qb.push(" AND status = ").push_bind(status);
+    }
+    if let Some(seller) = &filter.seller {
+        qb.push(" AND lower(seller) = lower(")
+            .push_bind(seller)
+            .push(")");
+    }
+    if let Some(token_id) = &filter.token_id {
+        qb.push(" AND token_id = ").push_bind(token_id.clone());
+    }
+    if let Some(c) = after {
+        qb.push(" AND listing_id > ")
+            .push_bind(c.listing_id.clone());
+    }
+    qb.push(" ORDER BY listing_id ASC LIMIT ").push_bind(limit);
+    qb
+}
+
+pub async fn list_listings(
+    pool: &PgPool,
+    filter: &ListingFilter,
+    after: Option<&ListingCursor>,
+    limit: i64,
+) -> Result<Vec<ListingRecord>> {
+    let recs = build_listing_list(filter, after, limit)
+        .build_query_as::<ListingRecord>()
+        .fetch_all(pool)
+        .await?;
+    tracing::debug!(count = recs.len(), ?filter, "Listed listings");
+    Ok(recs)
+}
+
+/// 批量查询若干票当前 ACTIVE 的挂单（每张票至多一条有效挂单，取最新）。
+pub async fn get_active_listings_by_tokens(
+    pool: &PgPool,
+    chain_id: i64,
+    token_ids: &[DbU256],
+) -> Result<Vec<ListingRecord>> {
+    let recs = sqlx::query_as::<_, ListingRecord>(&format!(
+        r#"
+        SELECT DISTINCT ON (token_id) {LISTING_COLUMNS} FROM listings
+        WHERE chain_id = $1 AND token_id = ANY($2) AND status = 'ACTIVE'
+        ORDER BY token_id, listing_id DESC;
+        "#
+    ))
+    .bind(chain_id)
+    .bind(token_ids)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
+
+/// 每个卖家取前 limit 条挂单（listing_id 升序）；地址不区分大小写。
+pub async fn list_listings_for_sellers(
+    pool: &PgPool,
+    chain_id: i64,
+    sellers: &[String],
+    limit: i64,
+) -> Result<Vec<ListingRecord>> {
+    let lowered: Vec<String> =
+        sellers.iter().map(|s| s.to_lowercase()).collect();
+    let recs = sqlx::query_as::<_, ListingRecord>(&format!(
+        r#"
+        SELECT {LISTING_COLUMNS} FROM (
+            SELECT *, row_number() OVER (PARTITION BY lower(seller) ORDER BY listing_id) AS rn
+            FROM listings WHERE chain_id = $1 AND lower(seller) = ANY($2)
+        ) l
+        WHERE rn <= $3
+        ORDER BY lower(seller), listing_id;
+        "#
+    ))
+    .bind(chain_id)
+    .bind(&lowered)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
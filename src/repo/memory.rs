@@ -0,0 +1,1324 @@
+//! [`Repos`](super::traits::Repos) 的进程内实现，供测试在没有 Postgres 时驱动 handler、worker 与索引器。
+//! 保存 shows / tickets / transfers / listings / DID 投影、检查点、createShow 任务、webhook 订阅与投递、
+//! 审计记录（创建事件只保留交易哈希，不保存详情表），过滤、排序与分页语义与 Postgres 查询一致；
+//! 全文检索 `q` 近似为逐词不区分大小写的子串匹配，不计算相关度。
+use crate::repo::{
+    audit_repo::{
+        AuditContext, AuditCursor, AuditFilter, AuditRecord, NewAuditEntry,
+    },
+    checkpoint_repo::CheckpointRecord,
+    did_repo::{AddressDidRecord, DidRecord, DidUpdate},
+    listing_repo::{
+        ListingCursor, ListingFilter, ListingRecord, ListingStatus, NewListing,
+    },
+    show_job_repo::{NewShowCreateJob, ShowCreateJobRecord, ShowJobStatus},
+    show_repo::{
+        ShowCreatedDetailRecord, ShowCreatedRecord, ShowCursor, ShowCursorKey,
+        ShowDataRecord, ShowFilter, ShowPatch, ShowPatchOutcome, ShowSortKey,
+        SortOrder,
+    },
+    ticket_repo::{
+        NewTicket, NewTicketTransfer, TicketCursor, TicketFilter, TicketRecord,
+        TicketStatus, TicketTransferRecord, TransferCursor, TransferFilter,
+    },
+    traits::{
+        AuditRepo, DidRepo, EventRepo, ListingRepo, ShowJobRepo, ShowRepo,
+        TicketRepo, WebhookRepo,
+    },
+    webhook_repo::{
+        DeliveryCursor, DeliveryFilter, DeliveryStatus, DueDelivery,
+        NewWebhook, WebhookDeliveryRecord, WebhookRecord,
+    },
+};
+use crate::utils::uint256::{DbU256, U256};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+    sync::{Mutex, MutexGuard},
+};
+
+#[derive(Debug, Default)]
+pub struct MemoryRepo {
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    /// (chain_id, id) -> (记录, 是否已软删除)
+    shows: BTreeMap<(i64, DbU256), (ShowDataRecord, bool)>,
+    tickets: BTreeMap<(i64, DbU256), TicketRecord>,
+    transfers: Vec<TicketTransferRecord>,
+    /// ShowCreated 的交易哈希 -> show id（代替 show_created_events 表）
+    created_txs: BTreeMap<String, DbU256>,
+    listings: BTreeMap<(i64, DbU256), ListingRecord>,
+    dids: BTreeMap<(i64, String), DidRecord>,
+    /// (chain_id, 小写地址) -> did_hash
+    did_addresses: BTreeMap<(i64, String), String>,
+    checkpoints: BTreeMap<(i64, String), CheckpointRecord>,
+    webhook_events: Vec<(String, serde_json::Value)>,
+    jobs: BTreeMap<i64, ShowCreateJobRecord>,
+    webhooks: BTreeMap<i64, WebhookRecord>,
+    deliveries: BTreeMap<i64, WebhookDeliveryRecord>,
+    /// 与数据库序列一致：id 不复用
+    last_id: i64,
+    audit: Vec<AuditRecord>,
+}
+
+impl MemoryRepo {
+    fn state(&self) -> MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 直接写入一条 show（准备测试数据），version 为 0 时按新行处理为 1
+    pub fn put_show(&self, mut rec: ShowDataRecord) {
+        rec.version = rec.version.max(1);
+        self.state()
+            .shows
+            .insert((rec.chain_id, rec.id.clone()), (rec, false));
+    }
+
+    /// 已排队的 webhook 事件：(event_type, payload)，按写入顺序
+    pub fn webhook_events(&self) -> Vec<(String, serde_json::Value)> {
+        self.state().webhook_events.clone()
+    }
+
+    /// 审计记录，按写入顺序
+    pub fn audit_log(&self) -> Vec<AuditRecord> {
+        self.state().audit.clone()
+    }
+
+    /// 软删除的演出仍保留在内存中，便于检查删除结果
+    pub fn is_deleted(&self, chain_id: i64, id: &DbU256) -> bool {
+        self.state()
+            .shows
+            .get(&(chain_id, id.clone()))
+            .is_some_and(|(_, deleted)| *deleted)
+    }
+}
+
+impl MemoryState {
+    fn next_id(&mut self) -> i64 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    /// 与 Postgres 一致：sold_tickets 只在首次插入时按已有的票计数，之后不被快照覆盖
+    fn upsert_show(&mut self, data: &ShowDataRecord) -> ShowDataRecord {
+        let key = (data.chain_id, data.id.clone());
+        match self.shows.get_mut(&key) {
+            Some((rec, _)) => {
+                *rec = ShowDataRecord {
+                    sold_tickets: rec.sold_tickets.clone(),
+                    created_at: rec.created_at,
+                    version: rec.version + 1,
+                    ..data.clone()
+                };
+                rec.clone()
+            }
+            None => {
+                let sold = self
+                    .tickets
+                    .values()
+                    .filter(|t| {
+                        t.chain_id == data.chain_id && t.event_id == data.id
+                    })
+                    .count();
+                let rec = ShowDataRecord {
+                    sold_tickets: DbU256::from(sold as u64),
+                    created_at: Utc::now(),
+                    version: 1,
+                    ..data.clone()
+                };
+                self.shows.insert(key, (rec.clone(), false));
+                rec
+            }
+        }
+    }
+
+    fn audit(&mut self, ctx: &AuditContext, entry: NewAuditEntry) {
+        let rec = AuditRecord {
+            id: self.audit.len() as i64 + 1,
+            chain_id: entry.chain_id,
+            resource: entry.resource.to_string(),
+            resource_id: entry.resource_id,
+            action: entry.action.to_string(),
+            actor: ctx.actor.as_ref().map(|a| a.to_lowercase()),
+            request_id: ctx.request_id.clone(),
+            before: entry.before,
+            after: entry.after,
+            created_at: Utc::now(),
+        };
+        self.audit.push(rec);
+    }
+}
+
+fn contains_ci(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn show_matches(rec: &ShowDataRecord, f: &ShowFilter) -> bool {
+    f.chain_id.is_none_or(|c| rec.chain_id == c)
+        && f.is_active.is_none_or(|a| rec.is_active == a)
+        && f.organizer
+            .as_ref()
+            .is_none_or(|o| rec.organizer.eq_ignore_ascii_case(o))
+        && f.event_time_from
+            .as_ref()
+            .is_none_or(|t| &rec.event_time >= t)
+        && f.event_time_to
+            .as_ref()
+            .is_none_or(|t| &rec.event_time <= t)
+        && f.location
+            .as_ref()
+            .is_none_or(|l| contains_ci(&rec.location, l))
+        && f.min_price.as_ref().is_none_or(|p| &rec.ticket_price >= p)
+        && f.max_price.as_ref().is_none_or(|p| &rec.ticket_price <= p)
+        && f.q.as_ref().is_none_or(|q| {
+            q.split_whitespace().all(|term| {
+                contains_ci(&rec.name, term)
+                    || contains_ci(&rec.description, term)
+                    || contains_ci(&rec.location, term)
+            })
+        })
+}
+
+/// 排序列的值；CreatedAt 与数值列不会在同一次比较中混用
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue<'a> {
+    Time(DateTime<Utc>),
+    Num(&'a DbU256),
+}
+
+fn sort_value(
+    rec: &ShowDataRecord,
+    sort: Option<ShowSortKey>,
+) -> SortValue<'_> {
+    match sort {
+        None => SortValue::Time(rec.created_at),
+        Some(ShowSortKey::EventTime) => SortValue::Num(&rec.event_time),
+        Some(ShowSortKey::Price) => SortValue::Num(&rec.ticket_price),
+        Some(ShowSortKey::Sold) => SortValue::Num(&rec.sold_tickets),
+    }
+}
+
+fn cursor_value(c: &ShowCursor) -> (Option<ShowSortKey>, SortValue<'_>) {
+    match &c.key {
+        ShowCursorKey::CreatedAt(ts) => (None, SortValue::Time(*ts)),
+        ShowCursorKey::EventTime(v) => {
+            (Some(ShowSortKey::EventTime), SortValue::Num(v))
+        }
+        ShowCursorKey::Price(v) => {
+            (Some(ShowSortKey::Price), SortValue::Num(v))
+        }
+        ShowCursorKey::Sold(v) => (Some(ShowSortKey::Sold), SortValue::Num(v)),
+    }
+}
+
+fn page<T>(items: impl Iterator<Item = T>, offset: i64, limit: i64) -> Vec<T> {
+    items
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
+
+#[async_trait]
+impl ShowRepo for MemoryRepo {
+    async fn upsert_show_all(
+        &self,
+        basic: &ShowCreatedRecord,
+        _detail: &ShowCreatedDetailRecord,
+        data: &ShowDataRecord,
+    ) -> Result<ShowDataRecord> {
+        let mut state = self.state();
+        if let Some(h) = &basic.tx_hash {
+            state.created_txs.insert(h.clone(), basic.show_id.clone());
+        }
+        Ok(state.upsert_show(data))
+    }
+
+    async fn upsert_show_snapshot(
+        &self,
+        _detail: &ShowCreatedDetailRecord,
+        data: &ShowDataRecord,
+    ) -> Result<ShowDataRecord> {
+        Ok(self.state().upsert_show(data))
+    }
+
+    async fn get_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+    ) -> Result<Option<ShowDataRecord>> {
+        Ok(self
+            .state()
+            .shows
+            .get(&(chain_id, id.clone()))
+            .filter(|(_, deleted)| !deleted)
+            .map(|(rec, _)| rec.clone()))
+    }
+
+    async fn search_shows(
+        &self,
+        filter: &ShowFilter,
+        sort: Option<ShowSortKey>,
+        order: SortOrder,
+        after: Option<&ShowCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ShowDataRecord>> {
+        let state = self.state();
+        let directed = |o: Ordering| match order {
+            SortOrder::Asc => o,
+            SortOrder::Desc => o.reverse(),
+        };
+        let mut rows: Vec<&ShowDataRecord> = state
+            .shows
+            .values()
+            .filter(|(_, deleted)| !deleted)
+            .map(|(rec, _)| rec)
+            .filter(|rec| show_matches(rec, filter))
+            .filter(|rec| {
+                after.is_none_or(|c| {
+                    let (key, value) = cursor_value(c);
+                    let ord =
+                        (sort_value(rec, key), &rec.id).cmp(&(value, &c.id));
+                    directed(ord) == Ordering::Greater
+                })
+            })
+            .collect();
+        if sort.is_none() && filter.q.is_some() {
+            // 无相关度可用：与 Postgres 的次级排序一致
+            rows.sort_by(|a, b| {
+                (b.created_at, &b.id).cmp(&(a.created_at, &a.id))
+            });
+        } else {
+            rows.sort_by(|a, b| {
+                directed(
+                    (sort_value(a, sort), &a.id)
+                        .cmp(&(sort_value(b, sort), &b.id)),
+                )
+            });
+        }
+        let offset = if after.is_some() { 0 } else { offset };
+        Ok(page(rows.into_iter().cloned(), offset, limit))
+    }
+
+    async fn update_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+        patch: &ShowPatch,
+        if_version: Option<&[i64]>,
+        audit: &AuditContext,
+    ) -> Result<ShowPatchOutcome> {
+        let mut state = self.state();
+        let Some(current) = state
+            .shows
+            .get(&(chain_id, id.clone()))
+            .filter(|(_, deleted)| !deleted)
+            .map(|(rec, _)| rec.clone())
+        else {
+            return Ok(ShowPatchOutcome::NotFound);
+        };
+        let version_ok =
+            if_version.is_none_or(|vs| vs.contains(&current.version));
+        if patch.is_empty() {
+            return Ok(if version_ok {
+                ShowPatchOutcome::Updated(current)
+            } else {
+                ShowPatchOutcome::Rejected(current)
+            });
+        }
+        let sold_ok = patch
+            .max_tickets
+            .as_ref()
+            .is_none_or(|max| current.sold_tickets <= *max);
+        if !version_ok || !sold_ok {
+            return Ok(ShowPatchOutcome::Rejected(current));
+        }
+        let mut updated = current.clone();
+        if let Some(v) = &patch.name {
+            updated.name = v.clone();
+        }
+        if let Some(v) = &patch.description {
+            updated.description = v.clone();
+        }
+        if let Some(v) = &patch.location {
+            updated.location = v.clone();
+        }
+        if let Some(v) = &patch.event_time {
+            updated.event_time = v.clone();
+        }
+        if let Some(v) = &patch.ticket_price {
+            updated.ticket_price = v.clone();
+        }
+        if let Some(v) = &patch.max_tickets {
+            updated.max_tickets = v.clone();
+        }
+        if let Some(v) = patch.is_active {
+            updated.is_active = v;
+        }
+        updated.version += 1;
+        state
+            .shows
+            .insert((chain_id, id.clone()), (updated.clone(), false));
+        let entry = NewAuditEntry::new(
+            Some(chain_id),
+            "show",
+            id.to_string(),
+            "update",
+            Some(&current),
+            Some(&updated),
+        );
+        state.audit(audit, entry);
+        Ok(ShowPatchOutcome::Updated(updated))
+    }
+
+    async fn delete_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+        audit: &AuditContext,
+    ) -> Result<Option<ShowDataRecord>> {
+        let mut state = self.state();
+        let Some((rec, deleted)) = state.shows.get_mut(&(chain_id, id.clone()))
+        else {
+            return Ok(None);
+        };
+        if *deleted {
+            return Ok(None);
+        }
+        *deleted = true;
+        rec.version += 1;
+        let rec = rec.clone();
+        let entry = NewAuditEntry::new(
+            Some(chain_id),
+            "show",
+            id.to_string(),
+            "delete",
+            Some(&rec),
+            None,
+        );
+        state.audit(audit, entry);
+        Ok(Some(rec))
+    }
+
+    async fn get_shows_by_ids(
+        &self,
+        chain_id: i64,
+        ids: &[DbU256],
+    ) -> Result<Vec<ShowDataRecord>> {
+        let state = self.state();
+        Ok(ids
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter_map(|id| state.shows.get(&(chain_id, id.clone())))
+            .filter(|(_, deleted)| !deleted)
+            .map(|(rec, _)| rec.clone())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TicketRepo for MemoryRepo {
+    async fn upsert_minted_ticket(&self, rec: &NewTicket) -> Result<bool> {
+        let mut state = self.state();
+        let now = Utc::now();
+        let key = (rec.chain_id, rec.token_id.clone());
+        if let Some(t) = state.tickets.get_mut(&key) {
+            t.event_id = rec.event_id.clone();
+            t.seat_number = rec.seat_number.clone();
+            t.price = rec.price.clone();
+            t.minted_tx_hash = rec.minted_tx_hash.clone();
+            t.updated_at = now;
+            return Ok(false);
+        }
+        state.tickets.insert(
+            key,
+            TicketRecord {
+                chain_id: rec.chain_id,
+                token_id: rec.token_id.clone(),
+                event_id: rec.event_id.clone(),
+                owner: rec.owner.clone(),
+                seat_number: rec.seat_number.clone(),
+                price: rec.price.clone(),
+                status: TicketStatus::Valid,
+                minted_tx_hash: rec.minted_tx_hash.clone(),
+                created_at: now,
+                updated_at: now,
+            },
+        );
+        if let Some((show, _)) =
+            state.shows.get_mut(&(rec.chain_id, rec.event_id.clone()))
+        {
+            show.sold_tickets = DbU256(show.sold_tickets.0 + U256::from(1u64));
+        }
+        Ok(true)
+    }
+
+    async fn record_transfer(&self, rec: &NewTicketTransfer) -> Result<()> {
+        let mut state = self.state();
+        let duplicate = state.transfers.iter().any(|t| {
+            t.chain_id == rec.chain_id
+                && t.tx_hash == rec.tx_hash
+                && t.log_index == rec.log_index
+        });
+        if duplicate {
+            return Ok(());
+        }
+        let now = Utc::now();
+        state.transfers.push(TicketTransferRecord {
+            chain_id: rec.chain_id,
+            tx_hash: rec.tx_hash.clone(),
+            log_index: rec.log_index,
+            block_number: rec.block_number,
+            token_id: rec.token_id.clone(),
+            from_address: rec.from_address.clone(),
+            to_address: rec.to_address.clone(),
+            created_at: now,
+        });
+        if let Some(t) =
+            state.tickets.get_mut(&(rec.chain_id, rec.token_id.clone()))
+        {
+            t.owner = rec.to_address.clone();
+            t.updated_at = now;
+        }
+        Ok(())
+    }
+
+    async fn update_status(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+        status: TicketStatus,
+    ) -> Result<bool> {
+        let mut state = self.state();
+        let Some(t) = state.tickets.get_mut(&(chain_id, token_id.clone()))
+        else {
+            return Ok(false);
+        };
+        t.status = status;
+        t.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    async fn get_ticket(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+    ) -> Result<Option<TicketRecord>> {
+        Ok(self
+            .state()
+            .tickets
+            .get(&(chain_id, token_id.clone()))
+            .cloned())
+    }
+
+    async fn list_tickets(
+        &self,
+        filter: &TicketFilter,
+        after: Option<&TicketCursor>,
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>> {
+        let state = self.state();
+        let mut rows: Vec<&TicketRecord> = state
+            .tickets
+            .values()
+            .filter(|t| filter.chain_id.is_none_or(|c| t.chain_id == c))
+            .filter(|t| {
+                filter
+                    .owner
+                    .as_ref()
+                    .is_none_or(|o| t.owner.eq_ignore_ascii_case(o))
+            })
+            .filter(|t| {
+                filter.event_id.as_ref().is_none_or(|e| &t.event_id == e)
+            })
+            .filter(|t| filter.status.is_none_or(|s| t.status == s))
+            .filter(|t| after.is_none_or(|c| t.token_id > c.token_id))
+            .collect();
+        rows.sort_by(|a, b| {
+            (&a.token_id, a.chain_id).cmp(&(&b.token_id, b.chain_id))
+        });
+        Ok(page(rows.into_iter().cloned(), 0, limit))
+    }
+
+    async fn list_transfers(
+        &self,
+        filter: &TransferFilter,
+        after: Option<&TransferCursor>,
+        limit: i64,
+    ) -> Result<Vec<TicketTransferRecord>> {
+        let state = self.state();
+        let mut rows: Vec<&TicketTransferRecord> = state
+            .transfers
+            .iter()
+            .filter(|t| filter.chain_id.is_none_or(|c| t.chain_id == c))
+            .filter(|t| {
+                filter.token_id.as_ref().is_none_or(|id| &t.token_id == id)
+            })
+            .filter(|t| {
+                filter.address.as_ref().is_none_or(|a| {
+                    t.from_address.eq_ignore_ascii_case(a)
+                        || t.to_address.eq_ignore_ascii_case(a)
+                })
+            })
+            .filter(|t| {
+                after.is_none_or(|c| {
+                    (t.block_number, t.log_index)
+                        < (c.block_number, c.log_index)
+                })
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            (b.block_number, b.log_index).cmp(&(a.block_number, a.log_index))
+        });
+        Ok(page(rows.into_iter().cloned(), 0, limit))
+    }
+
+    async fn get_tickets_by_ids(
+        &self,
+        chain_id: i64,
+        token_ids: &[DbU256],
+    ) -> Result<Vec<TicketRecord>> {
+        let state = self.state();
+        Ok(token_ids
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter_map(|id| state.tickets.get(&(chain_id, id.clone())))
+            .cloned()
+            .collect())
+    }
+
+    async fn list_tickets_for_shows(
+        &self,
+        chain_id: i64,
+        show_ids: &[DbU256],
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>> {
+        let state = self.state();
+        let rows = state.tickets.values().filter(|t| {
+            t.chain_id == chain_id && show_ids.contains(&t.event_id)
+        });
+        Ok(top_per_group(
+            rows,
+            |t| t.event_id.clone(),
+            |t| t.token_id.clone(),
+            limit,
+        ))
+    }
+
+    async fn list_tickets_for_owners(
+        &self,
+        chain_id: i64,
+        owners: &[String],
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>> {
+        let owners: HashSet<String> =
+            owners.iter().map(|o| o.to_lowercase()).collect();
+        let state = self.state();
+        let rows = state.tickets.values().filter(|t| {
+            t.chain_id == chain_id && owners.contains(&t.owner.to_lowercase())
+        });
+        Ok(top_per_group(
+            rows,
+            |t| t.owner.to_lowercase(),
+            |t| t.token_id.clone(),
+            limit,
+        ))
+    }
+}
+
+/// 按 group 分组、组内按 order 升序各取前 limit 条，结果按 (group, order) 排序
+fn top_per_group<'a, T, G, O>(
+    rows: impl Iterator<Item = &'a T>,
+    group: impl Fn(&T) -> G,
+    order: impl Fn(&T) -> O,
+    limit: i64,
+) -> Vec<T>
+where
+    T: Clone + 'a,
+    G: Ord,
+    O: Ord,
+{
+    let mut groups: BTreeMap<G, Vec<&T>> = BTreeMap::new();
+    for row in rows {
+        groups.entry(group(row)).or_default().push(row);
+    }
+    groups
+        .into_values()
+        .flat_map(|mut rows| {
+            rows.sort_by_key(|r| order(r));
+            page(rows.into_iter().cloned(), 0, limit)
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ListingRepo for MemoryRepo {
+    async fn upsert_listing(&self, rec: &NewListing) -> Result<ListingRecord> {
+        let mut state = self.state();
+        let now = Utc::now();
+        let row = state
+            .listings
+            .entry((rec.chain_id, rec.listing_id.clone()))
+            .or_insert_with(|| ListingRecord {
+                chain_id: rec.chain_id,
+                listing_id: rec.listing_id.clone(),
+                token_id: rec.token_id.clone(),
+                seller: rec.seller.clone(),
+                price: rec.price.clone(),
+                eth_price: rec.eth_price.clone(),
+                expires_at: rec.expires_at.clone(),
+                status: ListingStatus::Active,
+                buyer: None,
+                tx_hash: rec.tx_hash.clone(),
+                created_at: now,
+                updated_at: now,
+            });
+        row.token_id = rec.token_id.clone();
+        row.seller = rec.seller.clone();
+        row.price = rec.price.clone();
+        row.eth_price = rec.eth_price.clone();
+        row.expires_at = rec.expires_at.clone();
+        row.tx_hash = rec.tx_hash.clone();
+        row.updated_at = now;
+        Ok(row.clone())
+    }
+
+    async fn update_listing_price(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+        price: &DbU256,
+        eth_price: &DbU256,
+        expires_at: &DbU256,
+    ) -> Result<Option<ListingRecord>> {
+        let mut state = self.state();
+        let Some(row) = state.listings.get_mut(&(chain_id, listing_id.clone()))
+        else {
+            return Ok(None);
+        };
+        row.price = price.clone();
+        row.eth_price = eth_price.clone();
+        row.expires_at = expires_at.clone();
+        row.updated_at = Utc::now();
+        Ok(Some(row.clone()))
+    }
+
+    async fn set_listing_status(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+        status: ListingStatus,
+        buyer: Option<&str>,
+    ) -> Result<Option<ListingRecord>> {
+        let mut state = self.state();
+        let Some(row) = state.listings.get_mut(&(chain_id, listing_id.clone()))
+        else {
+            return Ok(None);
+        };
+        row.status = status;
+        if let Some(b) = buyer {
+            row.buyer = Some(b.to_string());
+        }
+        row.updated_at = Utc::now();
+        Ok(Some(row.clone()))
+    }
+
+    async fn get_listing(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+    ) -> Result<Option<ListingRecord>> {
+        Ok(self
+            .state()
+            .listings
+            .get(&(chain_id, listing_id.clone()))
+            .cloned())
+    }
+
+    async fn list_listings(
+        &self,
+        filter: &ListingFilter,
+        after: Option<&ListingCursor>,
+        limit: i64,
+    ) -> Result<Vec<ListingRecord>> {
+        let state = self.state();
+        let mut rows: Vec<&ListingRecord> = state
+            .listings
+            .values()
+            .filter(|l| filter.chain_id.is_none_or(|c| l.chain_id == c))
+            .filter(|l| filter.status.is_none_or(|s| l.status == s))
+            .filter(|l| {
+                filter
+                    .seller
+                    .as_ref()
+                    .is_none_or(|s| l.seller.eq_ignore_ascii_case(s))
+            })
+            .filter(|l| {
+                filter.token_id.as_ref().is_none_or(|t| &l.token_id == t)
+            })
+            .filter(|l| after.is_none_or(|c| l.listing_id > c.listing_id))
+            .collect();
+        rows.sort_by(|a, b| a.listing_id.cmp(&b.listing_id));
+        Ok(page(rows.into_iter().cloned(), 0, limit))
+    }
+
+    async fn get_active_listings_by_tokens(
+        &self,
+        chain_id: i64,
+        token_ids: &[DbU256],
+    ) -> Result<Vec<ListingRecord>> {
+        let state = self.state();
+        let mut latest: BTreeMap<&DbU256, &ListingRecord> = BTreeMap::new();
+        for l in state.listings.values().filter(|l| {
+            l.chain_id == chain_id
+                && l.status == ListingStatus::Active
+                && token_ids.contains(&l.token_id)
+        }) {
+            let cur = latest.entry(&l.token_id).or_insert(l);
+            if l.listing_id > cur.listing_id {
+                *cur = l;
+            }
+        }
+        Ok(latest.into_values().cloned().collect())
+    }
+
+    async fn list_listings_for_sellers(
+        &self,
+        chain_id: i64,
+        sellers: &[String],
+        limit: i64,
+    ) -> Result<Vec<ListingRecord>> {
+        let sellers: HashSet<String> =
+            sellers.iter().map(|s| s.to_lowercase()).collect();
+        let state = self.state();
+        let rows = state.listings.values().filter(|l| {
+            l.chain_id == chain_id && sellers.contains(&l.seller.to_lowercase())
+        });
+        Ok(top_per_group(
+            rows,
+            |l| l.seller.to_lowercase(),
+            |l| l.listing_id.clone(),
+            limit,
+        ))
+    }
+}
+
+#[async_trait]
+impl DidRepo for MemoryRepo {
+    async fn upsert_did(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        did: &str,
+        controller: &str,
+        cid: &str,
+    ) -> Result<()> {
+        let mut state = self.state();
+        let now = Utc::now();
+        let row = state
+            .dids
+            .entry((chain_id, did_hash.to_string()))
+            .or_insert_with(|| DidRecord {
+                chain_id,
+                did_hash: did_hash.to_string(),
+                did: did.to_string(),
+                controller: controller.to_string(),
+                cid: cid.to_string(),
+                verified: false,
+                revoked: false,
+                created_at: now,
+                updated_at: now,
+            });
+        row.did = did.to_string();
+        row.controller = controller.to_string();
+        row.cid = cid.to_string();
+        row.updated_at = now;
+        state.did_addresses.insert(
+            (chain_id, controller.to_lowercase()),
+            did_hash.to_string(),
+        );
+        Ok(())
+    }
+
+    async fn bind_did_address(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        address: &str,
+    ) -> Result<()> {
+        self.state()
+            .did_addresses
+            .insert((chain_id, address.to_lowercase()), did_hash.to_string());
+        Ok(())
+    }
+
+    async fn unbind_did_address(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        address: &str,
+    ) -> Result<()> {
+        let mut state = self.state();
+        let key = (chain_id, address.to_lowercase());
+        if state.did_addresses.get(&key).is_some_and(|h| h == did_hash) {
+            state.did_addresses.remove(&key);
+        }
+        Ok(())
+    }
+
+    async fn update_did(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        update: &DidUpdate,
+    ) -> Result<bool> {
+        let mut state = self.state();
+        let Some(row) = state.dids.get_mut(&(chain_id, did_hash.to_string()))
+        else {
+            return Ok(false);
+        };
+        if let Some(v) = &update.cid {
+            row.cid = v.clone();
+        }
+        if let Some(v) = update.verified {
+            row.verified = v;
+        }
+        if let Some(v) = update.revoked {
+            row.revoked = v;
+        }
+        if let Some(v) = &update.controller {
+            row.controller = v.clone();
+        }
+        row.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    async fn get_dids_by_addresses(
+        &self,
+        chain_id: i64,
+        addresses: &[String],
+    ) -> Result<Vec<AddressDidRecord>> {
+        let state = self.state();
+        Ok(addresses
+            .iter()
+            .map(|a| a.to_lowercase())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter_map(|address| {
+                let hash =
+                    state.did_addresses.get(&(chain_id, address.clone()))?;
+                let did = state.dids.get(&(chain_id, hash.clone()))?;
+                Some(AddressDidRecord {
+                    address,
+                    did: did.clone(),
+                })
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl EventRepo for MemoryRepo {
+    async fn record_checkpoint(
+        &self,
+        chain_id: i64,
+        contract: &str,
+        address: &str,
+        block: i64,
+        log_index: i64,
+    ) -> Result<()> {
+        let mut state = self.state();
+        let entry = state
+            .checkpoints
+            .entry((chain_id, contract.to_string()))
+            .or_insert_with(|| CheckpointRecord {
+                chain_id,
+                contract: contract.to_string(),
+                address: address.to_string(),
+                last_block: block,
+                last_log_index: Some(log_index),
+                updated_at: Utc::now(),
+            });
+        let current =
+            (entry.last_block, entry.last_log_index.unwrap_or(i64::MAX));
+        if entry.address != address || (block, log_index) > current {
+            entry.address = address.to_string();
+            entry.last_block = block;
+            entry.last_log_index = Some(log_index);
+            entry.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn list_checkpoints(&self) -> Result<Vec<CheckpointRecord>> {
+        Ok(self.state().checkpoints.values().cloned().collect())
+    }
+
+    /// 事件同时记入 [`MemoryRepo::webhook_events`]，便于没有注册订阅的测试检查通知
+    async fn enqueue_webhook_event(
+        &self,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<u64> {
+        let mut state = self.state();
+        state
+            .webhook_events
+            .push((event_type.to_string(), payload.clone()));
+        let targets: Vec<i64> = state
+            .webhooks
+            .values()
+            .filter(|w| {
+                w.active
+                    && (w.event_types.is_empty()
+                        || w.event_types.iter().any(|t| t == event_type))
+            })
+            .map(|w| w.id)
+            .collect();
+        let now = Utc::now();
+        for webhook_id in &targets {
+            let id = state.next_id();
+            state.deliveries.insert(
+                id,
+                WebhookDeliveryRecord {
+                    id,
+                    webhook_id: *webhook_id,
+                    event_type: event_type.to_string(),
+                    payload: payload.clone(),
+                    status: DeliveryStatus::Pending,
+                    attempts: 0,
+                    next_attempt_at: now,
+                    last_status_code: None,
+                    last_error: None,
+                    delivered_at: None,
+                    created_at: now,
+                    updated_at: now,
+                },
+            );
+        }
+        Ok(targets.len() as u64)
+    }
+}
+
+#[async_trait]
+impl ShowJobRepo for MemoryRepo {
+    async fn insert_job(
+        &self,
+        job: &NewShowCreateJob,
+        audit: &AuditContext,
+    ) -> Result<ShowCreateJobRecord> {
+        let mut state = self.state();
+        let now = Utc::now();
+        let rec = ShowCreateJobRecord {
+            id: state.next_id(),
+            signer: job.signer.clone(),
+            organizer: job.organizer.clone(),
+            name: job.name.clone(),
+            description: job.description.clone(),
+            location: job.location.clone(),
+            event_time: job.event_time.clone(),
+            end_time: job.end_time.clone(),
+            ticket_price: job.ticket_price.clone(),
+            max_tickets: job.max_tickets.clone(),
+            metadata_uri: job.metadata_uri.clone(),
+            status: ShowJobStatus::Pending,
+            tx_hash: None,
+            sender: None,
+            nonce: None,
+            show_id: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        state.jobs.insert(rec.id, rec.clone());
+        let entry = NewAuditEntry::new(
+            None,
+            "show_job",
+            rec.id.to_string(),
+            "create",
+            None,
+            Some(&rec),
+        );
+        state.audit(audit, entry);
+        Ok(rec)
+    }
+
+    async fn get_job(&self, id: i64) -> Result<Option<ShowCreateJobRecord>> {
+        Ok(self.state().jobs.get(&id).cloned())
+    }
+
+    async fn list_open_jobs(&self) -> Result<Vec<i64>> {
+        Ok(self
+            .state()
+            .jobs
+            .values()
+            .filter(|j| {
+                matches!(
+                    j.status,
+                    ShowJobStatus::Pending
+                        | ShowJobStatus::Submitting
+                        | ShowJobStatus::Submitted
+                )
+            })
+            .map(|j| j.id)
+            .collect())
+    }
+
+    async fn claim_job(
+        &self,
+        id: i64,
+        tx_hash: &str,
+        sender: &str,
+        nonce: i64,
+    ) -> Result<bool> {
+        let mut state = self.state();
+        let Some(job) = state
+            .jobs
+            .get_mut(&id)
+            .filter(|j| j.status == ShowJobStatus::Pending)
+        else {
+            return Ok(false);
+        };
+        job.status = ShowJobStatus::Submitting;
+        job.tx_hash = Some(tx_hash.to_string());
+        job.sender = Some(sender.to_string());
+        job.nonce = Some(nonce);
+        job.error = None;
+        job.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    async fn mark_submitted(&self, id: i64) -> Result<()> {
+        let mut state = self.state();
+        let MemoryState {
+            jobs, created_txs, ..
+        } = &mut *state;
+        let Some(job) = jobs
+            .get_mut(&id)
+            .filter(|j| j.status == ShowJobStatus::Submitting)
+        else {
+            return Ok(());
+        };
+        let created = job.tx_hash.as_ref().and_then(|h| created_txs.get(h));
+        job.show_id = created.cloned();
+        job.status = if created.is_some() {
+            ShowJobStatus::Confirmed
+        } else {
+            ShowJobStatus::Submitted
+        };
+        job.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, error: &str) -> Result<()> {
+        let mut state = self.state();
+        if let Some(job) = state
+            .jobs
+            .get_mut(&id)
+            .filter(|j| j.status != ShowJobStatus::Confirmed)
+        {
+            job.status = ShowJobStatus::Failed;
+            job.error = Some(error.to_string());
+            job.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn confirm_by_tx(
+        &self,
+        tx_hash: &str,
+        show_id: &DbU256,
+    ) -> Result<bool> {
+        let mut state = self.state();
+        let mut hit = false;
+        for job in state
+            .jobs
+            .values_mut()
+            .filter(|j| j.tx_hash.as_deref() == Some(tx_hash))
+        {
+            job.status = ShowJobStatus::Confirmed;
+            job.show_id = Some(show_id.clone());
+            job.error = None;
+            job.updated_at = Utc::now();
+            hit = true;
+        }
+        Ok(hit)
+    }
+}
+
+#[async_trait]
+impl WebhookRepo for MemoryRepo {
+    async fn insert_webhook(&self, rec: &NewWebhook) -> Result<WebhookRecord> {
+        let mut state = self.state();
+        let now = Utc::now();
+        let row = WebhookRecord {
+            id: state.next_id(),
+            url: rec.url.clone(),
+            event_types: rec.event_types.clone(),
+            secret: rec.secret.clone(),
+            active: true,
+            created_at: now,
+            updated_at: now,
+        };
+        state.webhooks.insert(row.id, row.clone());
+        Ok(row)
+    }
+
+    async fn get_webhook(&self, id: i64) -> Result<Option<WebhookRecord>> {
+        Ok(self.state().webhooks.get(&id).cloned())
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookRecord>> {
+        Ok(self.state().webhooks.values().cloned().collect())
+    }
+
+    async fn delete_webhook(&self, id: i64) -> Result<bool> {
+        let mut state = self.state();
+        let existed = state.webhooks.remove(&id).is_some();
+        state.deliveries.retain(|_, d| d.webhook_id != id);
+        Ok(existed)
+    }
+
+    async fn list_deliveries(
+        &self,
+        webhook_id: i64,
+        filter: &DeliveryFilter,
+        after: Option<&DeliveryCursor>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryRecord>> {
+        let state = self.state();
+        let rows = state
+            .deliveries
+            .values()
+            .rev()
+            .filter(|d| d.webhook_id == webhook_id)
+            .filter(|d| filter.status.is_none_or(|s| d.status == s))
+            .filter(|d| after.is_none_or(|c| d.id < c.id))
+            .cloned();
+        Ok(page(rows, 0, limit))
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        limit: i64,
+        lease_secs: f64,
+    ) -> Result<Vec<DueDelivery>> {
+        let mut state = self.state();
+        let now = Utc::now();
+        let mut due: Vec<(DateTime<Utc>, i64)> = state
+            .deliveries
+            .values()
+            .filter(|d| {
+                d.status == DeliveryStatus::Pending && d.next_attempt_at <= now
+            })
+            .map(|d| (d.next_attempt_at, d.id))
+            .collect();
+        due.sort();
+        let lease =
+            chrono::Duration::milliseconds((lease_secs * 1000.0) as i64);
+        let mut claimed = Vec::new();
+        for (_, id) in page(due.into_iter(), 0, limit) {
+            let MemoryState {
+                deliveries,
+                webhooks,
+                ..
+            } = &mut *state;
+            let Some(d) = deliveries.get_mut(&id) else {
+                continue;
+            };
+            d.attempts += 1;
+            d.next_attempt_at = now + lease;
+            d.updated_at = now;
+            if let Some(w) = webhooks.get(&d.webhook_id) {
+                claimed.push(DueDelivery {
+                    id: d.id,
+                    webhook_id: d.webhook_id,
+                    event_type: d.event_type.clone(),
+                    payload: d.payload.clone(),
+                    attempts: d.attempts,
+                    created_at: d.created_at,
+                    url: w.url.clone(),
+                    secret: w.secret.clone(),
+                });
+            }
+        }
+        Ok(claimed)
+    }
+
+    async fn mark_delivery_succeeded(
+        &self,
+        id: i64,
+        status_code: i32,
+    ) -> Result<()> {
+        let mut state = self.state();
+        if let Some(d) = state.deliveries.get_mut(&id) {
+            let now = Utc::now();
+            d.status = DeliveryStatus::Succeeded;
+            d.last_status_code = Some(status_code);
+            d.last_error = None;
+            d.delivered_at = Some(now);
+            d.updated_at = now;
+        }
+        Ok(())
+    }
+
+    async fn mark_delivery_failed(
+        &self,
+        id: i64,
+        status_code: Option<i32>,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let mut state = self.state();
+        if let Some(d) = state.deliveries.get_mut(&id) {
+            match next_attempt_at {
+                Some(at) => d.next_attempt_at = at,
+                None => d.status = DeliveryStatus::Failed,
+            }
+            d.last_status_code = status_code;
+            d.last_error = Some(error.to_string());
+            d.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditRepo for MemoryRepo {
+    async fn list_audit(
+        &self,
+        filter: &AuditFilter,
+        after: Option<&AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditRecord>> {
+        let state = self.state();
+        let rows = state
+            .audit
+            .iter()
+            .rev()
+            .filter(|a| {
+                filter.resource.as_ref().is_none_or(|r| &a.resource == r)
+            })
+            .filter(|a| {
+                filter
+                    .resource_id
+                    .as_ref()
+                    .is_none_or(|id| &a.resource_id == id)
+            })
+            .filter(|a| {
+                filter.actor.as_ref().is_none_or(|actor| {
+                    a.actor.as_deref() == Some(actor.to_lowercase().as_str())
+                })
+            })
+            .filter(|a| after.is_none_or(|c| a.id < c.id))
+            .cloned();
+        Ok(page(rows, 0, limit))
+    }
+}
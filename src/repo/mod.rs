@@ -1 +1,11 @@
+pub mod audit_repo;
+pub mod checkpoint_repo;
+pub mod did_repo;
+pub mod listing_repo;
+pub mod memory;
+pub mod postgres;
+pub mod show_job_repo;
 pub mod show_repo;
+pub mod ticket_repo;
+pub mod traits;
+pub mod webhook_repo;
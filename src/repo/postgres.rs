@@ -0,0 +1,509 @@
+//! [`Repos`](super::traits::Repos) 的 Postgres 实现：委托给各 `*_repo` 模块中的查询函数。
+use crate::repo::{
+    audit_repo::{self, AuditContext, AuditCursor, AuditFilter, AuditRecord},
+    checkpoint_repo::{self, CheckpointRecord},
+    did_repo::{self, AddressDidRecord, DidUpdate},
+    listing_repo::{
+        self, ListingCursor, ListingFilter, ListingRecord, ListingStatus,
+        NewListing,
+    },
+    show_job_repo::{self, NewShowCreateJob, ShowCreateJobRecord},
+    show_repo::{
+        self, ShowCreatedDetailRecord, ShowCreatedRecord, ShowCursor,
+        ShowDataRecord, ShowFilter, ShowPatch, ShowPatchOutcome, ShowSortKey,
+        SortOrder,
+    },
+    ticket_repo::{
+        self, NewTicket, NewTicketTransfer, TicketCursor, TicketFilter,
+        TicketRecord, TicketStatus, TicketTransferRecord, TransferCursor,
+        TransferFilter,
+    },
+    traits::{
+        AuditRepo, DidRepo, EventRepo, ListingRepo, ShowJobRepo, ShowRepo,
+        TicketRepo, WebhookRepo,
+    },
+    webhook_repo::{
+        self, DeliveryCursor, DeliveryFilter, DueDelivery, NewWebhook,
+        WebhookDeliveryRecord, WebhookRecord,
+    },
+};
+use crate::utils::uint256::DbU256;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use sqlx::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct PgRepo {
+    pool: PgPool,
+}
+
+impl PgRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ShowRepo for PgRepo {
+    async fn upsert_show_all(
+        &self,
+        basic: &ShowCreatedRecord,
+        detail: &ShowCreatedDetailRecord,
+        data: &ShowDataRecord,
+    ) -> Result<ShowDataRecord> {
+        show_repo::upsert_show_all(&self.pool, basic, detail, data).await
+    }
+
+    async fn upsert_show_snapshot(
+        &self,
+        detail: &ShowCreatedDetailRecord,
+        data: &ShowDataRecord,
+    ) -> Result<ShowDataRecord> {
+        show_repo::upsert_show_snapshot(&self.pool, detail, data).await
+    }
+
+    async fn get_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+    ) -> Result<Option<ShowDataRecord>> {
+        show_repo::get_show_by_id(&self.pool, chain_id, id.clone()).await
+    }
+
+    async fn search_shows(
+        &self,
+        filter: &ShowFilter,
+        sort: Option<ShowSortKey>,
+        order: SortOrder,
+        after: Option<&ShowCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ShowDataRecord>> {
+        show_repo::repo_search_shows(
+            &self.pool, filter, sort, order, after, limit, offset,
+        )
+        .await
+    }
+
+    async fn update_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+        patch: &ShowPatch,
+        if_version: Option<&[i64]>,
+        audit: &AuditContext,
+    ) -> Result<ShowPatchOutcome> {
+        show_repo::update_show_fields(
+            &self.pool, chain_id, id, patch, if_version, audit,
+        )
+        .await
+    }
+
+    async fn delete_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+        audit: &AuditContext,
+    ) -> Result<Option<ShowDataRecord>> {
+        show_repo::soft_delete_show(&self.pool, chain_id, id, audit).await
+    }
+
+    async fn get_shows_by_ids(
+        &self,
+        chain_id: i64,
+        ids: &[DbU256],
+    ) -> Result<Vec<ShowDataRecord>> {
+        show_repo::get_shows_by_ids(&self.pool, chain_id, ids).await
+    }
+}
+
+#[async_trait]
+impl TicketRepo for PgRepo {
+    async fn upsert_minted_ticket(&self, rec: &NewTicket) -> Result<bool> {
+        ticket_repo::upsert_minted_ticket(&self.pool, rec).await
+    }
+
+    async fn record_transfer(&self, rec: &NewTicketTransfer) -> Result<()> {
+        ticket_repo::record_ticket_transfer(&self.pool, rec).await
+    }
+
+    async fn update_status(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+        status: TicketStatus,
+    ) -> Result<bool> {
+        ticket_repo::update_ticket_status(
+            &self.pool,
+            chain_id,
+            token_id.clone(),
+            status,
+        )
+        .await
+    }
+
+    async fn get_ticket(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+    ) -> Result<Option<TicketRecord>> {
+        ticket_repo::get_ticket_by_id(&self.pool, chain_id, token_id.clone())
+            .await
+    }
+
+    async fn list_tickets(
+        &self,
+        filter: &TicketFilter,
+        after: Option<&TicketCursor>,
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>> {
+        ticket_repo::list_tickets(&self.pool, filter, after, limit).await
+    }
+
+    async fn list_transfers(
+        &self,
+        filter: &TransferFilter,
+        after: Option<&TransferCursor>,
+        limit: i64,
+    ) -> Result<Vec<TicketTransferRecord>> {
+        ticket_repo::list_ticket_transfers(&self.pool, filter, after, limit)
+            .await
+    }
+
+    async fn get_tickets_by_ids(
+        &self,
+        chain_id: i64,
+        token_ids: &[DbU256],
+    ) -> Result<Vec<TicketRecord>> {
+        ticket_repo::get_tickets_by_ids(&self.pool, chain_id, token_ids).await
+    }
+
+    async fn list_tickets_for_shows(
+        &self,
+        chain_id: i64,
+        show_ids: &[DbU256],
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>> {
+        ticket_repo::list_tickets_for_shows(
+            &self.pool, chain_id, show_ids, limit,
+        )
+        .await
+    }
+
+    async fn list_tickets_for_owners(
+        &self,
+        chain_id: i64,
+        owners: &[String],
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>> {
+        ticket_repo::list_tickets_for_owners(
+            &self.pool, chain_id, owners, limit,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl ListingRepo for PgRepo {
+    async fn upsert_listing(&self, rec: &NewListing) -> Result<ListingRecord> {
+        listing_repo::upsert_listing(&self.pool, rec).await
+    }
+
+    async fn update_listing_price(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+        price: &DbU256,
+        eth_price: &DbU256,
+        expires_at: &DbU256,
+    ) -> Result<Option<ListingRecord>> {
+        listing_repo::update_listing_price(
+            &self.pool,
+            chain_id,
+            listing_id.clone(),
+            price.clone(),
+            eth_price.clone(),
+            expires_at.clone(),
+        )
+        .await
+    }
+
+    async fn set_listing_status(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+        status: ListingStatus,
+        buyer: Option<&str>,
+    ) -> Result<Option<ListingRecord>> {
+        listing_repo::set_listing_status(
+            &self.pool,
+            chain_id,
+            listing_id.clone(),
+            status,
+            buyer,
+        )
+        .await
+    }
+
+    async fn get_listing(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+    ) -> Result<Option<ListingRecord>> {
+        listing_repo::get_listing_by_id(
+            &self.pool,
+            chain_id,
+            listing_id.clone(),
+        )
+        .await
+    }
+
+    async fn list_listings(
+        &self,
+        filter: &ListingFilter,
+        after: Option<&ListingCursor>,
+        limit: i64,
+    ) -> Result<Vec<ListingRecord>> {
+        listing_repo::list_listings(&self.pool, filter, after, limit).await
+    }
+
+    async fn get_active_listings_by_tokens(
+        &self,
+        chain_id: i64,
+        token_ids: &[DbU256],
+    ) -> Result<Vec<ListingRecord>> {
+        listing_repo::get_active_listings_by_tokens(
+            &self.pool, chain_id, token_ids,
+        )
+        .await
+    }
+
+    async fn list_listings_for_sellers(
+        &self,
+        chain_id: i64,
+        sellers: &[String],
+        limit: i64,
+    ) -> Result<Vec<ListingRecord>> {
+        listing_repo::list_listings_for_sellers(
+            &self.pool, chain_id, sellers, limit,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl DidRepo for PgRepo {
+    async fn upsert_did(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        did: &str,
+        controller: &str,
+        cid: &str,
+    ) -> Result<()> {
+        did_repo::upsert_did(
+            &self.pool, chain_id, did_hash, did, controller, cid,
+        )
+        .await
+    }
+
+    async fn bind_did_address(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        address: &str,
+    ) -> Result<()> {
+        did_repo::bind_did_address(&self.pool, chain_id, did_hash, address)
+            .await
+    }
+
+    async fn unbind_did_address(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        address: &str,
+    ) -> Result<()> {
+        did_repo::unbind_did_address(&self.pool, chain_id, did_hash, address)
+            .await
+    }
+
+    async fn update_did(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        update: &DidUpdate,
+    ) -> Result<bool> {
+        did_repo::update_did(&self.pool, chain_id, did_hash, update).await
+    }
+
+    async fn get_dids_by_addresses(
+        &self,
+        chain_id: i64,
+        addresses: &[String],
+    ) -> Result<Vec<AddressDidRecord>> {
+        did_repo::get_dids_by_addresses(&self.pool, chain_id, addresses).await
+    }
+}
+
+#[async_trait]
+impl EventRepo for PgRepo {
+    async fn record_checkpoint(
+        &self,
+        chain_id: i64,
+        contract: &str,
+        address: &str,
+        block: i64,
+        log_index: i64,
+    ) -> Result<()> {
+        checkpoint_repo::record_checkpoint(
+            &self.pool, chain_id, contract, address, block, log_index,
+        )
+        .await
+    }
+
+    async fn list_checkpoints(&self) -> Result<Vec<CheckpointRecord>> {
+        checkpoint_repo::list_checkpoints(&self.pool).await
+    }
+
+    async fn enqueue_webhook_event(
+        &self,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<u64> {
+        webhook_repo::enqueue_webhook_deliveries(
+            &self.pool, event_type, payload,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl ShowJobRepo for PgRepo {
+    async fn insert_job(
+        &self,
+        job: &NewShowCreateJob,
+        audit: &AuditContext,
+    ) -> Result<ShowCreateJobRecord> {
+        show_job_repo::insert_show_create_job(&self.pool, job, audit).await
+    }
+
+    async fn get_job(&self, id: i64) -> Result<Option<ShowCreateJobRecord>> {
+        show_job_repo::get_show_create_job(&self.pool, id).await
+    }
+
+    async fn list_open_jobs(&self) -> Result<Vec<i64>> {
+        show_job_repo::list_open_show_create_jobs(&self.pool).await
+    }
+
+    async fn claim_job(
+        &self,
+        id: i64,
+        tx_hash: &str,
+        sender: &str,
+        nonce: i64,
+    ) -> Result<bool> {
+        show_job_repo::claim_show_create_job(
+            &self.pool, id, tx_hash, sender, nonce,
+        )
+        .await
+    }
+
+    async fn mark_submitted(&self, id: i64) -> Result<()> {
+        show_job_repo::mark_show_create_job_submitted(&self.pool, id).await
+    }
+
+    async fn mark_failed(&self, id: i64, error: &str) -> Result<()> {
+        show_job_repo::mark_show_create_job_failed(&self.pool, id, error).await
+    }
+
+    async fn confirm_by_tx(
+        &self,
+        tx_hash: &str,
+        show_id: &DbU256,
+    ) -> Result<bool> {
+        show_job_repo::confirm_show_create_job_by_tx(
+            &self.pool,
+            tx_hash,
+            show_id.clone(),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl WebhookRepo for PgRepo {
+    async fn insert_webhook(&self, rec: &NewWebhook) -> Result<WebhookRecord> {
+        webhook_repo::insert_webhook(&self.pool, rec).await
+    }
+
+    async fn get_webhook(&self, id: i64) -> Result<Option<WebhookRecord>> {
+        webhook_repo::get_webhook(&self.pool, id).await
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookRecord>> {
+        webhook_repo::list_webhooks(&self.pool).await
+    }
+
+    async fn delete_webhook(&self, id: i64) -> Result<bool> {
+        webhook_repo::delete_webhook(&self.pool, id).await
+    }
+
+    async fn list_deliveries(
+        &self,
+        webhook_id: i64,
+        filter: &DeliveryFilter,
+        after: Option<&DeliveryCursor>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryRecord>> {
+        webhook_repo::list_webhook_deliveries(
+            &self.pool, webhook_id, filter, after, limit,
+        )
+        .await
+    }
+
+    async fn claim_due_deliveries(
+        &self,
+        limit: i64,
+        lease_secs: f64,
+    ) -> Result<Vec<DueDelivery>> {
+        webhook_repo::claim_due_deliveries(&self.pool, limit, lease_secs).await
+    }
+
+    async fn mark_delivery_succeeded(
+        &self,
+        id: i64,
+        status_code: i32,
+    ) -> Result<()> {
+        webhook_repo::mark_delivery_succeeded(&self.pool, id, status_code).await
+    }
+
+    async fn mark_delivery_failed(
+        &self,
+        id: i64,
+        status_code: Option<i32>,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        webhook_repo::mark_delivery_failed(
+            &self.pool,
+            id,
+            status_code,
+            error,
+            next_attempt_at,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl AuditRepo for PgRepo {
+    async fn list_audit(
+        &self,
+        filter: &AuditFilter,
+        after: Option<&AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditRecord>> {
+        audit_repo::list_audit(&self.pool, filter, after, limit).await
+    }
+}
@@ -0,0 +1,217 @@
+use crate::repo::audit_repo::{AuditContext, NewAuditEntry, insert_audit_tx};
+use crate::utils::uint256::DbU256;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::Serialize;
+use sqlx::{PgPool, prelude::FromRow};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type, ToSchema,
+)]
+#[sqlx(type_name = "show_job_status", rename_all = "UPPERCASE")]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ShowJobStatus {
+    Pending,
+    /// 交易已签名并记录哈希，可能已广播；只按链上状态核对，不再重发
+    Submitting,
+    Submitted,
+    Confirmed,
+    Failed,
+}
+
+/// 待上链的 createShow 请求；show_id 在 ShowCreated 事件被索引后回填。
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ShowCreateJobRecord {
+    pub id: i64,
+    #[serde(skip_serializing)]
+    #[schema(ignore)]
+    pub signer: String,
+    pub organizer: String,
+    pub name: String,
+    pub description: String,
+    pub location: String,
+    pub event_time: DbU256,
+    pub end_time: DbU256,
+    pub ticket_price: DbU256,
+    pub max_tickets: DbU256,
+    pub metadata_uri: String,
+    pub status: ShowJobStatus,
+    pub tx_hash: Option<String>,
+    /// 签名交易的发送地址与 nonce（SUBMITTING 起记录）
+    pub sender: Option<String>,
+    pub nonce: Option<i64>,
+    pub show_id: Option<DbU256>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 新建 job 时的入参（id/status/时间戳由数据库生成）。
+#[derive(Debug, Clone)]
+pub struct NewShowCreateJob {
+    pub signer: String,
+    pub organizer: String,
+    pub name: String,
+    pub description: String,
+    pub location: String,
+    pub event_time: DbU256,
+    pub end_time: DbU256,
+    pub ticket_price: DbU256,
+    pub max_tickets: DbU256,
+    pub metadata_uri: String,
+}
+
+const JOB_COLUMNS: &str = "id, signer, organizer, name, description, location, event_time, end_time, ticket_price, max_tickets, metadata_uri, status, tx_hash, sender, nonce, show_id, error, created_at, updated_at";
+
+/// 新建 job，并在同一事务中写入审计记录（after 为完整的 job）
+pub async fn insert_show_create_job(
+    pool: &PgPool,
+    job: &NewShowCreateJob,
+    audit: &AuditContext,
+) -> Result<ShowCreateJobRecord> {
+    let mut tx = pool.begin().await?;
+    let rec = sqlx::query_as::<_, ShowCreateJobRecord>(&format!(
+        r#"
+        INSERT INTO show_create_jobs (signer, organizer, name, description, location, event_time, end_time, ticket_price, max_tickets, metadata_uri)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING {JOB_COLUMNS};
+        "#
+    ))
+    .bind(&job.signer)
+    .bind(&job.organizer)
+    .bind(&job.name)
+    .bind(&job.description)
+    .bind(&job.location)
+    .bind(job.event_time.clone())
+    .bind(job.end_time.clone())
+    .bind(job.ticket_price.clone())
+    .bind(job.max_tickets.clone())
+    .bind(&job.metadata_uri)
+    .fetch_one(&mut *tx)
+    .await?;
+    let entry = NewAuditEntry::new(
+        None,
+        "show_job",
+        rec.id.to_string(),
+        "create",
+        None,
+        Some(&rec),
+    );
+    insert_audit_tx(&mut tx, audit, &entry).await?;
+    tx.commit().await?;
+    tracing::debug!(job_id = rec.id, "Inserted show_create_jobs");
+    Ok(rec)
+}
+
+pub async fn get_show_create_job(
+    pool: &PgPool,
+    id: i64,
+) -> Result<Option<ShowCreateJobRecord>> {
+    let rec = sqlx::query_as::<_, ShowCreateJobRecord>(&format!(
+        "SELECT {JOB_COLUMNS} FROM show_create_jobs WHERE id = $1;"
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(rec)
+}
+
+/// 进程重启后需要继续处理的 job：PENDING 重新提交，SUBMITTING / SUBMITTED 按链上状态核对。
+pub async fn list_open_show_create_jobs(pool: &PgPool) -> Result<Vec<i64>> {
+    let ids: Vec<(i64,)> = sqlx::query_as(
+        "SELECT id FROM show_create_jobs WHERE status IN ('PENDING', 'SUBMITTING', 'SUBMITTED') ORDER BY id;",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(ids.into_iter().map(|(id,)| id).collect())
+}
+
+/// 广播前认领 job：PENDING -> SUBMITTING，同时记录已签名交易的哈希、发送地址与 nonce。
+/// 返回 false 表示 job 已不是 PENDING（被其他 worker 认领或已结束），调用方不得广播。
+pub async fn claim_show_create_job(
+    pool: &PgPool,
+    id: i64,
+    tx_hash: &str,
+    sender: &str,
+    nonce: i64,
+) -> Result<bool> {
+    let res = sqlx::query(
+        r#"
+        UPDATE show_create_jobs
+        SET status = 'SUBMITTING', tx_hash = $2, sender = $3, nonce = $4, error = NULL, updated_at = NOW()
+        WHERE id = $1 AND status = 'PENDING';
+        "#,
+    )
+    .bind(id)
+    .bind(tx_hash)
+    .bind(sender)
+    .bind(nonce)
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected() == 1)
+}
+
+/// 认领的交易已确认在链上（或交易池中）。若索引器已先一步写入该交易的 ShowCreated，则直接标记为 CONFIRMED。
+pub async fn mark_show_create_job_submitted(
+    pool: &PgPool,
+    id: i64,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE show_create_jobs j
+        SET show_id = (SELECT show_id FROM show_created_events e WHERE e.tx_hash = j.tx_hash LIMIT 1),
+            status = CASE
+                WHEN EXISTS (SELECT 1 FROM show_created_events e WHERE e.tx_hash = j.tx_hash)
+                THEN 'CONFIRMED'::show_job_status
+                ELSE 'SUBMITTED'::show_job_status
+            END,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'SUBMITTING';
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 标记失败。已 CONFIRMED 的 job 不会被覆盖（索引器可能先一步确认）。
+pub async fn mark_show_create_job_failed(
+    pool: &PgPool,
+    id: i64,
+    error: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE show_create_jobs
+        SET status = 'FAILED', error = $2, updated_at = NOW()
+        WHERE id = $1 AND status <> 'CONFIRMED';
+        "#,
+    )
+    .bind(id)
+    .bind(error)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 索引器写入 ShowCreated 后调用：按交易哈希回填 show_id。返回是否命中某个 job。
+pub async fn confirm_show_create_job_by_tx(
+    pool: &PgPool,
+    tx_hash: &str,
+    show_id: DbU256,
+) -> Result<bool> {
+    let res = sqlx::query(
+        r#"
+        UPDATE show_create_jobs
+        SET status = 'CONFIRMED', show_id = $2, error = NULL, updated_at = NOW()
+        WHERE tx_hash = $1;
+        "#,
+    )
+    .bind(tx_hash)
+    .bind(show_id)
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected() > 0)
+}
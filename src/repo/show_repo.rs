@@ -1,12 +1,17 @@
+use crate::repo::audit_repo::{AuditContext, NewAuditEntry, insert_audit_tx};
 use crate::utils::uint256::DbU256;
 use chrono::{DateTime, Utc};
 use eyre::Result;
 use serde::{Deserialize, Serialize};
-use sqlx::{Executor, PgPool, Postgres, Transaction, prelude::FromRow};
+use sqlx::{
+    Executor, PgPool, Postgres, QueryBuilder, Transaction, prelude::FromRow,
+};
+use utoipa::ToSchema;
 
 // 结构化 ShowCreated 详情记录（拥有所有权字段，便于跨异步边界传递与查询返回）。
 #[derive(Debug, Serialize, FromRow)]
 pub struct ShowCreatedRecord {
+    pub chain_id: i64,
     pub show_id: DbU256,
     pub tx_hash: Option<String>,
     pub block_number: Option<DbU256>,
@@ -26,6 +31,7 @@ pub enum ShowStatus {
 
 #[derive(Debug, Serialize, FromRow)]
 pub struct ShowCreatedDetailRecord {
+    pub chain_id: i64,
     pub show_id: DbU256,
     pub start_time: DbU256,
     pub end_time: DbU256,
@@ -42,8 +48,9 @@ pub struct ShowCreatedDetailRecord {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ShowDataRecord {
+    pub chain_id: i64,
     pub id: DbU256,
     pub name: String,
     pub description: String,
@@ -51,21 +58,59 @@ pub struct ShowDataRecord {
     pub event_time: DbU256,
     pub ticket_price: DbU256,
     pub max_tickets: DbU256,
+    /// 已索引的售出票数：只由 tickets 表决定（铸造时累加），链上快照写入时不覆盖
     pub sold_tickets: DbU256,
     pub is_active: bool,
     pub organizer: String,
     pub created_at: DateTime<Utc>,
+    /// 每次写入递增，作为 ETag；写入时由数据库维护，构造记录时填 0 即可
+    #[serde(default)]
+    #[sqlx(default)]
+    pub version: i64,
 }
-/// Upsert 一条 ShowCreated 详情记录；以 show_id 为主键，重复则更新基础字段。
+
+/// PATCH /show/{id} 的字段变更：只更新为 Some 的列
+#[derive(Debug, Clone, Default)]
+pub struct ShowPatch {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub event_time: Option<DbU256>,
+    pub ticket_price: Option<DbU256>,
+    pub max_tickets: Option<DbU256>,
+    pub is_active: Option<bool>,
+}
+
+impl ShowPatch {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.description.is_none()
+            && self.location.is_none()
+            && self.event_time.is_none()
+            && self.ticket_price.is_none()
+            && self.max_tickets.is_none()
+            && self.is_active.is_none()
+    }
+}
+
+/// 条件更新的结果
+#[derive(Debug)]
+pub enum ShowPatchOutcome {
+    Updated(ShowDataRecord),
+    NotFound,
+    /// 行存在但条件不满足（version 不匹配，或已售超过新的 max_tickets），附当前记录
+    Rejected(ShowDataRecord),
+}
+/// Upsert 一条 ShowCreated 详情记录；以 (chain_id, show_id) 为主键，重复则更新基础字段。
 pub async fn insert_show_created(
     pool: &PgPool,
     rec: &ShowCreatedRecord,
 ) -> Result<()> {
     let res = sqlx::query(
         r#"
-        INSERT INTO show_created_events (show_id, tx_hash, block_number, organizer, log_index, created_at)
-        VALUES ($1, $2, $3, $4, $5, NOW())
-        ON CONFLICT (show_id) DO UPDATE
+        INSERT INTO show_created_events (chain_id, show_id, tx_hash, block_number, organizer, log_index, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW())
+        ON CONFLICT (chain_id, show_id) DO UPDATE
         SET tx_hash = EXCLUDED.tx_hash,
             block_number = EXCLUDED.block_number,
             organizer = EXCLUDED.organizer,
@@ -73,6 +118,7 @@ pub async fn insert_show_created(
             created_at = EXCLUDED.created_at;
         "#,
     )
+    .bind(rec.chain_id)
     .bind(rec.show_id.clone())
     .bind(&rec.tx_hash)
     .bind(rec.block_number.clone())
@@ -90,9 +136,9 @@ pub async fn insert_show_created_tx(
 ) -> Result<()> {
     let query = sqlx::query(
         r#"
-        INSERT INTO show_created_events (show_id, tx_hash, block_number, organizer, log_index, created_at)
-        VALUES ($1, $2, $3, $4, $5, NOW())
-        ON CONFLICT (show_id) DO UPDATE
+        INSERT INTO show_created_events (chain_id, show_id, tx_hash, block_number, organizer, log_index, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW())
+        ON CONFLICT (chain_id, show_id) DO UPDATE
         SET tx_hash = EXCLUDED.tx_hash,
             block_number = EXCLUDED.block_number,
             organizer = EXCLUDED.organizer,
@@ -100,6 +146,7 @@ pub async fn insert_show_created_tx(
             created_at = EXCLUDED.created_at;
         "#,
     )
+    .bind(rec.chain_id)
     .bind(rec.show_id.clone())
     .bind(&rec.tx_hash)
     .bind(rec.block_number.clone())
@@ -116,9 +163,9 @@ pub async fn insert_show_created_detail(
 ) -> Result<()> {
     let query = sqlx::query(
         r#"
-        INSERT INTO show_created_events_detail (show_id, start_time, end_time, total_tickets, ticket_price, decimal, ticket_sold, organizer, location, name, description, metadata_uri, status, created_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW())
-        ON CONFLICT (show_id) DO UPDATE
+        INSERT INTO show_created_events_detail (chain_id, show_id, start_time, end_time, total_tickets, ticket_price, decimal, ticket_sold, organizer, location, name, description, metadata_uri, status, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, NOW())
+        ON CONFLICT (chain_id, show_id) DO UPDATE
         SET start_time = EXCLUDED.start_time,
             end_time = EXCLUDED.end_time,
             total_tickets = EXCLUDED.total_tickets,
@@ -135,6 +182,7 @@ pub async fn insert_show_created_detail(
         RETURNING *;
         "#,
     )
+    .bind(rec.chain_id)
     .bind(rec.show_id.clone())
     .bind(rec.start_time.clone())
     .bind(rec.end_time.clone())
@@ -159,9 +207,9 @@ pub async fn insert_show_created_detail_tx(
 ) -> Result<()> {
     let query = sqlx::query(
         r#"
-        INSERT INTO show_created_events_detail (show_id, start_time, end_time, total_tickets, ticket_price, decimal, ticket_sold, organizer, location, name, description, metadata_uri, status, created_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW())
-        ON CONFLICT (show_id) DO UPDATE
+        INSERT INTO show_created_events_detail (chain_id, show_id, start_time, end_time, total_tickets, ticket_price, decimal, ticket_sold, organizer, location, name, description, metadata_uri, status, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, NOW())
+        ON CONFLICT (chain_id, show_id) DO UPDATE
         SET start_time = EXCLUDED.start_time,
             end_time = EXCLUDED.end_time,
             total_tickets = EXCLUDED.total_tickets,
@@ -178,6 +226,7 @@ pub async fn insert_show_created_detail_tx(
         RETURNING *;
         "#,
     )
+    .bind(rec.chain_id)
     .bind(rec.show_id.clone())
     .bind(rec.start_time.clone())
     .bind(rec.end_time.clone())
@@ -202,21 +251,24 @@ pub async fn insert_show_data(
 ) -> Result<()> {
     let query = sqlx::query(
         r#"
-        INSERT INTO shows (id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())
-        ON CONFLICT (id) DO UPDATE
+        INSERT INTO shows (chain_id, id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8,
+            (SELECT COUNT(*) FROM tickets WHERE chain_id = $1 AND event_id = $2),
+            $9, $10, NOW())
+        ON CONFLICT (chain_id, id) DO UPDATE
         SET name = EXCLUDED.name,
             description = EXCLUDED.description,
             location = EXCLUDED.location,
             event_time = EXCLUDED.event_time,
             ticket_price = EXCLUDED.ticket_price,
             max_tickets = EXCLUDED.max_tickets,
-            sold_tickets = EXCLUDED.sold_tickets,
             is_active = EXCLUDED.is_active,
             organizer = EXCLUDED.organizer,
-            created_at = EXCLUDED.created_at;
+            version = shows.version + 1,
+            updated_at = NOW();
         "#,
     )
+    .bind(rec.chain_id)
     .bind(rec.id.clone())
     .bind(&rec.name)
     .bind(&rec.description)
@@ -224,7 +276,6 @@ pub async fn insert_show_data(
     .bind(rec.event_time.clone())
     .bind(rec.ticket_price.clone())
     .bind(rec.max_tickets.clone())
-    .bind(rec.sold_tickets.clone())
     .bind(rec.is_active)
     .bind(&rec.organizer);
     let res = pool.execute(query).await?;
@@ -232,27 +283,32 @@ pub async fn insert_show_data(
     Ok(())
 }
 
+/// 写入 shows 行并返回写入后的记录。sold_tickets 只在首次插入时按已索引的票计数，之后只由铸票累加。
 pub async fn insert_show_data_tx(
     tx: &mut Transaction<'_, Postgres>,
     rec: &ShowDataRecord,
-) -> Result<()> {
-    let query = sqlx::query(
+) -> Result<ShowDataRecord> {
+    let stored = sqlx::query_as::<_, ShowDataRecord>(&format!(
         r#"
-        INSERT INTO shows (id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())
-        ON CONFLICT (id) DO UPDATE
+        INSERT INTO shows (chain_id, id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8,
+            (SELECT COUNT(*) FROM tickets WHERE chain_id = $1 AND event_id = $2),
+            $9, $10, NOW())
+        ON CONFLICT (chain_id, id) DO UPDATE
         SET name = EXCLUDED.name,
             description = EXCLUDED.description,
             location = EXCLUDED.location,
             event_time = EXCLUDED.event_time,
             ticket_price = EXCLUDED.ticket_price,
             max_tickets = EXCLUDED.max_tickets,
-            sold_tickets = EXCLUDED.sold_tickets,
             is_active = EXCLUDED.is_active,
             organizer = EXCLUDED.organizer,
-            created_at = EXCLUDED.created_at;
-        "#,
-    )
+            version = shows.version + 1,
+            updated_at = NOW()
+        RETURNING {SHOW_COLUMNS};
+        "#
+    ))
+    .bind(rec.chain_id)
     .bind(rec.id.clone())
     .bind(&rec.name)
     .bind(&rec.description)
@@ -260,12 +316,12 @@ pub async fn insert_show_data_tx(
     .bind(rec.event_time.clone())
     .bind(rec.ticket_price.clone())
     .bind(rec.max_tickets.clone())
-    .bind(rec.sold_tickets.clone())
     .bind(rec.is_active)
-    .bind(&rec.organizer);
-    let res = tx.execute(query).await?;
-    tracing::debug!(?res, "Inserted/Updated shows (tx)");
-    Ok(())
+    .bind(&rec.organizer)
+    .fetch_one(&mut **tx)
+    .await?;
+    tracing::debug!(?stored, "Inserted/Updated shows (tx)");
+    Ok(stored)
 }
 
 /// 方便的聚合写接口：在一个事务中写入三张表
@@ -274,26 +330,37 @@ pub async fn upsert_show_all(
     basic: &ShowCreatedRecord,
     detail: &ShowCreatedDetailRecord,
     data: &ShowDataRecord,
-) -> Result<()> {
+) -> Result<ShowDataRecord> {
     let mut tx = pool.begin().await?;
     insert_show_created_tx(&mut tx, basic).await?;
     insert_show_created_detail_tx(&mut tx, detail).await?;
-    insert_show_data_tx(&mut tx, data).await?;
+    let stored = insert_show_data_tx(&mut tx, data).await?;
     tx.commit().await?;
-    Ok(())
+    Ok(stored)
+}
+
+/// ShowUpdated/Activated/Cancelled/Ended：用链上最新快照刷新详情与 shows（不改动创建事件行）
+pub async fn upsert_show_snapshot(
+    pool: &PgPool,
+    detail: &ShowCreatedDetailRecord,
+    data: &ShowDataRecord,
+) -> Result<ShowDataRecord> {
+    let mut tx = pool.begin().await?;
+    insert_show_created_detail_tx(&mut tx, detail).await?;
+    let stored = insert_show_data_tx(&mut tx, data).await?;
+    tx.commit().await?;
+    Ok(stored)
 }
 
 pub async fn get_show_by_id(
     pool: &PgPool,
+    chain_id: i64,
     show_id: DbU256,
 ) -> Result<Option<ShowDataRecord>> {
-    let rec = sqlx::query_as::<_, ShowDataRecord>(
-        r#"
-        SELECT id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at
-        FROM shows
-        WHERE id = $1;
-        "#,
-    )
+    let rec = sqlx::query_as::<_, ShowDataRecord>(&format!(
+        "SELECT {SHOW_COLUMNS} FROM shows WHERE chain_id = $1 AND id = $2 AND deleted_at IS NULL;"
+    ))
+    .bind(chain_id)
     .bind(show_id)
     .fetch_optional(pool)
     .await?;
@@ -303,17 +370,14 @@ pub async fn get_show_by_id(
 
 pub async fn repo_list_shows(
     pool: &PgPool,
+    chain_id: i64,
     limit: i64,
     offset: i64,
 ) -> Result<Vec<ShowDataRecord>> {
-    let recs = sqlx::query_as::<_, ShowDataRecord>(
-        r#"
-        SELECT id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at
-        FROM shows
-        ORDER BY created_at DESC
-        LIMIT $1 OFFSET $2;
-        "#,
-    )
+    let recs = sqlx::query_as::<_, ShowDataRecord>(&format!(
+        "SELECT {SHOW_COLUMNS} FROM shows WHERE chain_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC LIMIT $2 OFFSET $3;"
+    ))
+    .bind(chain_id)
     .bind(limit)
     .bind(offset)
     .fetch_all(pool)
@@ -321,3 +385,369 @@ pub async fn repo_list_shows(
     tracing::debug!(count = recs.len(), "Listed shows");
     Ok(recs)
 }
+
+/// GET /shows 的排序字段；未指定时按 created_at 排序（有 q 时按相关度）。
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ShowSortKey {
+    EventTime,
+    Price,
+    Sold,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// shows 查询过滤条件；None 表示不过滤。
+#[derive(Debug, Clone, Default)]
+pub struct ShowFilter {
+    pub chain_id: Option<i64>,
+    pub is_active: Option<bool>,
+    pub organizer: Option<String>,
+    pub event_time_from: Option<DbU256>,
+    pub event_time_to: Option<DbU256>,
+    pub location: Option<String>,
+    pub min_price: Option<DbU256>,
+    pub max_price: Option<DbU256>,
+    /// 全文检索（name/description/location），使用 websearch 语法
+    pub q: Option<String>,
+}
+
+/// shows 的 keyset 游标：最后一条记录的排序列值 + id（id 作为并列时的决胜字段）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "k", content = "v", rename_all = "snake_case")]
+pub enum ShowCursorKey {
+    CreatedAt(DateTime<Utc>),
+    EventTime(DbU256),
+    Price(DbU256),
+    Sold(DbU256),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShowCursor {
+    pub key: ShowCursorKey,
+    pub order: SortOrder,
+    pub id: DbU256,
+}
+
+impl ShowCursor {
+    pub fn from_record(
+        rec: &ShowDataRecord,
+        sort: Option<ShowSortKey>,
+        order: SortOrder,
+    ) -> Self {
+        let key = match sort {
+            None => ShowCursorKey::CreatedAt(rec.created_at),
+            Some(ShowSortKey::EventTime) => {
+                ShowCursorKey::EventTime(rec.event_time.clone())
+            }
+            Some(ShowSortKey::Price) => {
+                ShowCursorKey::Price(rec.ticket_price.clone())
+            }
+            Some(ShowSortKey::Sold) => {
+                ShowCursorKey::Sold(rec.sold_tickets.clone())
+            }
+        };
+        Self {
+            key,
+            order,
+            id: rec.id.clone(),
+        }
+    }
+
+    /// 游标是否由相同的排序参数生成
+    pub fn matches(&self, sort: Option<ShowSortKey>, order: SortOrder) -> bool {
+        let same_key = matches!(
+            (&self.key, sort),
+            (ShowCursorKey::CreatedAt(_), None)
+                | (ShowCursorKey::EventTime(_), Some(ShowSortKey::EventTime))
+                | (ShowCursorKey::Price(_), Some(ShowSortKey::Price))
+                | (ShowCursorKey::Sold(_), Some(ShowSortKey::Sold))
+        );
+        same_key && self.order == order
+    }
+}
+
+const SHOW_COLUMNS: &str = "chain_id, id, name, description, location, event_time, ticket_price, max_tickets, sold_tickets, is_active, organizer, created_at, version";
+
+/// 只更新 patch 中出现的列并递增 version（单独暴露便于在无数据库时检查 SQL）。
+/// `if_version` 为 If-Match 中的版本：给出时仅在当前 version 属于其中时更新；
+/// 修改 max_tickets 时同时要求不少于已售数量，避免与索引器的售票更新竞争。
+/// patch 为空时返回 None。
+pub fn build_show_update<'a>(
+    chain_id: i64,
+    id: &DbU256,
+    patch: &'a ShowPatch,
+    if_version: Option<&'a [i64]>,
+) -> Option<QueryBuilder<'a, Postgres>> {
+    if patch.is_empty() {
+        return None;
+    }
+    let mut qb: QueryBuilder<'a, Postgres> =
+        QueryBuilder::new("UPDATE shows SET ");
+    let mut set = qb.separated(", ");
+    if let Some(v) = &patch.name {
+        set.push("name = ").push_bind_unseparated(v);
+    }
+    if let Some(v) = &patch.description {
+        set.push("description = ").push_bind_unseparated(v);
+    }
+    if let Some(v) = &patch.location {
+        set.push("location = ").push_bind_unseparated(v);
+    }
+    if let Some(v) = &patch.event_time {
+        set.push("event_time = ").push_bind_unseparated(v.clone());
+    }
+    if let Some(v) = &patch.ticket_price {
+        set.push("ticket_price = ").push_bind_unseparated(v.clone());
+    }
+    if let Some(v) = &patch.max_tickets {
+        set.push("max_tickets = ").push_bind_unseparated(v.clone());
+    }
+    if let Some(v) = patch.is_active {
+        set.push("is_active = ").push_bind_unseparated(v);
+    }
+    set.push("version = version + 1");
+    set.push("updated_at = NOW()");
+    qb.push(" WHERE chain_id = ")
+        .push_bind(chain_id)
+        .push(" AND id = ")
+        .push_bind(id.clone())
+        .push(" AND deleted_at IS NULL");
+    if let Some(versions) = if_version {
+        qb.push(" AND version = ANY(").push_bind(versions).push(")");
+    }
+    if let Some(max) = &patch.max_tickets {
+        qb.push(" AND sold_tickets <= ").push_bind(max.clone());
+    }
+    qb.push(format!(" RETURNING {SHOW_COLUMNS}"));
+    Some(qb)
+}
+
+/// 按 [`build_show_update`] 条件更新，并在同一事务中写入审计记录（只含变化的字段）。
+/// 先锁住当前行，条件判断与审计的 before 基于同一版本；已软删除的演出视为不存在。
+/// patch 为空时只做条件检查，返回当前记录，不写审计。
+pub async fn update_show_fields(
+    pool: &PgPool,
+    chain_id: i64,
+    id: &DbU256,
+    patch: &ShowPatch,
+    if_version: Option<&[i64]>,
+    audit: &AuditContext,
+) -> Result<ShowPatchOutcome> {
+    let mut tx = pool.begin().await?;
+    let Some(current) = sqlx::query_as::<_, ShowDataRecord>(&format!(
+        "SELECT {SHOW_COLUMNS} FROM shows WHERE chain_id = $1 AND id = $2 AND deleted_at IS NULL FOR UPDATE;"
+    ))
+    .bind(chain_id)
+    .bind(id.clone())
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(ShowPatchOutcome::NotFound);
+    };
+    let Some(mut qb) = build_show_update(chain_id, id, patch, if_version)
+    else {
+        let version_ok =
+            if_version.is_none_or(|vs| vs.contains(&current.version));
+        return Ok(if version_ok {
+            ShowPatchOutcome::Updated(current)
+        } else {
+            ShowPatchOutcome::Rejected(current)
+        });
+    };
+    let Some(updated) = qb
+        .build_query_as::<ShowDataRecord>()
+        .fetch_optional(&mut *tx)
+        .await?
+    else {
+        return Ok(ShowPatchOutcome::Rejected(current));
+    };
+    let entry = NewAuditEntry::new(
+        Some(chain_id),
+        "show",
+        id.to_string(),
+        "update",
+        Some(&current),
+        Some(&updated),
+    );
+    insert_audit_tx(&mut tx, audit, &entry).await?;
+    tx.commit().await?;
+    Ok(ShowPatchOutcome::Updated(updated))
+}
+
+/// 软删除：标记 deleted_at 并递增 version，与审计记录同一事务提交。
+/// 不存在或已删除时返回 None。
+pub async fn soft_delete_show(
+    pool: &PgPool,
+    chain_id: i64,
+    id: &DbU256,
+    audit: &AuditContext,
+) -> Result<Option<ShowDataRecord>> {
+    let mut tx = pool.begin().await?;
+    let deleted = sqlx::query_as::<_, ShowDataRecord>(&format!(
+        r#"
+        UPDATE shows SET deleted_at = NOW(), version = version + 1, updated_at = NOW()
+        WHERE chain_id = $1 AND id = $2 AND deleted_at IS NULL
+        RETURNING {SHOW_COLUMNS};
+        "#
+    ))
+    .bind(chain_id)
+    .bind(id.clone())
+    .fetch_optional(&mut *tx)
+    .await?;
+    if let Some(rec) = &deleted {
+        let entry = NewAuditEntry::new(
+            Some(chain_id),
+            "show",
+            id.to_string(),
+            "delete",
+            Some(rec),
+            None,
+        );
+        insert_audit_tx(&mut tx, audit, &entry).await?;
+    }
+    tx.commit().await?;
+    tracing::debug!(chain_id, show_id = %id, found = deleted.is_some(), "Soft deleted show");
+    Ok(deleted)
+}
+
+/// 构造带过滤/排序/分页的 shows 查询（单独暴露便于在无数据库时检查 SQL）。
+/// 传入 `after` 时使用 keyset 分页（忽略 offset）；调用方需保证游标与 sort/order 一致。
+pub fn build_show_search<'a>(
+    filter: &'a ShowFilter,
+    sort: Option<ShowSortKey>,
+    order: SortOrder,
+    after: Option<&ShowCursor>,
+    limit: i64,
+    offset: i64,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb: QueryBuilder<'a, Postgres> = QueryBuilder::new(format!(
+        "SELECT {SHOW_COLUMNS} FROM shows WHERE deleted_at IS NULL"
+    ));
+    if let Some(chain_id) = filter.chain_id {
+        qb.push(" AND chain_id = ").push_bind(chain_id);
+    }
+    if let Some(active) = filter.is_active {
+        qb.push(" AND is_active = ").push_bind(active);
+    }
+    if let Some(org) = &filter.organizer {
+        qb.push(" AND lower(organizer) = lower(")
+            .push_bind(org)
+            .push(")");
+    }
+    if let Some(from) = &filter.event_time_from {
+        qb.push(" AND event_time >= ").push_bind(from.clone());
+    }
+    if let Some(to) = &filter.event_time_to {
+        qb.push(" AND event_time <= ").push_bind(to.clone());
+    }
+    if let Some(loc) = &filter.location {
+        qb.push(" AND location ILIKE ")
+            .push_bind(format!("%{}%", escape_like(loc)));
+    }
+    if let Some(min) = &filter.min_price {
+        qb.push(" AND ticket_price >= ").push_bind(min.clone());
+    }
+    if let Some(max) = &filter.max_price {
+        qb.push(" AND ticket_price <= ").push_bind(max.clone());
+    }
+    if let Some(q) = &filter.q {
+        qb.push(" AND search_vector @@ websearch_to_tsquery('simple', ")
+            .push_bind(q)
+            .push(")");
+    }
+
+    let (dir, cmp) = match order {
+        SortOrder::Asc => ("ASC", ">"),
+        SortOrder::Desc => ("DESC", "<"),
+    };
+    if let Some(c) = after {
+        let col = match &c.key {
+            ShowCursorKey::CreatedAt(_) => "created_at",
+            ShowCursorKey::EventTime(_) => "event_time",
+            ShowCursorKey::Price(_) => "ticket_price",
+            ShowCursorKey::Sold(_) => "sold_tickets",
+        };
+        qb.push(format!(" AND ({col}, id) {cmp} ("));
+        match &c.key {
+            ShowCursorKey::CreatedAt(ts) => qb.push_bind(*ts),
+            ShowCursorKey::EventTime(v)
+            | ShowCursorKey::Price(v)
+            | ShowCursorKey::Sold(v) => qb.push_bind(v.clone()),
+        };
+        qb.push(", ").push_bind(c.id.clone()).push(")");
+    }
+    match (sort, &filter.q) {
+        (Some(key), _) => {
+            let col = match key {
+                ShowSortKey::EventTime => "event_time",
+                ShowSortKey::Price => "ticket_price",
+                ShowSortKey::Sold => "sold_tickets",
+            };
+            qb.push(format!(" ORDER BY {col} {dir}, id {dir}"));
+        }
+        (None, Some(q)) => {
+            qb.push(" ORDER BY ts_rank(search_vector, websearch_to_tsquery('simple', ")
+                .push_bind(q)
+                .push(format!(")) {dir}, created_at DESC, id DESC"));
+        }
+        (None, None) => {
+            qb.push(format!(" ORDER BY created_at {dir}, id {dir}"));
+        }
+    }
+    qb.push(" LIMIT ").push_bind(limit);
+    qb.push(" OFFSET ")
+        .push_bind(if after.is_some() { 0 } else { offset });
+    qb
+}
+
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+pub async fn repo_search_shows(
+    pool: &PgPool,
+    filter: &ShowFilter,
+    sort: Option<ShowSortKey>,
+    order: SortOrder,
+    after: Option<&ShowCursor>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ShowDataRecord>> {
+    let mut qb = build_show_search(filter, sort, order, after, limit, offset);
+    let recs = qb
+        .build_query_as::<ShowDataRecord>()
+        .fetch_all(pool)
+        .await?;
+    tracing::debug!(count = recs.len(), ?filter, "Searched shows");
+    Ok(recs)
+}
+
+/// 按 id 批量查询某条链上的 show（GraphQL DataLoader 使用）；不存在的 id 不返回。
+pub async fn get_shows_by_ids(
+    pool: &PgPool,
+    chain_id: i64,
+    ids: &[DbU256],
+) -> Result<Vec<ShowDataRecord>> {
+    let recs = sqlx::query_as::<_, ShowDataRecord>(&format!(
+        "SELECT {SHOW_COLUMNS} FROM shows WHERE chain_id = $1 AND id = ANY($2) AND deleted_at IS NULL;"
+    ))
+    .bind(chain_id)
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+    tracing::debug!(keys = ids.len(), count = recs.len(), "Batch loaded shows");
+    Ok(recs)
+}
@@ -0,0 +1,379 @@
+use crate::utils::uint256::DbU256;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, QueryBuilder, prelude::FromRow};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    ToSchema,
+)]
+#[sqlx(type_name = "ticket_status", rename_all = "UPPERCASE")]
+#[serde(rename_all = "snake_case")]
+pub enum TicketStatus {
+    Valid,
+    Used,
+    Cancelled,
+}
+
+/// tickets 表：每张票的当前状态（由 TicketMinted/Transfer/TicketUsed/TicketCancelled 投影）。
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TicketRecord {
+    pub chain_id: i64,
+    pub token_id: DbU256,
+    pub event_id: DbU256,
+    pub owner: String,
+    pub seat_number: DbU256,
+    pub price: DbU256,
+    pub status: TicketStatus,
+    pub minted_tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// ticket_transfers 表：ERC721 Transfer 历史（含 mint，from 为零地址）。
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TicketTransferRecord {
+    pub chain_id: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub block_number: i64,
+    pub token_id: DbU256,
+    pub from_address: String,
+    pub to_address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTicket {
+    pub chain_id: i64,
+    pub token_id: DbU256,
+    pub event_id: DbU256,
+    pub owner: String,
+    pub seat_number: DbU256,
+    pub price: DbU256,
+    pub minted_tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTicketTransfer {
+    pub chain_id: i64,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub block_number: i64,
+    pub token_id: DbU256,
+    pub from_address: String,
+    pub to_address: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TicketFilter {
+    pub chain_id: Option<i64>,
+    pub owner: Option<String>,
+    pub event_id: Option<DbU256>,
+    pub status: Option<TicketStatus>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TransferFilter {
+    pub chain_id: Option<i64>,
+    pub token_id: Option<DbU256>,
+    /// 作为 from 或 to 出现的地址
+    pub address: Option<String>,
+}
+
+/// tickets 的 keyset 游标：按 token_id 升序
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TicketCursor {
+    pub token_id: DbU256,
+}
+
+/// ticket_transfers 的 keyset 游标：按 (block_number, log_index) 倒序
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferCursor {
+    pub block_number: i64,
+    pub log_index: i64,
+}
+
+const TICKET_COLUMNS: &str = "chain_id, token_id, event_id, owner, seat_number, price, status, minted_tx_hash, created_at, updated_at";
+const TRANSFER_COLUMNS: &str = "chain_id, tx_hash, log_index, block_number, token_id, from_address, to_address, created_at";
+
+/// Upsert 一张新铸造的票；重放同一事件时覆盖为链上值。
+/// 首次写入时同事务内累加 shows.sold_tickets，返回是否为新票。
+pub async fn upsert_minted_ticket(
+    pool: &PgPool,
+    rec: &NewTicket,
+) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+    let (inserted,): (bool,) = sqlx::query_as(
+        r#"
+        INSERT INTO tickets (chain_id, token_id, event_id, owner, seat_number, price, status, minted_tx_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, 'VALID', $7)
+        ON CONFLICT (chain_id, token_id) DO UPDATE
+        SET event_id = EXCLUDED.event_id,
+            seat_number = EXCLUDED.seat_number,
+            price = EXCLUDED.price,
+            minted_tx_hash = EXCLUDED.minted_tx_hash,
+            updated_at = NOW()
+        RETURNING (xmax = 0);
+        "#,
+    )
+    .bind(rec.chain_id)
+    .bind(rec.token_id.clone())
+    .bind(rec.event_id.clone())
+    .bind(&rec.owner)
+    .bind(rec.seat_number.clone())
+    .bind(rec.price.clone())
+    .bind(&rec.minted_tx_hash)
+    .fetch_one(&mut *tx)
+    .await?;
+    if inserted {
+        sqlx::query(
+            "UPDATE shows SET sold_tickets = sold_tickets + 1 WHERE chain_id = $1 AND id = $2;",
+        )
+        .bind(rec.chain_id)
+        .bind(rec.event_id.clone())
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    tracing::debug!(inserted, "Inserted/Updated tickets");
+    Ok(inserted)
+}
+
+/// 记录一次 Transfer 并同步 tickets.owner（同一事务）。重复日志被忽略。
+pub async fn record_ticket_transfer(
+    pool: &PgPool,
+    rec: &NewTicketTransfer,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO ticket_transfers (chain_id, tx_hash, log_index, block_number, token_id, from_address, to_address)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        ON CONFLICT (chain_id, tx_hash, log_index) DO NOTHING;
+        "#,
+    )
+    .bind(rec.chain_id)
+    .bind(&rec.tx_hash)
+    .bind(rec.log_index)
+    .bind(rec.block_number)
+    .bind(rec.token_id.clone())
+    .bind(&rec.from_address)
+    .bind(&rec.to_address)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if inserted > 0 {
+        sqlx::query(
+            "UPDATE tickets SET owner = $3, updated_at = NOW() WHERE chain_id = $1 AND token_id = $2;",
+        )
+        .bind(rec.chain_id)
+        .bind(rec.token_id.clone())
+        .bind(&rec.to_address)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    tracing::debug!(inserted, "Recorded ticket transfer");
+    Ok(())
+}
+
+pub async fn update_ticket_status(
+    pool: &PgPool,
+    chain_id: i64,
+    token_id: DbU256,
+    status: TicketStatus,
+) -> Result<bool> {
+    let res = sqlx::query(
+        "UPDATE tickets SET status = $3, updated_at = NOW() WHERE chain_id = $1 AND token_id = $2;",
+    )
+    .bind(chain_id)
+    .bind(token_id)
+    .bind(status)
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected() > 0)
+}
+
+pub async fn get_ticket_by_id(
+    pool: &PgPool,
+    chain_id: i64,
+    token_id: DbU256,
+) -> Result<Option<TicketRecord>> {
+    let rec = sqlx::query_as::<_, TicketRecord>(&format!(
+        "SELECT {TICKET_COLUMNS} FROM tickets WHERE chain_id = $1 AND token_id = $2;"
+    ))
+    .bind(chain_id)
+    .bind(token_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(rec)
+}
+
+/// 构造 tickets 列表查询（token_id 升序 keyset 分页）。
+pub fn build_ticket_list<'a>(
+    filter: &'a TicketFilter,
+    after: Option<&TicketCursor>,
+    limit: i64,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb = QueryBuilder::new(format!(
+        "SELECT {TICKET_COLUMNS} FROM tickets WHERE TRUE"
+    ));
+    if let Some(chain_id) = filter.chain_id {
+        qb.push(" AND chain_id = ").push_bind(chain_id);
+    }
+    if let Some(owner) = &filter.owner {
+        qb.push(" AND lower(owner) = lower(")
+            .push_bind(owner)
+            .push(")");
+    }
+    if let Some(event_id) = &filter.event_id {
+        qb.push(" AND event_id = ").push_bind(event_id.clone());
+    }
+    if let Some(status) = filter.status {
+        qb.push(" AND status = ").push_bind(status);
+    }
+    if let Some(c) = after {
+        qb.push(" AND token_id > ").push_bind(c.token_id.clone());
+    }
+    qb.push(" ORDER BY token_id ASC LIMIT ").push_bind(limit);
+    qb
+}
+
+pub async fn list_tickets(
+    pool: &PgPool,
+    filter: &TicketFilter,
+    after: Option<&TicketCursor>,
+    limit: i64,
+) -> Result<Vec<TicketRecord>> {
+    let recs = build_ticket_list(filter, after, limit)
+        .build_query_as::<TicketRecord>()
+        .fetch_all(pool)
+        .await?;
+    tracing::debug!(count = recs.len(), ?filter, "Listed tickets");
+    Ok(recs)
+}
+
+/// 构造转移记录查询（(block_number, log_index) 倒序 keyset 分页）。
+pub fn build_transfer_list<'a>(
+    filter: &'a TransferFilter,
+    after: Option<&TransferCursor>,
+    limit: i64,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb = QueryBuilder::new(format!(
+        "SELECT {TRANSFER_COLUMNS} FROM ticket_transfers WHERE TRUE"
+    ));
+    if let Some(chain_id) = filter.chain_id {
+        qb.push(" AND chain_id = ").push_bind(chain_id);
+    }
+    if let Some(token_id) = &filter.token_id {
+        qb.push(" AND token_id = ").push_bind(token_id.clone());
+    }
+    if let Some(addr) = &filter.address {
+        qb.push(" AND (lower(from_address) = lower(")
+            .push_bind(addr)
+            .push(") OR lower(to_address) = lower(")
+            .push_bind(addr)
+            .push("))");
+    }
+    if let Some(c) = after {
+        qb.push(" AND (block_number, log_index) < (")
+            .push_bind(c.block_number)
+            .push(", ")
+            .push_bind(c.log_index)
+            .push(")");
+    }
+    qb.push(" ORDER BY block_number DESC, log_index DESC LIMIT ")
+        .push_bind(limit);
+    qb
+}
+
+pub async fn list_ticket_transfers(
+    pool: &PgPool,
+    filter: &TransferFilter,
+    after: Option<&TransferCursor>,
+    limit: i64,
+) -> Result<Vec<TicketTransferRecord>> {
+    let recs = build_transfer_list(filter, after, limit)
+        .build_query_as::<TicketTransferRecord>()
+        .fetch_all(pool)
+        .await?;
+    tracing::debug!(count = recs.len(), ?filter, "Listed ticket transfers");
+    Ok(recs)
+}
+
+pub async fn get_tickets_by_ids(
+    pool: &PgPool,
+    chain_id: i64,
+    token_ids: &[DbU256],
+) -> Result<Vec<TicketRecord>> {
+    let recs = sqlx::query_as::<_, TicketRecord>(&format!(
+        "SELECT {TICKET_COLUMNS} FROM tickets WHERE chain_id = $1 AND token_id = ANY($2);"
+    ))
+    .bind(chain_id)
+    .bind(token_ids)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
+
+/// 每个 show 取前 limit 张票（token_id 升序），一次查询覆盖多个 show。
+pub async fn list_tickets_for_shows(
+    pool: &PgPool,
+    chain_id: i64,
+    show_ids: &[DbU256],
+    limit: i64,
+) -> Result<Vec<TicketRecord>> {
+    let recs = sqlx::query_as::<_, TicketRecord>(&format!(
+        r#"
+        SELECT {TICKET_COLUMNS} FROM (
+            SELECT *, row_number() OVER (PARTITION BY event_id ORDER BY token_id) AS rn
+            FROM tickets WHERE chain_id = $1 AND event_id = ANY($2)
+        ) t
+        WHERE rn <= $3
+        ORDER BY event_id, token_id;
+        "#
+    ))
+    .bind(chain_id)
+    .bind(show_ids)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
+
+/// 每个持有人取前 limit 张票（token_id 升序）；地址不区分大小写。
+pub async fn list_tickets_for_owners(
+    pool: &PgPool,
+    chain_id: i64,
+    owners: &[String],
+    limit: i64,
+) -> Result<Vec<TicketRecord>> {
+    let lowered: Vec<String> =
+        owners.iter().map(|o| o.to_lowercase()).collect();
+    let recs = sqlx::query_as::<_, TicketRecord>(&format!(
+        r#"
+        SELECT {TICKET_COLUMNS} FROM (
+            SELECT *, row_number() OVER (PARTITION BY lower(owner) ORDER BY token_id) AS rn
+            FROM tickets WHERE chain_id = $1 AND lower(owner) = ANY($2)
+        ) t
+        WHERE rn <= $3
+        ORDER BY lower(owner), token_id;
+        "#
+    ))
+    .bind(chain_id)
+    .bind(&lowered)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
@@ -0,0 +1,381 @@
+//! 仓储接口：API handler、后台 worker 与索引器通过 [`Repos`] 读写 shows / tickets / 挂单 / DID、
+//! 索引事件、createShow 任务、webhook 与审计日志，不直接持有连接池。生产环境使用 [`PgRepo`]，测试使用 [`MemoryRepo`]（无需数据库）。
+use crate::repo::{
+    audit_repo::{AuditContext, AuditCursor, AuditFilter, AuditRecord},
+    checkpoint_repo::CheckpointRecord,
+    did_repo::{AddressDidRecord, DidUpdate},
+    listing_repo::{
+        ListingCursor, ListingFilter, ListingRecord, ListingStatus, NewListing,
+    },
+    memory::MemoryRepo,
+    postgres::PgRepo,
+    show_job_repo::{NewShowCreateJob, ShowCreateJobRecord},
+    show_repo::{
+        ShowCreatedDetailRecord, ShowCreatedRecord, ShowCursor, ShowDataRecord,
+        ShowFilter, ShowPatch, ShowPatchOutcome, ShowSortKey, SortOrder,
+    },
+    ticket_repo::{
+        NewTicket, NewTicketTransfer, TicketCursor, TicketFilter, TicketRecord,
+        TicketStatus, TicketTransferRecord, TransferCursor, TransferFilter,
+    },
+    webhook_repo::{
+        DeliveryCursor, DeliveryFilter, DueDelivery, NewWebhook,
+        WebhookDeliveryRecord, WebhookRecord,
+    },
+};
+use crate::utils::uint256::DbU256;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use sqlx::PgPool;
+use std::{fmt, sync::Arc};
+
+/// shows 投影（含 ShowCreated 事件与详情）的读写
+#[async_trait]
+pub trait ShowRepo: Send + Sync + fmt::Debug {
+    /// ShowCreated：写入创建事件、详情与 shows 行，返回写入后的 shows 行
+    async fn upsert_show_all(
+        &self,
+        basic: &ShowCreatedRecord,
+        detail: &ShowCreatedDetailRecord,
+        data: &ShowDataRecord,
+    ) -> Result<ShowDataRecord>;
+    /// 状态/更新事件：用链上快照刷新详情与 shows 行（sold_tickets 除外），返回写入后的 shows 行
+    async fn upsert_show_snapshot(
+        &self,
+        detail: &ShowCreatedDetailRecord,
+        data: &ShowDataRecord,
+    ) -> Result<ShowDataRecord>;
+    /// 按 id 查询；已软删除的演出返回 None
+    async fn get_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+    ) -> Result<Option<ShowDataRecord>>;
+    /// 过滤/排序/分页查询，语义同 [`crate::repo::show_repo::build_show_search`]
+    async fn search_shows(
+        &self,
+        filter: &ShowFilter,
+        sort: Option<ShowSortKey>,
+        order: SortOrder,
+        after: Option<&ShowCursor>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ShowDataRecord>>;
+    /// 条件更新并写审计，语义同 [`crate::repo::show_repo::update_show_fields`]
+    async fn update_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+        patch: &ShowPatch,
+        if_version: Option<&[i64]>,
+        audit: &AuditContext,
+    ) -> Result<ShowPatchOutcome>;
+    /// 软删除并写审计；不存在或已删除时返回 None
+    async fn delete_show(
+        &self,
+        chain_id: i64,
+        id: &DbU256,
+        audit: &AuditContext,
+    ) -> Result<Option<ShowDataRecord>>;
+    /// 按 id 批量查询（GraphQL DataLoader 使用）；不存在或已删除的 id 不返回
+    async fn get_shows_by_ids(
+        &self,
+        chain_id: i64,
+        ids: &[DbU256],
+    ) -> Result<Vec<ShowDataRecord>>;
+}
+
+/// tickets / ticket_transfers 投影的读写
+#[async_trait]
+pub trait TicketRepo: Send + Sync + fmt::Debug {
+    /// 新铸造的票；首次写入时累加对应 show 的 sold_tickets，返回是否为新票
+    async fn upsert_minted_ticket(&self, rec: &NewTicket) -> Result<bool>;
+    /// 记录 Transfer 并同步 owner；重复日志被忽略
+    async fn record_transfer(&self, rec: &NewTicketTransfer) -> Result<()>;
+    async fn update_status(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+        status: TicketStatus,
+    ) -> Result<bool>;
+    async fn get_ticket(
+        &self,
+        chain_id: i64,
+        token_id: &DbU256,
+    ) -> Result<Option<TicketRecord>>;
+    /// token_id 升序 keyset 分页
+    async fn list_tickets(
+        &self,
+        filter: &TicketFilter,
+        after: Option<&TicketCursor>,
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>>;
+    /// (block_number, log_index) 倒序 keyset 分页
+    async fn list_transfers(
+        &self,
+        filter: &TransferFilter,
+        after: Option<&TransferCursor>,
+        limit: i64,
+    ) -> Result<Vec<TicketTransferRecord>>;
+    /// 按 token_id 批量查询；不存在的 id 不返回
+    async fn get_tickets_by_ids(
+        &self,
+        chain_id: i64,
+        token_ids: &[DbU256],
+    ) -> Result<Vec<TicketRecord>>;
+    /// 每个 show 取前 limit 张票（token_id 升序）
+    async fn list_tickets_for_shows(
+        &self,
+        chain_id: i64,
+        show_ids: &[DbU256],
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>>;
+    /// 每个持有人取前 limit 张票（token_id 升序）；地址不区分大小写
+    async fn list_tickets_for_owners(
+        &self,
+        chain_id: i64,
+        owners: &[String],
+        limit: i64,
+    ) -> Result<Vec<TicketRecord>>;
+}
+
+/// listings 投影（Marketplace 事件）的读写
+#[async_trait]
+pub trait ListingRepo: Send + Sync + fmt::Debug {
+    /// ListingCreated：插入或以链上值覆盖，返回最新行
+    async fn upsert_listing(&self, rec: &NewListing) -> Result<ListingRecord>;
+    /// ListingUpdated：更新价格与过期时间；挂单不存在时返回 None
+    async fn update_listing_price(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+        price: &DbU256,
+        eth_price: &DbU256,
+        expires_at: &DbU256,
+    ) -> Result<Option<ListingRecord>>;
+    /// ListingCancelled / TicketSold：变更状态（成交时记录买家）；挂单不存在时返回 None
+    async fn set_listing_status(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+        status: ListingStatus,
+        buyer: Option<&str>,
+    ) -> Result<Option<ListingRecord>>;
+    async fn get_listing(
+        &self,
+        chain_id: i64,
+        listing_id: &DbU256,
+    ) -> Result<Option<ListingRecord>>;
+    /// listing_id 升序 keyset 分页
+    async fn list_listings(
+        &self,
+        filter: &ListingFilter,
+        after: Option<&ListingCursor>,
+        limit: i64,
+    ) -> Result<Vec<ListingRecord>>;
+    /// 若干票当前 ACTIVE 的挂单，每张票取 listing_id 最大的一条
+    async fn get_active_listings_by_tokens(
+        &self,
+        chain_id: i64,
+        token_ids: &[DbU256],
+    ) -> Result<Vec<ListingRecord>>;
+    /// 每个卖家取前 limit 条挂单（listing_id 升序）；地址不区分大小写
+    async fn list_listings_for_sellers(
+        &self,
+        chain_id: i64,
+        sellers: &[String],
+        limit: i64,
+    ) -> Result<Vec<ListingRecord>>;
+}
+
+/// dids / did_addresses 投影（DIDRegistry 事件）的读写
+#[async_trait]
+pub trait DidRepo: Send + Sync + fmt::Debug {
+    /// DIDRegistered：写入 DID，并把 controller 绑定到该 DID
+    async fn upsert_did(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        did: &str,
+        controller: &str,
+        cid: &str,
+    ) -> Result<()>;
+    async fn bind_did_address(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        address: &str,
+    ) -> Result<()>;
+    async fn unbind_did_address(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        address: &str,
+    ) -> Result<()>;
+    /// 局部更新；未知 DID 时返回 false
+    async fn update_did(
+        &self,
+        chain_id: i64,
+        did_hash: &str,
+        update: &DidUpdate,
+    ) -> Result<bool>;
+    /// 按地址批量解析 DID（地址不区分大小写，返回行中的 address 为小写）
+    async fn get_dids_by_addresses(
+        &self,
+        chain_id: i64,
+        addresses: &[String],
+    ) -> Result<Vec<AddressDidRecord>>;
+}
+
+/// 索引事件的簿记：各合约的检查点，以及需要投递给 webhook 订阅方的事件
+#[async_trait]
+pub trait EventRepo: Send + Sync + fmt::Debug {
+    /// 推进检查点到 (block, log_index)；同一地址只前进不后退，地址变化时覆盖
+    async fn record_checkpoint(
+        &self,
+        chain_id: i64,
+        contract: &str,
+        address: &str,
+        block: i64,
+        log_index: i64,
+    ) -> Result<()>;
+    async fn list_checkpoints(&self) -> Result<Vec<CheckpointRecord>>;
+    /// 为订阅了 event_type 的 webhook 排队投递，返回排队条数
+    async fn enqueue_webhook_event(
+        &self,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<u64>;
+}
+
+/// POST /show 创建的 createShow 任务（show_create_jobs）
+#[async_trait]
+pub trait ShowJobRepo: Send + Sync + fmt::Debug {
+    /// 新建 job 并写审计
+    async fn insert_job(
+        &self,
+        job: &NewShowCreateJob,
+        audit: &AuditContext,
+    ) -> Result<ShowCreateJobRecord>;
+    async fn get_job(&self, id: i64) -> Result<Option<ShowCreateJobRecord>>;
+    /// 进程重启后需要继续处理的 job：PENDING / SUBMITTING / SUBMITTED
+    async fn list_open_jobs(&self) -> Result<Vec<i64>>;
+    /// PENDING -> SUBMITTING 并记录已签名交易；job 已不是 PENDING 时返回 false
+    async fn claim_job(
+        &self,
+        id: i64,
+        tx_hash: &str,
+        sender: &str,
+        nonce: i64,
+    ) -> Result<bool>;
+    /// SUBMITTING -> SUBMITTED；该交易的 ShowCreated 已索引时直接 CONFIRMED
+    async fn mark_submitted(&self, id: i64) -> Result<()>;
+    /// 标记 FAILED；已 CONFIRMED 的 job 不会被覆盖
+    async fn mark_failed(&self, id: i64, error: &str) -> Result<()>;
+    /// 索引到 ShowCreated 后按交易哈希回填 show_id，返回是否命中某个 job
+    async fn confirm_by_tx(
+        &self,
+        tx_hash: &str,
+        show_id: &DbU256,
+    ) -> Result<bool>;
+}
+
+/// webhook 订阅与投递记录
+#[async_trait]
+pub trait WebhookRepo: Send + Sync + fmt::Debug {
+    async fn insert_webhook(&self, rec: &NewWebhook) -> Result<WebhookRecord>;
+    async fn get_webhook(&self, id: i64) -> Result<Option<WebhookRecord>>;
+    /// id 升序
+    async fn list_webhooks(&self) -> Result<Vec<WebhookRecord>>;
+    /// 删除订阅及其投递记录，返回是否存在
+    async fn delete_webhook(&self, id: i64) -> Result<bool>;
+    /// 某个订阅的投递日志，id 倒序 keyset 分页
+    async fn list_deliveries(
+        &self,
+        webhook_id: i64,
+        filter: &DeliveryFilter,
+        after: Option<&DeliveryCursor>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryRecord>>;
+    /// 领取最多 limit 条到期的投递：attempts + 1，并把下次尝试时间推后 lease_secs 作为租约
+    async fn claim_due_deliveries(
+        &self,
+        limit: i64,
+        lease_secs: f64,
+    ) -> Result<Vec<DueDelivery>>;
+    async fn mark_delivery_succeeded(
+        &self,
+        id: i64,
+        status_code: i32,
+    ) -> Result<()>;
+    /// 记录一次失败；next_attempt_at 为 None 时标记为最终失败
+    async fn mark_delivery_failed(
+        &self,
+        id: i64,
+        status_code: Option<i32>,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+}
+
+/// 审计日志的查询；写入发生在各写操作内部，与写操作同时提交
+#[async_trait]
+pub trait AuditRepo: Send + Sync + fmt::Debug {
+    /// id 倒序 keyset 分页
+    async fn list_audit(
+        &self,
+        filter: &AuditFilter,
+        after: Option<&AuditCursor>,
+        limit: i64,
+    ) -> Result<Vec<AuditRecord>>;
+}
+
+/// 注入到 `AppState`、`IndexerContext` 与后台 worker 的仓储集合
+#[derive(Debug, Clone)]
+pub struct Repos {
+    pub shows: Arc<dyn ShowRepo>,
+    pub tickets: Arc<dyn TicketRepo>,
+    pub listings: Arc<dyn ListingRepo>,
+    pub dids: Arc<dyn DidRepo>,
+    pub events: Arc<dyn EventRepo>,
+    pub jobs: Arc<dyn ShowJobRepo>,
+    pub webhooks: Arc<dyn WebhookRepo>,
+    pub audit: Arc<dyn AuditRepo>,
+}
+
+impl Repos {
+    pub fn postgres(pool: PgPool) -> Self {
+        Self::from_backend(Arc::new(PgRepo::new(pool)))
+    }
+
+    /// 进程内实现；返回的 [`MemoryRepo`] 与 Repos 共享数据，测试可直接检查写入结果
+    pub fn memory() -> (Self, Arc<MemoryRepo>) {
+        let repo = Arc::new(MemoryRepo::default());
+        (Self::from_backend(repo.clone()), repo)
+    }
+
+    fn from_backend<R>(repo: Arc<R>) -> Self
+    where
+        R: ShowRepo
+            + TicketRepo
+            + ListingRepo
+            + DidRepo
+            + EventRepo
+            + ShowJobRepo
+            + WebhookRepo
+            + AuditRepo
+            + 'static,
+    {
+        Self {
+            shows: repo.clone(),
+            tickets: repo.clone(),
+            listings: repo.clone(),
+            dids: repo.clone(),
+            events: repo.clone(),
+            jobs: repo.clone(),
+            webhooks: repo.clone(),
+            audit: repo,
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, QueryBuilder, prelude::FromRow};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    ToSchema,
+)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "UPPERCASE")]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+/// webhooks 表：一条订阅。secret 仅在创建时返回一次。
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct WebhookRecord {
+    pub id: i64,
+    pub url: String,
+    /// 为空表示订阅全部事件类型
+    pub event_types: Vec<String>,
+    #[serde(skip_serializing)]
+    #[schema(ignore)]
+    pub secret: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWebhook {
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub secret: String,
+}
+
+/// webhook_deliveries 表：每个 (事件, 订阅) 一行，记录重试进度与最后一次响应。
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct WebhookDeliveryRecord {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// worker 领取到的一次投递（已附带目标 url 与签名密钥）
+#[derive(Debug, Clone, FromRow)]
+pub struct DueDelivery {
+    pub id: i64,
+    pub webhook_id: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// 含本次在内的尝试次数
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+    pub url: String,
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryFilter {
+    pub status: Option<DeliveryStatus>,
+}
+
+/// 投递日志的 keyset 游标：按 id 倒序
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryCursor {
+    pub id: i64,
+}
+
+const WEBHOOK_COLUMNS: &str =
+    "id, url, event_types, secret, active, created_at, updated_at";
+const DELIVERY_COLUMNS: &str = "id, webhook_id, event_type, payload, status, attempts, next_attempt_at, last_status_code, last_error, delivered_at, created_at, updated_at";
+
+pub async fn insert_webhook(
+    pool: &PgPool,
+    rec: &NewWebhook,
+) -> Result<WebhookRecord> {
+    let row = sqlx::query_as::<_, WebhookRecord>(&format!(
+        r#"
+        INSERT INTO webhooks (url, event_types, secret)
+        VALUES ($1, $2, $3)
+        RETURNING {WEBHOOK_COLUMNS};
+        "#
+    ))
+    .bind(&rec.url)
+    .bind(&rec.event_types)
+    .bind(&rec.secret)
+    .fetch_one(pool)
+    .await?;
+    tracing::debug!(webhook_id = row.id, "Inserted webhooks");
+    Ok(row)
+}
+
+pub async fn get_webhook(
+    pool: &PgPool,
+    id: i64,
+) -> Result<Option<WebhookRecord>> {
+    let rec = sqlx::query_as::<_, WebhookRecord>(&format!(
+        "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = $1;"
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(rec)
+}
+
+pub async fn list_webhooks(pool: &PgPool) -> Result<Vec<WebhookRecord>> {
+    let recs = sqlx::query_as::<_, WebhookRecord>(&format!(
+        "SELECT {WEBHOOK_COLUMNS} FROM webhooks ORDER BY id ASC;"
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
+
+/// 删除订阅（级联删除其投递记录），返回是否存在
+pub async fn delete_webhook(pool: &PgPool, id: i64) -> Result<bool> {
+    let res = sqlx::query("DELETE FROM webhooks WHERE id = $1;")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// 为所有订阅了 event_type 的有效 webhook 各插入一条待投递记录，返回插入条数。
+pub async fn enqueue_webhook_deliveries(
+    pool: &PgPool,
+    event_type: &str,
+    payload: &serde_json::Value,
+) -> Result<u64> {
+    let res = sqlx::query(
+        r#"
+        INSERT INTO webhook_deliveries (webhook_id, event_type, payload)
+        SELECT id, $1, $2 FROM webhooks
+        WHERE active AND (cardinality(event_types) = 0 OR $1 = ANY(event_types));
+        "#,
+    )
+    .bind(event_type)
+    .bind(payload)
+    .execute(pool)
+    .await?;
+    Ok(res.rows_affected())
+}
+
+/// 领取最多 limit 条到期的投递：attempts + 1，并把 next_attempt_at 推后 lease_secs 作为租约，
+/// 避免发送期间被其他实例重复领取（SKIP LOCKED 支持多实例并行）。
+pub async fn claim_due_deliveries(
+    pool: &PgPool,
+    limit: i64,
+    lease_secs: f64,
+) -> Result<Vec<DueDelivery>> {
+    let recs = sqlx::query_as::<_, DueDelivery>(
+        r#"
+        WITH due AS (
+            SELECT id FROM webhook_deliveries
+            WHERE status = 'PENDING' AND next_attempt_at <= NOW()
+            ORDER BY next_attempt_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        ), claimed AS (
+            UPDATE webhook_deliveries d
+            SET attempts = d.attempts + 1,
+                next_attempt_at = NOW() + make_interval(secs => $2),
+                updated_at = NOW()
+            FROM due WHERE d.id = due.id
+            RETURNING d.id, d.webhook_id, d.event_type, d.payload, d.attempts, d.created_at
+        )
+        SELECT c.id, c.webhook_id, c.event_type, c.payload, c.attempts, c.created_at, w.url, w.secret
+        FROM claimed c JOIN webhooks w ON w.id = c.webhook_id;
+        "#,
+    )
+    .bind(limit)
+    .bind(lease_secs)
+    .fetch_all(pool)
+    .await?;
+    Ok(recs)
+}
+
+pub async fn mark_delivery_succeeded(
+    pool: &PgPool,
+    id: i64,
+    status_code: i32,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = 'SUCCEEDED', last_status_code = $2, last_error = NULL,
+            delivered_at = NOW(), updated_at = NOW()
+        WHERE id = $1;
+        "#,
+    )
+    .bind(id)
+    .bind(status_code)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 记录一次失败；next_attempt_at 为 None 时标记为最终失败。
+pub async fn mark_delivery_failed(
+    pool: &PgPool,
+    id: i64,
+    status_code: Option<i32>,
+    error: &str,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE webhook_deliveries
+        SET status = CASE WHEN $4::TIMESTAMPTZ IS NULL THEN 'FAILED'::WEBHOOK_DELIVERY_STATUS ELSE status END,
+            next_attempt_at = COALESCE($4, next_attempt_at),
+            last_status_code = $2, last_error = $3, updated_at = NOW()
+        WHERE id = $1;
+        "#,
+    )
+    .bind(id)
+    .bind(status_code)
+    .bind(error)
+    .bind(next_attempt_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 构造投递日志查询（id 倒序 keyset 分页）。
+pub fn build_delivery_list<'a>(
+    webhook_id: i64,
+    filter: &'a DeliveryFilter,
+    after: Option<&DeliveryCursor>,
+    limit: i64,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb = QueryBuilder::new(format!(
+        "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE webhook_id = "
+    ));
+    qb.push_bind(webhook_id);
+    if let Some(status) = filter.status {
+        qb.push(" AND status = ").push_bind(status);
+    }
+    if let Some(c) = after {
+        qb.push(" AND id < ").push_bind(c.id);
+    }
+    qb.push(" ORDER BY id DESC LIMIT ").push_bind(limit);
+    qb
+}
+
+pub async fn list_webhook_deliveries(
+    pool: &PgPool,
+    webhook_id: i64,
+    filter: &DeliveryFilter,
+    after: Option<&DeliveryCursor>,
+    limit: i64,
+) -> Result<Vec<WebhookDeliveryRecord>> {
+    let recs = build_delivery_list(webhook_id, filter, after, limit)
+        .build_query_as::<WebhookDeliveryRecord>()
+        .fetch_all(pool)
+        .await?;
+    tracing::debug!(
+        count = recs.len(),
+        webhook_id,
+        "Listed webhook deliveries"
+    );
+    Ok(recs)
+}
@@ -0,0 +1,79 @@
+//! 进程级关闭协调：main 收到 Ctrl+C / SIGTERM 后触发，API、索引器与后台 worker 各自持有
+//! [`Shutdown`] 副本，停止接收新工作、完成手头的请求、日志或任务后退出。
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// 关闭信号的触发端（main 持有）
+#[derive(Debug)]
+pub struct ShutdownTrigger {
+    tx: watch::Sender<bool>,
+}
+
+/// 关闭信号的监听端；可随意 clone
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    rx: watch::Receiver<bool>,
+    /// 仅 `never()` 使用：持有发送端，避免 wait 因通道关闭而返回
+    _keep: Option<Arc<watch::Sender<bool>>>,
+}
+
+pub fn channel() -> (ShutdownTrigger, Shutdown) {
+    let (tx, rx) = watch::channel(false);
+    (ShutdownTrigger { tx }, Shutdown { rx, _keep: None })
+}
+
+impl ShutdownTrigger {
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+}
+
+impl Shutdown {
+    /// 永不触发的信号（测试或独立运行某个组件时使用）
+    pub fn never() -> Self {
+        let (tx, rx) = watch::channel(false);
+        Self {
+            rx,
+            _keep: Some(Arc::new(tx)),
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    /// 等待关闭信号；触发端被丢弃同样视为关闭
+    pub async fn wait(&self) {
+        let mut rx = self.rx.clone();
+        let _ = rx.wait_for(|v| *v).await;
+    }
+}
+
+/// 等待 Ctrl+C，Unix 上同时等待 SIGTERM（容器停止时发送）
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = ?e, "Failed to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut s) => {
+                s.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received Ctrl+C, shutting down"),
+        _ = terminate => tracing::info!("Received SIGTERM, shutting down"),
+    }
+}
@@ -9,7 +9,8 @@ use chrono::Utc;
 use eyre::Result;
 use std::str::FromStr;
 
-pub async fn seed_mock(db: &Db) -> Result<()> {
+/// 向 `chain_id` 对应链的表中写入三条演示 show。
+pub async fn seed_mock(db: &Db, chain_id: i64) -> Result<()> {
     let shows = vec![
         (
             "1",
@@ -77,6 +78,7 @@ pub async fn seed_mock(db: &Db) -> Result<()> {
         let sold = DbU256(U256::from(sold));
 
         let basic = ShowCreatedRecord {
+            chain_id,
             show_id: id.clone(),
             tx_hash: None,
             block_number: None,
@@ -85,6 +87,7 @@ pub async fn seed_mock(db: &Db) -> Result<()> {
             created_at: Utc::now(),
         };
         let detail = ShowCreatedDetailRecord {
+            chain_id,
             show_id: id.clone(),
             start_time: start.clone(),
             end_time: end.clone(),
@@ -105,6 +108,7 @@ pub async fn seed_mock(db: &Db) -> Result<()> {
             created_at: Utc::now(),
         };
         let data = ShowDataRecord {
+            chain_id,
             id: id.clone(),
             name: name.to_string(),
             description: desc.to_string(),
@@ -116,6 +120,7 @@ pub async fn seed_mock(db: &Db) -> Result<()> {
             is_active: active,
             organizer: org.to_string(),
             created_at: Utc::now(),
+            version: 0,
         };
         crate::repo::show_repo::upsert_show_all(
             db.pool(),
@@ -124,7 +129,7 @@ pub async fn seed_mock(db: &Db) -> Result<()> {
             &data,
         )
         .await?;
-        tracing::info!(show_id = %id.to_string(), name, "Seeded show");
+        tracing::info!(chain_id, show_id = %id.to_string(), name, "Seeded show");
     }
     tracing::info!("Mock data seeding complete.");
     Ok(())
@@ -12,7 +12,7 @@ use sqlx::{
 use std::{fmt, str::FromStr};
 
 /// 数据库存储用的本地包装类型，解决 orphan rule：为本地类型实现外部 trait
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DbU256(pub U256);
 
 impl From<u64> for DbU256 {
@@ -41,6 +41,23 @@ impl<'de> serde::Deserialize<'de> for DbU256 {
     }
 }
 
+// OpenAPI：与 serde 一致，对外表现为十进制字符串（输入也接受 0x 十六进制）
+impl utoipa::PartialSchema for DbU256 {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, Type};
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .pattern(Some("^([0-9]+|0[xX][0-9a-fA-F]+)$"))
+            .description(Some(
+                "uint256, serialized as a decimal string; 0x-prefixed hex is accepted on input",
+            ))
+            .examples([serde_json::json!("1000")])
+            .into()
+    }
+}
+
+impl utoipa::ToSchema for DbU256 {}
+
 impl fmt::Display for DbU256 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
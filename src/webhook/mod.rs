@@ -0,0 +1,105 @@
+pub mod target;
+pub mod worker;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{fmt, str::FromStr, time::Duration};
+use utoipa::ToSchema;
+
+/// 签名头：`t=<unix 秒>,v1=<hex(HMAC-SHA256(secret, "<t>.<body>"))>`
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+pub const EVENT_HEADER: &str = "x-webhook-event";
+pub const DELIVERY_HEADER: &str = "x-webhook-delivery";
+
+/// 可订阅的 webhook 事件类型（比实时事件更细：show 的状态变化分别出类型）
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    ShowCreated,
+    ShowUpdated,
+    ShowActivated,
+    ShowCancelled,
+    ShowEnded,
+    TicketSold,
+    TicketCheckedIn,
+    ListingChanged,
+}
+
+impl WebhookEventType {
+    pub const ALL: [WebhookEventType; 8] = [
+        WebhookEventType::ShowCreated,
+        WebhookEventType::ShowUpdated,
+        WebhookEventType::ShowActivated,
+        WebhookEventType::ShowCancelled,
+        WebhookEventType::ShowEnded,
+        WebhookEventType::TicketSold,
+        WebhookEventType::TicketCheckedIn,
+        WebhookEventType::ListingChanged,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEventType::ShowCreated => "show_created",
+            WebhookEventType::ShowUpdated => "show_updated",
+            WebhookEventType::ShowActivated => "show_activated",
+            WebhookEventType::ShowCancelled => "show_cancelled",
+            WebhookEventType::ShowEnded => "show_ended",
+            WebhookEventType::TicketSold => "ticket_sold",
+            WebhookEventType::TicketCheckedIn => "ticket_checked_in",
+            WebhookEventType::ListingChanged => "listing_changed",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WebhookEventType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or_else(|| format!("unknown webhook event type: {s}"))
+    }
+}
+
+/// 发送给订阅方的请求体；重试时内容不变，订阅方可按 id 去重。
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookPayload {
+    /// 投递 id（webhook_deliveries.id）
+    pub id: i64,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+/// hex(HMAC-SHA256(secret, data))
+pub fn hmac_sha256_hex(secret: &[u8], data: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+        .expect("HMAC accepts keys of any length");
+    mac.update(data);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 生成签名头的值。时间戳参与签名，订阅方应拒绝过旧的请求以防重放。
+pub fn signature_header(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut signed = format!("{timestamp}.").into_bytes();
+    signed.extend_from_slice(body);
+    let sig = hmac_sha256_hex(secret.as_bytes(), &signed);
+    format!("t={timestamp},v1={sig}")
+}
+
+/// 第 attempt 次（从 1 开始）失败后的重试间隔：base * 2^(attempt-1)，上限 max。
+pub fn retry_delay(attempt: u32, base: Duration, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    base.saturating_mul(factor).min(max)
+}
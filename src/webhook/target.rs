@@ -0,0 +1,98 @@
+//! webhook 目标地址检查：拒绝解析到内网、回环、链路本地等地址的 URL，防止借 webhook 访问内部服务（SSRF）。
+//! 注册时检查一次；投递时由 [`PublicResolver`] 只连接公网地址，DNS 在两次之间改指向内网也无效。
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// 是否为可投递的公网地址
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(v6),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8 与运营商级 NAT 100.64.0.0/10
+        || a == 0
+        || (a == 100 && (b & 0xc0) == 64))
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // 唯一本地 fc00::/7 与链路本地 fe80::/10
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+/// 检查 URL 的主机：IP 字面量直接判断，域名解析后要求所有地址都是公网地址。
+/// 返回面向调用方的错误描述。
+pub async fn check_target(url: &str) -> Result<(), String> {
+    let uri = url
+        .parse::<axum::http::Uri>()
+        .map_err(|_| "must be an http(s) URL".to_string())?;
+    let host = uri
+        .host()
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| "must be an http(s) URL".to_string())?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return if is_public_ip(ip) {
+            Ok(())
+        } else {
+            Err(format!("host {ip} is not a public address"))
+        };
+    }
+    let port = uri.port_u16().unwrap_or(80);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("cannot resolve host {host}: {e}"))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("host {host} has no addresses"));
+    }
+    match addrs.iter().find(|a| !is_public_ip(a.ip())) {
+        Some(a) => Err(format!(
+            "host {host} resolves to non-public address {}",
+            a.ip()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// 投递用的 DNS 解析：丢弃非公网地址，全部被丢弃时解析失败
+#[derive(Debug, Default)]
+pub struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0))
+                    .await?
+                    .filter(|a| is_public_ip(a.ip()))
+                    .collect();
+            if addrs.is_empty() {
+                return Err(format!(
+                    "host {host} does not resolve to a public address"
+                )
+                .into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
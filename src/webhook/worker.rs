@@ -0,0 +1,212 @@
+use super::{
+    DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER, WebhookPayload,
+    retry_delay, signature_header,
+    target::{PublicResolver, check_target},
+};
+use crate::{
+    config,
+    repo::{traits::WebhookRepo, webhook_repo::DueDelivery},
+    shutdown::Shutdown,
+};
+use eyre::Result;
+use futures::future::join_all;
+use std::{sync::Arc, time::Duration};
+
+/// 记录到 last_error 的响应体最大长度
+const MAX_ERROR_LEN: usize = 512;
+
+#[derive(Debug, Clone)]
+pub struct WorkerSettings {
+    /// 达到该尝试次数仍失败则标记 FAILED
+    pub max_attempts: u32,
+    /// 单次 HTTP 请求超时
+    pub timeout: Duration,
+    /// 队列为空时的轮询间隔
+    pub poll_interval: Duration,
+    pub retry_base: Duration,
+    pub retry_max: Duration,
+    pub batch_size: i64,
+}
+
+impl WorkerSettings {
+    pub fn from_config() -> Self {
+        let cfg = config::get();
+        Self {
+            max_attempts: cfg.webhook_max_attempts,
+            timeout: Duration::from_secs(cfg.webhook_timeout_secs),
+            poll_interval: Duration::from_millis(cfg.webhook_poll_interval_ms),
+            retry_base: Duration::from_secs(5),
+            retry_max: Duration::from_secs(3600),
+            batch_size: 32,
+        }
+    }
+}
+
+/// 一次投递的结果
+#[derive(Debug)]
+enum Outcome {
+    Delivered(u16),
+    Failed { status: Option<u16>, error: String },
+}
+
+/// 投递 worker：轮询 webhook_deliveries 中到期的记录并发送，失败按指数退避重试。
+/// 领取使用 SKIP LOCKED + 租约，多实例可同时运行。
+pub struct WebhookWorker {
+    repo: Arc<dyn WebhookRepo>,
+    client: reqwest::Client,
+    settings: WorkerSettings,
+}
+
+impl WebhookWorker {
+    pub fn new(
+        repo: Arc<dyn WebhookRepo>,
+        settings: WorkerSettings,
+    ) -> Result<Self> {
+        // 只连接公网地址；不跟随重定向，避免被 3xx 引向内网
+        let client = reqwest::Client::builder()
+            .timeout(settings.timeout)
+            .user_agent("ticket-backend-webhooks")
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+        Ok(Self {
+            repo,
+            client,
+            settings,
+        })
+    }
+
+    /// 轮询直到 `shutdown` 触发：当前这一批投递完成（结果已落库）后返回
+    pub async fn run(self, shutdown: Shutdown) {
+        tracing::info!("Webhook delivery worker started");
+        while !shutdown.is_triggered() {
+            match self.tick().await {
+                Ok(n) if n > 0 => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(error = ?e, "Webhook delivery tick failed")
+                }
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(self.settings.poll_interval) => {}
+                _ = shutdown.wait() => {}
+            }
+        }
+        tracing::info!("Webhook delivery worker stopped");
+    }
+
+    /// 领取并发送一批，返回处理条数
+    async fn tick(&self) -> Result<usize> {
+        let lease = self.settings.timeout + Duration::from_secs(30);
+        let batch = self
+            .repo
+            .claim_due_deliveries(self.settings.batch_size, lease.as_secs_f64())
+            .await?;
+        let n = batch.len();
+        join_all(batch.into_iter().map(|d| self.process(d))).await;
+        Ok(n)
+    }
+
+    async fn process(&self, delivery: DueDelivery) {
+        let outcome = self.send(&delivery).await;
+        let res = match outcome {
+            Outcome::Delivered(status) => {
+                tracing::debug!(
+                    delivery_id = delivery.id,
+                    status,
+                    "Webhook delivered"
+                );
+                self.repo
+                    .mark_delivery_succeeded(delivery.id, i32::from(status))
+                    .await
+            }
+            Outcome::Failed { status, error } => {
+                let attempts = u32::try_from(delivery.attempts).unwrap_or(0);
+                let next = (attempts < self.settings.max_attempts).then(|| {
+                    let delay = retry_delay(
+                        attempts,
+                        self.settings.retry_base,
+                        self.settings.retry_max,
+                    );
+                    chrono::Utc::now()
+                        + chrono::Duration::from_std(delay).unwrap_or_default()
+                });
+                tracing::warn!(
+                    delivery_id = delivery.id,
+                    webhook_id = delivery.webhook_id,
+                    attempts,
+                    ?status,
+                    error = %error,
+                    retry_at = ?next,
+                    "Webhook delivery failed"
+                );
+                self.repo
+                    .mark_delivery_failed(
+                        delivery.id,
+                        status.map(i32::from),
+                        &error,
+                        next,
+                    )
+                    .await
+            }
+        };
+        if let Err(e) = res {
+            tracing::error!(delivery_id = delivery.id, error = ?e, "Failed to record webhook delivery result");
+        }
+    }
+
+    async fn send(&self, d: &DueDelivery) -> Outcome {
+        // 注册后 DNS 可能改指向内网；IP 字面量也不经过解析器，发送前再检查一次
+        if let Err(error) = check_target(&d.url).await {
+            return Outcome::Failed {
+                status: None,
+                error,
+            };
+        }
+        let payload = WebhookPayload {
+            id: d.id,
+            event_type: d.event_type.clone(),
+            created_at: d.created_at,
+            data: d.payload.clone(),
+        };
+        let body = match serde_json::to_vec(&payload) {
+            Ok(b) => b,
+            Err(e) => {
+                return Outcome::Failed {
+                    status: None,
+                    error: e.to_string(),
+                };
+            }
+        };
+        let signature =
+            signature_header(&d.secret, chrono::Utc::now().timestamp(), &body);
+        let res = self
+            .client
+            .post(&d.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, &d.event_type)
+            .header(DELIVERY_HEADER, d.id.to_string())
+            .body(body)
+            .send()
+            .await;
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                Outcome::Delivered(resp.status().as_u16())
+            }
+            Ok(resp) => {
+                let status = resp.status().as_u16();
+                let mut error = resp.text().await.unwrap_or_default();
+                error.truncate(error.floor_char_boundary(MAX_ERROR_LEN));
+                Outcome::Failed {
+                    status: Some(status),
+                    error,
+                }
+            }
+            Err(e) => Outcome::Failed {
+                status: None,
+                error: e.to_string(),
+            },
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use alloy::primitives::{Address, B256, Bytes, LogData, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use backend::contract::abi_check::{
+    Drift, bundled_abi, check_log, check_output, view_functions,
+};
+use backend::contract::bindings::ShowManager;
+
+fn log(topics: Vec<B256>) -> Log {
+    Log {
+        inner: alloy::primitives::Log {
+            address: Address::ZERO,
+            data: LogData::new_unchecked(topics, Bytes::new()),
+        },
+        block_number: Some(42),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn bundled_abis_cover_indexed_contracts() {
+    for contract in [
+        "did_registry",
+        "show_manager",
+        "ticket_manager",
+        "marketplace",
+    ] {
+        let abi = bundled_abi(contract).expect(contract);
+        assert!(!view_functions(&abi).is_empty(), "{contract}");
+    }
+    assert!(bundled_abi("token_swap").is_none());
+}
+
+#[test]
+fn view_output_must_decode_with_bundled_types() {
+    let abi = bundled_abi("show_manager").unwrap();
+    let next = view_functions(&abi)
+        .into_iter()
+        .find(|f| f.name == "nextShowId")
+        .unwrap();
+    let ok = U256::from(7u64).to_be_bytes::<32>();
+    assert_eq!(check_output(next, &ok), None);
+    // 空返回（函数不存在时常见）或长度不足都视为不一致
+    assert!(matches!(
+        check_output(next, &[]),
+        Some(Drift::OutputMismatch { .. })
+    ));
+    assert!(check_output(next, &ok[..16]).is_some());
+}
+
+#[test]
+fn logs_are_matched_by_selector_and_topic_count() {
+    let abi = bundled_abi("show_manager").unwrap();
+    let created = ShowManager::ShowCreated::SIGNATURE_HASH;
+    let indexed = abi
+        .events()
+        .find(|e| e.name == "ShowCreated")
+        .unwrap()
+        .inputs
+        .iter()
+        .filter(|p| p.indexed)
+        .count();
+    let topics = vec![created; 1 + indexed];
+    assert_eq!(check_log(&abi, &log(topics)), None);
+
+    let drift = check_log(&abi, &log(vec![created; 2 + indexed])).unwrap();
+    assert!(
+        matches!(drift, Drift::TopicLayout { ref event, .. } if event == "ShowCreated")
+    );
+
+    let unknown = B256::repeat_byte(0xab);
+    assert_eq!(
+        check_log(&abi, &log(vec![unknown])),
+        Some(Drift::UnknownEvent {
+            topic0: unknown,
+            block: Some(42)
+        })
+    );
+    // 匿名事件没有 topic0，无法判断
+    assert_eq!(check_log(&abi, &log(vec![])), None);
+}
@@ -29,8 +29,9 @@ impl backend::api::schema::Validate for DummyBody {
     type Err = backend::api::schema::ValidationError;
     fn validate(self) -> Result<Self, Self::Err> {
         if self.name.is_empty() {
-            return Err(backend::api::schema::ValidationError(
-                "name empty".into(),
+            return Err(backend::api::schema::ValidationError::field(
+                "name",
+                "must not be empty",
             ));
         }
         Ok(self)
@@ -77,3 +78,350 @@ async fn test_json_invalid() {
     let res = app.clone().oneshot(req).await.unwrap();
     assert_eq!(res.status(), StatusCode::BAD_REQUEST);
 }
+
+fn create_show_req(
+    event_time: &str,
+    end_time: &str,
+    max_tickets: &str,
+) -> String {
+    format!(
+        r#"{{"name":"Demo","description":"d","location":"Hall","event_time":"{event_time}","end_time":"{end_time}","ticket_price":"1000","max_tickets":"{max_tickets}"}}"#
+    )
+}
+
+#[test]
+fn test_create_show_req_validation() {
+    use backend::api::schema::Validate;
+    use backend::api::show_manager::CreateShowReq;
+
+    let parse = |s: String| serde_json::from_str::<CreateShowReq>(&s).unwrap();
+    let ok = parse(create_show_req("4102444800", "4102452000", "100"));
+    assert!(ok.validate().is_ok());
+
+    let reversed = parse(create_show_req("4102452000", "4102444800", "100"));
+    assert!(reversed.validate().is_err());
+
+    let no_tickets = parse(create_show_req("4102444800", "4102452000", "0"));
+    assert!(no_tickets.validate().is_err());
+
+    // 所有违反的规则一次返回
+    let err = parse(create_show_req("1735689600", "1735682400", "0"))
+        .validate()
+        .unwrap_err();
+    let fields: Vec<_> = err.fields.iter().map(|f| f.field.as_str()).collect();
+    assert_eq!(fields, ["event_time", "max_tickets", "end_time"]);
+}
+
+#[test]
+fn test_update_show_req_validation() {
+    use backend::api::schema::Validate;
+    use backend::api::show_manager::UpdateShowReq;
+    use backend::repo::show_repo::ShowDataRecord;
+    use backend::utils::uint256::DbU256;
+
+    let parse = |s: &str| serde_json::from_str::<UpdateShowReq>(s).unwrap();
+    assert!(parse("{}").validate().is_ok());
+    let err =
+        parse(r#"{"name":" ","event_time":"1735689600","max_tickets":"0"}"#)
+            .validate()
+            .unwrap_err();
+    assert_eq!(err.fields.len(), 3);
+    assert!(
+        serde_json::from_str::<UpdateShowReq>(r#"{"ticket_price":"-1"}"#)
+            .is_err()
+    );
+    // 与创建一致：name / location 去掉首尾空白后再校验与写入
+    let trimmed = parse(r#"{"name":"  Jazz Night ","location":"\tHall A  "}"#)
+        .validate()
+        .unwrap();
+    assert_eq!(trimmed.name.as_deref(), Some("Jazz Night"));
+    assert_eq!(trimmed.location.as_deref(), Some("Hall A"));
+    // 空白 location 清空为 ""，而不是当作未提供
+    let cleared = parse(r#"{"location":"   "}"#).validate().unwrap();
+    assert_eq!(cleared.location.as_deref(), Some(""));
+
+    let existing = ShowDataRecord {
+        chain_id: 31337,
+        id: DbU256::from(1u64),
+        name: "Demo".into(),
+        description: String::new(),
+        location: String::new(),
+        event_time: DbU256::from(4102444800u64),
+        ticket_price: DbU256::from(1000u64),
+        max_tickets: DbU256::from(100u64),
+        sold_tickets: DbU256::from(40u64),
+        is_active: true,
+        organizer: String::new(),
+        created_at: chrono::Utc::now(),
+        version: 0,
+    };
+    let shrink = parse(r#"{"max_tickets":"39"}"#).validate().unwrap();
+    assert_eq!(
+        shrink.check_against(&existing).unwrap_err().fields[0].field,
+        "max_tickets"
+    );
+    let keep = parse(r#"{"max_tickets":"40"}"#).validate().unwrap();
+    assert!(keep.check_against(&existing).is_ok());
+}
+
+#[derive(Debug, Deserialize, backend::api::validation::Validate)]
+#[validate(schema = "Rules::check_pair")]
+struct Rules {
+    #[validate(trim, non_blank, length(min = 2, max = 4))]
+    code: String,
+    #[validate(range(min = 10, max = "0x64"))]
+    amount: backend::utils::uint256::DbU256,
+    #[validate(trim, address)]
+    owner: Option<String>,
+    #[validate(url)]
+    callback: Option<String>,
+    #[validate(uri)]
+    metadata: Option<String>,
+    #[validate(custom = "even")]
+    count: i64,
+    low: i64,
+    high: i64,
+}
+
+impl Rules {
+    fn check_pair(&self, errors: &mut backend::api::schema::ValidationError) {
+        if self.low > self.high {
+            errors.add("low", "must not exceed high");
+        }
+    }
+}
+
+fn even(v: &i64) -> Result<(), String> {
+    if v % 2 == 0 {
+        Ok(())
+    } else {
+        Err("must be even".into())
+    }
+}
+
+#[test]
+fn test_derive_validate_rules() {
+    use backend::api::schema::Validate;
+    let parse = |s: &str| serde_json::from_str::<Rules>(s).unwrap().validate();
+
+    let ok = parse(
+        r#"{"code":" ab ","amount":"100","owner":"  ","callback":"https://example.com/hook","metadata":"ipfs://QmHash/1.json","count":2,"low":1,"high":2}"#,
+    )
+    .unwrap();
+    assert_eq!(ok.code, "ab");
+    assert_eq!(ok.owner, None);
+
+    let err = parse(
+        r#"{"code":"abcde","amount":"101","owner":"0x12","callback":"ftp://example.com","metadata":"not a uri","count":3,"low":2,"high":1}"#,
+    )
+    .unwrap_err();
+    let got: Vec<_> = err
+        .fields
+        .iter()
+        .map(|f| (f.field.as_str(), f.message.as_str()))
+        .collect();
+    assert_eq!(
+        got,
+        [
+            ("code", "length must be between 2 and 4"),
+            ("amount", "must be between 10 and 0x64"),
+            ("owner", "must be a 0x-prefixed 20-byte address"),
+            ("callback", "must be an http(s) URL"),
+            ("metadata", "must be an absolute URI"),
+            ("count", "must be even"),
+            ("low", "must not exceed high"),
+        ]
+    );
+    assert!(
+        parse(r#"{"code":"  ","amount":"9","count":0,"low":0,"high":0}"#)
+            .unwrap_err()
+            .fields
+            .iter()
+            .any(|f| f.field == "code" && f.message == "must not be blank")
+    );
+}
+
+#[derive(Debug, Deserialize, backend::api::validation::Validate)]
+struct Bounds {
+    #[validate(range(min = 1, max = 0x64))]
+    small: i64,
+    #[validate(range(
+        max = "0x8000000000000000000000000000000000000000000000000000000000000000"
+    ))]
+    wide: backend::utils::uint256::DbU256,
+}
+
+#[test]
+fn test_range_bounds_are_compile_time_values() {
+    use backend::api::schema::Validate;
+    let parse = |s: &str| serde_json::from_str::<Bounds>(s).unwrap().validate();
+
+    assert!(parse(r#"{"small":100,"wide":"0x1"}"#).is_ok());
+    let err = parse(
+        r#"{"small":-5,"wide":"0x8000000000000000000000000000000000000000000000000000000000000001"}"#,
+    )
+    .unwrap_err();
+    let got: Vec<_> = err
+        .fields
+        .iter()
+        .map(|f| (f.field.as_str(), f.message.as_str()))
+        .collect();
+    assert_eq!(
+        got,
+        [
+            ("small", "must be between 1 and 100"),
+            (
+                "wide",
+                "must be at most 0x8000000000000000000000000000000000000000000000000000000000000000"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_list_shows_query_validation() {
+    use backend::api::schema::Validate;
+    use backend::api::show_manager::{ListShowsQuery, ShowStatusFilter};
+    use backend::repo::show_repo::{ShowSortKey, SortOrder};
+
+    let parse = |qs: &str| {
+        serde_urlencoded::from_str::<ListShowsQuery>(qs)
+            .unwrap()
+            .validate()
+    };
+
+    let q = parse("status=active&sort=sold&order=asc&q=%20rock%20&offset=-3")
+        .unwrap();
+    assert_eq!(q.status, Some(ShowStatusFilter::Active));
+    assert_eq!(q.sort, Some(ShowSortKey::Sold));
+    assert_eq!(q.order, SortOrder::Asc);
+    assert_eq!(q.q.as_deref(), Some("rock"));
+    assert_eq!(q.offset, 0);
+    assert_eq!(q.filter(31337).is_active, Some(true));
+
+    assert!(parse("from=200&to=100").is_err());
+    assert!(parse("min_price=5&max_price=1").is_err());
+    assert!(serde_urlencoded::from_str::<ListShowsQuery>("sort=name").is_err());
+}
+
+#[tokio::test]
+async fn test_list_shows_query_rejects_unknown_sort() {
+    use backend::api::show_manager::ListShowsQuery;
+    async fn handler(
+        ValidatedQuery(_q): ValidatedQuery<ListShowsQuery>,
+    ) -> Response {
+        ok("ok")
+    }
+    let app = Router::new().route("/shows", get(handler));
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/shows?sort=bogus")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_cursor_roundtrip_and_into_page() {
+    use backend::api::schema::{decode_cursor, encode_cursor, into_page};
+    use backend::repo::ticket_repo::TransferCursor;
+
+    let c = TransferCursor {
+        block_number: 42,
+        log_index: 3,
+    };
+    let s = encode_cursor(&c);
+    assert!(!s.contains('=') && !s.contains('+') && !s.contains('/'));
+    assert_eq!(decode_cursor::<TransferCursor>(&s).unwrap(), c);
+    assert!(decode_cursor::<TransferCursor>("not-a-cursor").is_err());
+
+    // 多取一条：有剩余时返回最后一条的游标
+    let (rows, next) = into_page(vec![1, 2, 3], 2, |v| *v);
+    assert_eq!(rows, vec![1, 2]);
+    assert_eq!(decode_cursor::<i32>(&next.unwrap()).unwrap(), 2);
+    let (rows, next) = into_page(vec![1, 2], 2, |v| *v);
+    assert_eq!(rows.len(), 2);
+    assert!(next.is_none());
+}
+
+#[tokio::test]
+async fn test_cursor_query_rejects_invalid_cursor() {
+    use backend::api::request::CursorQuery;
+    use backend::api::schema::encode_cursor;
+    use backend::repo::ticket_repo::TicketCursor;
+    async fn handler(CursorQuery(page): CursorQuery<TicketCursor>) -> Response {
+        ok(page.limit)
+    }
+    let app = Router::new().route("/tickets", get(handler));
+    let send = |uri: String| {
+        let app = app.clone();
+        async move {
+            app.oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+        }
+    };
+    assert_eq!(
+        send("/tickets?cursor=%%%".into()).await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        send("/tickets?cursor=e30".into()).await,
+        StatusCode::BAD_REQUEST
+    );
+    let good = encode_cursor(&TicketCursor {
+        token_id: backend::utils::uint256::DbU256::from(7u64),
+    });
+    assert_eq!(
+        send(format!("/tickets?cursor={good}&limit=5")).await,
+        StatusCode::OK
+    );
+    assert_eq!(send("/tickets".into()).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_if_match_parses_strong_etags() {
+    use backend::api::request::{IfMatch, parse_etag};
+    use backend::api::response::etag;
+
+    assert_eq!(parse_etag(etag(12).to_str().unwrap()), Some(12));
+    assert_eq!(parse_etag("W/\"12\""), None);
+    assert_eq!(parse_etag("12"), None);
+
+    async fn handler(IfMatch(v): IfMatch) -> Response {
+        ok(v)
+    }
+    let app = Router::new().route("/s", get(handler));
+    let send = |value: Option<&'static str>| {
+        let app = app.clone();
+        async move {
+            let mut req = Request::builder().uri("/s");
+            if let Some(v) = value {
+                req = req.header("if-match", v);
+            }
+            let res = app
+                .oneshot(req.body(axum::body::Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"]
+                .clone()
+        }
+    };
+    assert_eq!(send(None).await, serde_json::Value::Null);
+    assert_eq!(send(Some("*")).await, serde_json::Value::Null);
+    assert_eq!(send(Some("\"3\", \"4\"")).await, serde_json::json!([3, 4]));
+    assert_eq!(send(Some("W/\"3\"")).await, serde_json::json!([]));
+}
@@ -0,0 +1,91 @@
+use backend::api::admin::{AuditQuery, check_admin_token};
+use backend::api::error::ErrorCode;
+use backend::api::schema::Validate;
+use backend::repo::audit_repo::{
+    AuditCursor, AuditFilter, NewAuditEntry, build_audit_list, json_diff,
+};
+use serde_json::json;
+
+#[test]
+fn json_diff_keeps_only_changed_fields() {
+    let before = json!({"name": "A", "location": "Hall", "version": 1});
+    let after =
+        json!({"name": "B", "location": "Hall", "version": 2, "extra": true});
+    let (old, new) = json_diff(&before, &after);
+    assert_eq!(old, json!({"name": "A", "version": 1}));
+    assert_eq!(new, json!({"name": "B", "version": 2, "extra": true}));
+
+    // 创建与删除只有一侧
+    let created = NewAuditEntry::new(
+        None,
+        "show_job",
+        "1".into(),
+        "create",
+        None,
+        Some(&after),
+    );
+    assert!(created.before.is_none());
+    assert_eq!(created.after, Some(after.clone()));
+    let deleted = NewAuditEntry::new(
+        Some(1),
+        "show",
+        "7".into(),
+        "delete",
+        Some(&before),
+        None,
+    );
+    assert_eq!(deleted.before, Some(before));
+    assert!(deleted.after.is_none());
+}
+
+#[test]
+fn build_audit_list_filters_and_pages_by_id() {
+    let filter = AuditFilter {
+        resource: Some("show".into()),
+        actor: Some("0xABC".into()),
+        ..Default::default()
+    };
+    let qb = build_audit_list(&filter, Some(&AuditCursor { id: 50 }), 21);
+    let sql = qb.sql();
+    assert!(sql.contains("resource = $1"));
+    assert!(sql.contains("actor = lower($2)"));
+    assert!(sql.contains("id < $3"));
+    assert!(sql.ends_with("ORDER BY id DESC LIMIT $4"));
+    assert!(!sql.contains("resource_id ="));
+}
+
+#[test]
+fn admin_token_is_required() {
+    let err = check_admin_token(None, Some("Bearer anything")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Forbidden);
+
+    let err = check_admin_token(Some("s3cret"), None).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    let err =
+        check_admin_token(Some("s3cret"), Some("Bearer nope")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    let err = check_admin_token(Some("s3cret"), Some("s3cret")).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+
+    assert!(check_admin_token(Some("s3cret"), Some("Bearer s3cret")).is_ok());
+}
+
+#[test]
+fn audit_query_validates_resource_and_actor() {
+    let q: AuditQuery = serde_json::from_value(json!({
+        "resource": " show ",
+        "actor": "0x00000000000000000000000000000000000000aa",
+    }))
+    .unwrap();
+    let q = q.validate().expect("valid");
+    assert_eq!(q.resource.as_deref(), Some("show"));
+
+    let q: AuditQuery = serde_json::from_value(json!({
+        "resource": "ticket",
+        "actor": "alice",
+    }))
+    .unwrap();
+    let err = q.validate().unwrap_err();
+    let fields: Vec<_> = err.fields.iter().map(|f| f.field.as_str()).collect();
+    assert_eq!(fields, ["actor", "resource"]);
+}
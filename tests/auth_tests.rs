@@ -0,0 +1,149 @@
+use alloy::signers::{SignerSync, local::PrivateKeySigner};
+use axum::{
+    Router,
+    body::Body,
+    http::{HeaderMap, Method, Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use backend::api::{
+    auth::{
+        AUTH_ADDRESS, AUTH_SIGNATURE, AUTH_TIMESTAMP, Authenticator,
+        MAX_SKEW_SECS, auth_message, authenticate, verify,
+    },
+    request::Actor,
+};
+use tower::ServiceExt;
+
+const NOW: i64 = 1_700_000_000;
+
+fn signed(
+    key: &PrivateKeySigner,
+    method: &Method,
+    path: &str,
+    ts: i64,
+    body: &[u8],
+) -> HeaderMap {
+    let sig = key
+        .sign_message_sync(auth_message(method, path, ts, body).as_bytes())
+        .unwrap();
+    let mut h = HeaderMap::new();
+    h.insert(AUTH_ADDRESS, key.address().to_string().parse().unwrap());
+    h.insert(AUTH_TIMESTAMP, ts.to_string().parse().unwrap());
+    h.insert(AUTH_SIGNATURE, sig.to_string().parse().unwrap());
+    h
+}
+
+#[test]
+fn signatures_bind_address_request_and_time() {
+    let key = PrivateKeySigner::random();
+    let body = br#"{"name":"Demo"}"#;
+    let h = signed(&key, &Method::PATCH, "/show/1", NOW, body);
+    let check = |h: &HeaderMap, m: &Method, path: &str, body: &[u8], now| {
+        verify(h, m, path, body, now).map(|s| s.map(|s| s.address))
+    };
+    assert_eq!(
+        check(&h, &Method::PATCH, "/show/1", body, NOW).unwrap(),
+        Some(key.address())
+    );
+    // 无签名头：匿名
+    assert_eq!(
+        check(&HeaderMap::new(), &Method::PATCH, "/show/1", body, NOW).unwrap(),
+        None
+    );
+    // 换了请求、请求体、过期、地址不符、只带部分头都被拒绝
+    assert!(check(&h, &Method::DELETE, "/show/1", body, NOW).is_err());
+    assert!(check(&h, &Method::PATCH, "/show/2", body, NOW).is_err());
+    assert!(
+        check(&h, &Method::PATCH, "/show/1", br#"{"name":"Evil"}"#, NOW)
+            .is_err()
+    );
+    assert!(
+        check(&h, &Method::PATCH, "/show/1", body, NOW + MAX_SKEW_SECS + 1)
+            .is_err()
+    );
+    let mut other = h.clone();
+    other.insert(
+        AUTH_ADDRESS,
+        PrivateKeySigner::random()
+            .address()
+            .to_string()
+            .parse()
+            .unwrap(),
+    );
+    assert!(check(&other, &Method::PATCH, "/show/1", body, NOW).is_err());
+    let mut partial = h.clone();
+    partial.remove(AUTH_SIGNATURE);
+    assert!(check(&partial, &Method::PATCH, "/show/1", body, NOW).is_err());
+}
+
+fn actor_app() -> Router {
+    Router::new()
+        .route(
+            "/show",
+            post(|Actor(ctx): Actor, body: String| async move {
+                format!("{} {body}", ctx.actor.unwrap_or_default())
+            }),
+        )
+        .layer(middleware::from_fn_with_state(
+            Authenticator::memory(),
+            authenticate,
+        ))
+}
+
+fn post_show(headers: HeaderMap, body: &'static str) -> Request<Body> {
+    let mut req = Request::builder()
+        .method(Method::POST)
+        .uri("/show")
+        .body(Body::from(body))
+        .unwrap();
+    *req.headers_mut() = headers;
+    req
+}
+
+#[tokio::test]
+async fn actor_is_the_signing_address() {
+    let app = actor_app();
+    let key = PrivateKeySigner::random();
+    let now = chrono::Utc::now().timestamp();
+    let body = r#"{"name":"Demo"}"#;
+    let h = signed(&key, &Method::POST, "/show", now, body.as_bytes());
+    let res = app.clone().oneshot(post_show(h, body)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    // 请求体校验之后原样交给 handler
+    let got = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(got, format!("{:#x} {body}", key.address()));
+
+    let h = signed(&key, &Method::POST, "/show", now - 3600, body.as_bytes());
+    let res = app.oneshot(post_show(h, body)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn changed_body_and_replayed_signatures_are_rejected() {
+    let app = actor_app();
+    let key = PrivateKeySigner::random();
+    let now = chrono::Utc::now().timestamp();
+    let body = r#"{"name":"Demo"}"#;
+    let h = signed(&key, &Method::POST, "/show", now, body.as_bytes());
+
+    // 截获的签名换一个请求体：签名不匹配
+    let res = app
+        .clone()
+        .oneshot(post_show(h.clone(), r#"{"name":"Evil"}"#))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = app
+        .clone()
+        .oneshot(post_show(h.clone(), body))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    // 原样重放：有效期内同一签名只接受一次
+    let res = app.oneshot(post_show(h, body)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
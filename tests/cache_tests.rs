@@ -0,0 +1,201 @@
+use backend::{
+    db::cache::{AppCache, Cache, CachedPage, ListNamespace, MemoryCache},
+    repo::show_repo::ShowDataRecord,
+    utils::uint256::DbU256,
+};
+use chrono::Utc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+use std::time::Duration;
+
+const CHAIN: i64 = 31337;
+
+fn show(id: u64, name: &str) -> ShowDataRecord {
+    ShowDataRecord {
+        chain_id: CHAIN,
+        id: DbU256::from(id),
+        name: name.to_string(),
+        description: "d".to_string(),
+        location: "l".to_string(),
+        event_time: DbU256::from(1u64),
+        ticket_price: DbU256::from(1u64),
+        max_tickets: DbU256::from(10u64),
+        sold_tickets: DbU256::from(0u64),
+        is_active: true,
+        organizer: "0x0".to_string(),
+        created_at: Utc::now(),
+        version: 0,
+    }
+}
+
+#[tokio::test]
+async fn memory_cache_expires_and_evicts() {
+    let cache = MemoryCache::new(2);
+    cache.set("a", "1".into(), 60).await.unwrap();
+    cache.set("expired", "x".into(), 0).await.unwrap();
+    assert_eq!(cache.get("a").await.unwrap().as_deref(), Some("1"));
+    assert!(cache.get("expired").await.unwrap().is_none());
+
+    // 容量为 2：插入第三个 key 淘汰最久未使用的
+    cache.set("b", "2".into(), 60).await.unwrap();
+    cache.get("a").await.unwrap();
+    cache.set("c", "3".into(), 60).await.unwrap();
+    assert!(cache.get("b").await.unwrap().is_none());
+    assert!(cache.get("a").await.unwrap().is_some());
+
+    // 计数器同样受容量与 TTL 约束：过期或被淘汰后从 1 重新开始
+    assert_eq!(cache.incr("gen", 60).await.unwrap(), 1);
+    assert_eq!(cache.incr("gen", 60).await.unwrap(), 2);
+    assert_eq!(cache.get("gen").await.unwrap().as_deref(), Some("2"));
+    for k in ["d", "e"] {
+        cache.set(k, k.into(), 60).await.unwrap();
+    }
+    assert!(cache.get("gen").await.unwrap().is_none());
+    assert_eq!(cache.incr("gen", 0).await.unwrap(), 1);
+    assert!(cache.get("gen").await.unwrap().is_none());
+    assert_eq!(cache.incr("gen", 60).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn app_cache_show_roundtrip_and_list_invalidation() {
+    let cache = AppCache::new(Arc::new(MemoryCache::new(100)), 60, 60);
+    let rec = show(7, "Concert");
+    assert!(cache.get_show(CHAIN, &rec.id).await.is_none());
+    cache.put_show(&rec).await;
+    assert_eq!(
+        cache.get_show(CHAIN, &rec.id).await.map(|r| r.name),
+        Some("Concert".to_string())
+    );
+    // 同一 show id 在其他链上是另一条记录
+    assert!(cache.get_show(10, &rec.id).await.is_none());
+
+    let page = CachedPage {
+        items: vec![show(7, "Concert")],
+        next_cursor: Some("abc".to_string()),
+    };
+    let key = cache.list_key(ListNamespace::Shows, "limit=1").await;
+    cache.put_list_page(&key, &page).await;
+    let hit = cache.get_list_page::<ShowDataRecord>(&key).await.unwrap();
+    assert_eq!(hit.items.len(), 1);
+    assert_eq!(hit.next_cursor.as_deref(), Some("abc"));
+
+    // 其他命名空间不受影响；show 变更后单条与列表缓存都失效
+    cache.bump_list(ListNamespace::Tickets).await;
+    let key = cache.list_key(ListNamespace::Shows, "limit=1").await;
+    assert!(cache.get_list_page::<ShowDataRecord>(&key).await.is_some());
+    cache.invalidate_show(CHAIN, &rec.id).await;
+    assert!(cache.get_show(CHAIN, &rec.id).await.is_none());
+    let key = cache.list_key(ListNamespace::Shows, "limit=1").await;
+    assert!(cache.get_list_page::<ShowDataRecord>(&key).await.is_none());
+
+    // 查询期间被作废：旧结果写在查询前的代际下，之后的读取看不到
+    cache.bump_list(ListNamespace::Shows).await;
+    cache.put_list_page(&key, &page).await;
+    let key = cache.list_key(ListNamespace::Shows, "limit=1").await;
+    assert!(cache.get_list_page::<ShowDataRecord>(&key).await.is_none());
+}
+
+#[tokio::test]
+async fn show_loads_are_coalesced_per_key() {
+    let cache = AppCache::new(Arc::new(MemoryCache::new(100)), 60, 60);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let id = DbU256::from(1u64);
+    let mut tasks = Vec::new();
+    for _ in 0..20 {
+        let cache = cache.clone();
+        let calls = calls.clone();
+        let id = id.clone();
+        tasks.push(tokio::spawn(async move {
+            cache
+                .get_show_with(CHAIN, &id, move || async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok(Some(show(1, "Hot")))
+                })
+                .await
+                .unwrap()
+        }));
+    }
+    for t in tasks {
+        assert_eq!(t.await.unwrap().unwrap().name, "Hot");
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn missing_show_is_negatively_cached() {
+    let cache = AppCache::new(Arc::new(MemoryCache::new(100)), 60, 60);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let id = DbU256::from(404u64);
+    for _ in 0..3 {
+        let calls = calls.clone();
+        let res = cache
+            .get_show_with(CHAIN, &id, move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(None)
+            })
+            .await
+            .unwrap();
+        assert!(res.is_none());
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    // 负缓存不会被当作命中返回给 get_show
+    assert!(cache.get_show(CHAIN, &id).await.is_none());
+}
+
+#[tokio::test]
+async fn stale_show_is_served_while_revalidating() {
+    // fresh TTL 为 0：写入后立即进入 stale 窗口
+    let cache = AppCache::new(Arc::new(MemoryCache::new(100)), 0, 60);
+    let id = DbU256::from(2u64);
+    let first = cache
+        .get_show_with(CHAIN, &id, || async { Ok(Some(show(2, "v1"))) })
+        .await
+        .unwrap();
+    assert_eq!(first.unwrap().name, "v1");
+
+    let stale = cache
+        .get_show_with(CHAIN, &id, || async { Ok(Some(show(2, "v2"))) })
+        .await
+        .unwrap();
+    assert_eq!(stale.unwrap().name, "v1");
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(cache.get_show(CHAIN, &id).await.unwrap().name, "v2");
+}
+
+#[tokio::test]
+async fn invalidation_during_load_is_not_overwritten() {
+    let cache = AppCache::new(Arc::new(MemoryCache::new(100)), 60, 60);
+    let id = DbU256::from(3u64);
+    let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+    let load = {
+        let cache = cache.clone();
+        let id = id.clone();
+        tokio::spawn(async move {
+            cache
+                .get_show_with(CHAIN, &id, move || async move {
+                    // 读到的是创建前的状态
+                    let _ = started_tx.send(());
+                    let _ = release_rx.await;
+                    Ok(None)
+                })
+                .await
+        })
+    };
+    started_rx.await.unwrap();
+    // 回源期间 ShowCreated 被索引并作废缓存
+    cache.invalidate_show(CHAIN, &id).await;
+    release_tx.send(()).unwrap();
+    assert!(load.await.unwrap().unwrap().is_none());
+
+    // 旧的“不存在”结果没有写回：下一次读取重新回源
+    let res = cache
+        .get_show_with(CHAIN, &id, || async { Ok(Some(show(3, "created"))) })
+        .await
+        .unwrap();
+    assert_eq!(res.unwrap().name, "created");
+}
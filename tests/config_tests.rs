@@ -0,0 +1,263 @@
+use backend::config::{
+    Config, Deployments, LogFormat, Origin, Sources,
+    sources::{Redact, SETTINGS, redact},
+};
+
+const BASE: &str = r#"
+[database]
+url = "postgres://app:hunter2@db:5432/ticket"
+
+[contracts]
+did_registry = "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707"
+show_manager = "0x8A791620dd6260079BF849Dc5567aDC3F2FdC318"
+"#;
+
+fn load(extra: &str) -> eyre::Result<Config> {
+    Config::from_sources(&Sources::from_toml(&format!("{BASE}\n{extra}"))?)
+}
+
+#[test]
+fn file_values_override_defaults() {
+    let src = Sources::from_toml(
+        r#"
+[server]
+port = 9090
+
+[logging]
+format = "json"
+http_headers = true
+
+[cors]
+allow_origins = ["https://a.example", "https://b.example"]
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        src.lookup("API_PORT"),
+        Some(("9090".to_string(), Origin::File))
+    );
+    assert_eq!(
+        src.lookup("API_HOST"),
+        Some(("127.0.0.1".to_string(), Origin::Default))
+    );
+    assert_eq!(src.var("LOG_HTTP_HEADERS").as_deref(), Some("1"));
+    assert_eq!(
+        src.var("CORS_ALLOW_ORIGIN").as_deref(),
+        Some("https://a.example,https://b.example")
+    );
+    assert_eq!(src.lookup("REDIS_URL"), None);
+}
+
+#[test]
+fn unknown_keys_and_bad_values_are_rejected() {
+    let err = Sources::from_toml("[server]\nprot = 1\n[nope]\nx = 1\n")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("server.prot"), "{err}");
+    assert!(err.contains("nope.x"), "{err}");
+    assert!(Sources::from_toml("[rate_limit]\nroutes = [1]\n").is_err());
+    assert!(Sources::from_toml("[server]\nport = { a = 1 }\n").is_err());
+}
+
+#[test]
+fn typed_config_is_validated() {
+    let cfg = load("[logging]\nformat = \"json\"\n[rate_limit]\nroutes = [\"POST /show=5\"]\n")
+        .unwrap();
+    assert_eq!(cfg.api_addr.to_string(), "127.0.0.1:8080");
+    assert_eq!(cfg.logging.format, LogFormat::Json);
+    assert_eq!(cfg.rate_limit.routes.len(), 1);
+    assert!(cfg.cors.allow_any());
+    assert!(cfg.rate_limit.enabled);
+    // stale 窗口可以关闭，其余 TTL 仍要求为正
+    let cfg = load("[cache]\nshow_stale_secs = 0\n").unwrap();
+    assert_eq!(cfg.show_cache_stale_secs, 0);
+
+    for (extra, needle) in [
+        ("[cache]\nshow_ttl_secs = 0\n", "SHOW_CACHE_TTL_SECS"),
+        ("[cache]\nshow_stale_secs = -1\n", "SHOW_CACHE_STALE_SECS"),
+        ("[server]\nport = 70000\n", "API_PORT"),
+        ("[logging]\nformat = \"xml\"\n", "LOG_FORMAT"),
+        ("[logging]\nrotation = \"weekly\"\n", "LOG_ROTATION"),
+        ("[logging]\nansi = \"maybe\"\n", "LOG_ANSI"),
+        (
+            "[cors]\nallow_origins = [\"example.com\"]\n",
+            "CORS_ALLOW_ORIGIN",
+        ),
+        ("[cache]\nbackend = \"redis\"\n", "REDIS_URL"),
+        ("[graphql]\nmax_depth = 0\n", "GRAPHQL_MAX_DEPTH"),
+        ("[rpc]\nws_url = \"http://x\"\n", "WS_RPC_URL"),
+    ] {
+        let err = load(extra).unwrap_err().to_string();
+        assert!(err.contains(needle), "{extra}: {err}");
+    }
+    let err = Config::from_sources(&Sources::from_toml("").unwrap())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("DATABASE_URL is required"), "{err}");
+}
+
+#[test]
+fn extra_chains_are_loaded_and_resolved() {
+    const OP: &str = r#"
+[[chains]]
+id = 10
+name = "optimism"
+ws_url = "wss://op.example/ws"
+did_registry = "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707"
+show_manager = "0x8A791620dd6260079BF849Dc5567aDC3F2FdC318"
+"#;
+    let cfg = load(OP).unwrap();
+    assert_eq!(cfg.chains.len(), 2);
+    assert_eq!(cfg.default_chain().chain_id, 31337);
+    assert_eq!(cfg.chain(10).unwrap().name, "optimism");
+    assert_eq!(cfg.resolve_chain(None).unwrap().chain_id, 31337);
+    assert_eq!(cfg.resolve_chain(Some(10)).unwrap().chain_id, 10);
+    let err = cfg.resolve_chain(Some(5)).unwrap_err();
+    assert!(err.contains("chain_id 5"), "{err}");
+
+    let err = load(&format!("{OP}{OP}")).unwrap_err().to_string();
+    assert!(err.contains("more than once"), "{err}");
+    let err = load(&OP.replace("ws_url = \"wss", "ws_url = \"https"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("chains[10].ws_url"), "{err}");
+    let err = Sources::from_toml(&format!("{OP}rpc = 1\n"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("rpc"), "{err}");
+}
+
+const BROADCAST: &str = r#"{
+  "transactions": [
+    { "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+      "transactionType": "CREATE", "contractName": "DIDRegistry",
+      "contractAddress": "0x5fc8d32690cc91d4c39d9d3abcbd16989f875707" },
+    { "hash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+      "transactionType": "CREATE", "contractName": "ShowManager",
+      "contractAddress": "0x8a791620dd6260079bf849dc5567adc3f2fdc318" },
+    { "hash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+      "transactionType": "CALL", "contractName": "ShowManager",
+      "contractAddress": "0x8a791620dd6260079bf849dc5567adc3f2fdc318" },
+    { "hash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+      "transactionType": "CREATE", "contractName": "PlatformToken",
+      "contractAddress": "0x5fbdb2315678afecb367f032d93f642f64180aa3" }
+  ],
+  "receipts": [
+    { "transactionHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+      "blockNumber": "0x5" },
+    { "transactionHash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+      "blockNumber": "0x7" }
+  ],
+  "chain": 31337
+}"#;
+
+#[test]
+fn deployments_manifest_supplies_addresses_and_blocks() {
+    let manifest = Deployments::from_json(BROADCAST).unwrap();
+    assert_eq!(manifest.chain_id, Some(31337));
+    assert_eq!(manifest.get("show_manager").unwrap().block, Some(7));
+    assert!(manifest.get("marketplace").is_none());
+
+    let path = std::env::temp_dir()
+        .join(format!("deployments-{}.json", std::process::id()));
+    std::fs::write(&path, BROADCAST).unwrap();
+    let toml = format!(
+        "[database]\nurl = \"postgres://db/ticket\"\n[contracts]\ndeployments = {:?}\n",
+        path.display().to_string()
+    );
+    let cfg =
+        Config::from_sources(&Sources::from_toml(&toml).unwrap()).unwrap();
+    let chain = cfg.default_chain();
+    assert_eq!(
+        chain.addresses.did_registry.to_string(),
+        "0x5FC8d32690cc91D4c39d9d3abcBD16989F875707"
+    );
+    assert_eq!(chain.deploy_blocks.get("did_registry"), Some(&5));
+    assert_eq!(chain.deploy_blocks.get("show_manager"), Some(&7));
+
+    // 显式地址覆盖清单，且不再沿用清单中的部署区块
+    let cfg = Config::from_sources(
+        &Sources::from_toml(&format!(
+            "{toml}show_manager = \"0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9\"\n"
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(
+        !cfg.default_chain()
+            .deploy_blocks
+            .contains_key("show_manager")
+    );
+
+    // 清单记录的链与配置不一致
+    let err = Config::from_sources(
+        &Sources::from_toml(&format!("{toml}[rpc]\nchain_id = 10\n")).unwrap(),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("deployed on chain 31337"), "{err}");
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn effective_config_redacts_secrets() {
+    let src = Sources::from_toml(&format!(
+        "{BASE}\n[signer]\nprivate_key = \"0xdeadbeef\"\n"
+    ))
+    .unwrap();
+    let effective = src.effective();
+    assert_eq!(effective.len(), SETTINGS.len());
+    let value = |key: &str| {
+        effective
+            .iter()
+            .find(|(s, _)| s.key == key)
+            .and_then(|(_, v)| v.clone())
+            .map(|(v, _)| v)
+    };
+    assert_eq!(value("signer.private_key").as_deref(), Some("<redacted>"));
+    assert_eq!(
+        value("database.url").as_deref(),
+        Some("postgres://app:***@db:5432/ticket")
+    );
+    assert_eq!(
+        redact(Redact::UrlPassword, "redis://:pw@cache:6379"),
+        "redis://:***@cache:6379"
+    );
+    assert_eq!(
+        redact(Redact::UrlPassword, "redis://cache:6379"),
+        "redis://cache:6379"
+    );
+}
+
+#[test]
+fn example_config_is_valid() {
+    let text = std::fs::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("config.example.toml"),
+    )
+    .unwrap();
+    Config::from_sources(&Sources::from_toml(&text).unwrap()).unwrap();
+}
+
+#[test]
+fn reload_reports_each_change_once() {
+    let with_port = |port: u16| {
+        Sources::from_toml(&format!("{BASE}\n[server]\nport = {port}\n"))
+            .unwrap()
+    };
+    backend::config::reload_from(with_port(9090)).unwrap();
+    let report = backend::config::reload_from(with_port(9091)).unwrap();
+    assert_eq!(report.restart_required, vec!["server.port"]);
+    // 与上一次成功加载的来源比较，而不是启动时的来源
+    let report = backend::config::reload_from(with_port(9091)).unwrap();
+    assert!(report.restart_required.is_empty(), "{report:?}");
+    // 校验失败不替换：之后的比较仍以 9091 为准
+    assert!(
+        backend::config::reload_from(
+            Sources::from_toml("[server]\nport = 1\n").unwrap()
+        )
+        .is_err()
+    );
+    let report = backend::config::reload_from(with_port(9091)).unwrap();
+    assert!(report.restart_required.is_empty(), "{report:?}");
+}
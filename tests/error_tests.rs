@@ -0,0 +1,190 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+    response::Response,
+    routing::{get, post},
+};
+use backend::api::error::{AppError, ErrorCode, scope_request_id};
+use backend::api::request::{PathIdU256, PathShowId, ValidatedJson};
+use backend::api::request_id_header;
+use backend::api::response::ok;
+use backend::api::schema::{Validate, ValidationError};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use tower::ServiceExt;
+use tower_http::request_id::{
+    MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer,
+};
+
+#[derive(Deserialize)]
+struct ShowBody {
+    name: String,
+    max_tickets: i64,
+}
+
+impl Validate for ShowBody {
+    type Err = ValidationError;
+    fn validate(self) -> Result<Self, Self::Err> {
+        let mut err = ValidationError::default();
+        if self.name.is_empty() {
+            err = err.with_field("name", "must not be empty");
+        }
+        if self.max_tickets <= 0 {
+            err = err.with_field("max_tickets", "must be positive");
+        }
+        if err.fields.is_empty() {
+            Ok(self)
+        } else {
+            Err(err)
+        }
+    }
+}
+
+async fn create(ValidatedJson(_b): ValidatedJson<ShowBody>) -> Response {
+    ok("created")
+}
+
+async fn show_by_id(PathShowId(_id): PathShowId) -> Response {
+    ok("show")
+}
+
+async fn ticket_by_id(PathIdU256(_id): PathIdU256) -> Response {
+    ok("ticket")
+}
+
+async fn db_failure() -> Response {
+    AppError::from(sqlx::Error::Protocol(
+        "relation \"shows\" does not exist at 10.0.0.5".into(),
+    ))
+    .to_response()
+}
+
+/// 与 listen_app 相同的 request id 层次，外加 JSON 404 fallback
+fn app() -> Router {
+    let hdr = request_id_header().clone();
+    Router::new()
+        .route("/show", post(create))
+        .route("/show/{id}", get(show_by_id))
+        .route("/tickets/{id}", get(ticket_by_id))
+        .route("/boom", get(db_failure))
+        .fallback(backend::api::error::route_not_found)
+        .layer(axum::middleware::from_fn(scope_request_id))
+        .layer(PropagateRequestIdLayer::new(hdr.clone()))
+        .layer(SetRequestIdLayer::new(hdr, MakeRequestUuid))
+}
+
+async fn send(req: Request<Body>) -> (StatusCode, Value) {
+    let res = app().oneshot(req).await.unwrap();
+    let status = res.status();
+    let bytes = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[test]
+fn error_codes_map_to_distinct_statuses() {
+    let codes: BTreeSet<i32> =
+        ErrorCode::ALL.iter().map(|c| c.code()).collect();
+    assert_eq!(codes.len(), ErrorCode::ALL.len());
+
+    assert_eq!(ErrorCode::Validation.status(), StatusCode::BAD_REQUEST);
+    for c in [
+        ErrorCode::ShowNotFound,
+        ErrorCode::JobNotFound,
+        ErrorCode::TicketNotFound,
+        ErrorCode::WebhookNotFound,
+        ErrorCode::RouteNotFound,
+    ] {
+        assert_eq!(c.status(), StatusCode::NOT_FOUND, "{c:?}");
+    }
+    assert_eq!(ErrorCode::Unauthorized.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(ErrorCode::Forbidden.status(), StatusCode::FORBIDDEN);
+    assert_eq!(ErrorCode::Conflict.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        ErrorCode::RateLimited.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    assert_eq!(
+        ErrorCode::Database.status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    // 不同资源的 404 保留各自的错误码
+    let ticket = AppError::TicketNotFound("7".into());
+    assert_eq!(ticket.code(), ErrorCode::TicketNotFound);
+    assert_eq!(ticket.status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn database_errors_are_sanitized() {
+    let err = AppError::from(sqlx::Error::PoolTimedOut);
+    assert_eq!(err.code(), ErrorCode::Database);
+    assert_eq!(err.public_message(), "database error");
+
+    let report = eyre::Report::new(sqlx::Error::RowNotFound);
+    assert_eq!(AppError::from(report).code(), ErrorCode::Database);
+    let other = eyre::eyre!("signer pool is not initialized");
+    let err = AppError::from(other);
+    assert_eq!(err.code(), ErrorCode::Internal);
+    assert_eq!(err.public_message(), "internal error");
+}
+
+#[tokio::test]
+async fn validation_errors_carry_field_details_and_request_id() {
+    let req = Request::builder()
+        .method("POST")
+        .uri("/show")
+        .header("content-type", "application/json")
+        .header(request_id_header(), "rid-42")
+        .body(Body::from(r#"{"name":"","max_tickets":0}"#))
+        .unwrap();
+    let (status, body) = send(req).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], ErrorCode::JsonInvalid.code());
+    assert_eq!(body["request_id"], "rid-42");
+    assert_eq!(
+        body["details"],
+        serde_json::json!([
+            {"field": "name", "message": "must not be empty"},
+            {"field": "max_tickets", "message": "must be positive"},
+        ])
+    );
+}
+
+#[tokio::test]
+async fn internal_errors_hide_details_and_unknown_routes_are_json() {
+    let (status, body) =
+        send(Request::builder().uri("/boom").body(Body::empty()).unwrap())
+            .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["code"], ErrorCode::Database.code());
+    assert_eq!(body["message"], "database error");
+    // 未携带 request id 时由 SetRequestIdLayer 生成
+    assert!(body["request_id"].as_str().is_some_and(|s| !s.is_empty()));
+    assert!(body.get("details").is_none());
+
+    let (status, body) =
+        send(Request::builder().uri("/nope").body(Body::empty()).unwrap())
+            .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], ErrorCode::RouteNotFound.code());
+}
+
+#[tokio::test]
+async fn invalid_path_ids_name_the_resource() {
+    for (uri, resource) in [("/show/abc", "show"), ("/tickets/0xzz", "ticket")]
+    {
+        let (status, body) =
+            send(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], ErrorCode::ParseIdInvalid.code());
+        let message = body["message"].as_str().unwrap();
+        assert!(
+            message.starts_with(&format!("invalid {resource} id: ")),
+            "{message}"
+        );
+    }
+    assert_eq!(ErrorCode::ParseIdInvalid.default_message(), "invalid id");
+}
@@ -0,0 +1,99 @@
+use backend::api::graphql::build_schema;
+use backend::repo::listing_repo::{
+    ListingCursor, ListingFilter, ListingStatus, build_listing_list,
+};
+use backend::utils::uint256::DbU256;
+use sqlx::Execute;
+
+// 以下查询都在执行前被拒绝或不触及 resolver 数据，因此无需数据库。
+
+#[tokio::test]
+async fn typename_resolves_without_data() {
+    let schema = build_schema(10, 1000);
+    let res = schema.execute("{ __typename }").await;
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    assert_eq!(
+        res.data.into_json().unwrap(),
+        serde_json::json!({ "__typename": "QueryRoot" })
+    );
+}
+
+#[tokio::test]
+async fn rejects_too_deep_queries() {
+    let schema = build_schema(5, 100_000);
+    let q = r#"{ owner(address: "0x0000000000000000000000000000000000000001") {
+        tickets { show { organizer { tickets { show { id } } } } } } }"#;
+    let res = schema.execute(q).await;
+    assert_eq!(res.errors.len(), 1);
+    assert!(res.errors[0].message.contains("nested too deep"));
+}
+
+#[tokio::test]
+async fn rejects_too_complex_queries() {
+    let schema = build_schema(10, 1000);
+    // 100 条 show * 每个 100 张票 远超 1000
+    let q =
+        "{ shows(first: 100) { items { tickets(limit: 100) { tokenId } } } }";
+    let res = schema.execute(q).await;
+    assert_eq!(res.errors.len(), 1);
+    assert!(res.errors[0].message.contains("too complex"));
+
+    // 超大 limit 按上限计算，同样被拒绝
+    let q = "{ shows(first: 2147483647) { items { owner: organizer { tickets(limit: 2147483647) { tokenId } } } } }";
+    let res = schema.execute(q).await;
+    assert_eq!(res.errors.len(), 1);
+    assert!(res.errors[0].message.contains("too complex"));
+}
+
+#[tokio::test]
+async fn rejects_invalid_arguments() {
+    let schema = build_schema(10, 1000);
+    let res = schema
+        .execute(r#"{ show(id: "not-a-number") { id } }"#)
+        .await;
+    assert_eq!(res.errors.len(), 1);
+    let res = schema
+        .execute(r#"{ owner(address: "0x12") { address } }"#)
+        .await;
+    assert_eq!(res.errors.len(), 1);
+    assert!(res.errors[0].message.contains("20-byte address"));
+}
+
+#[test]
+fn sdl_exposes_types_and_u256_scalar() {
+    let sdl = build_schema(10, 1000).sdl();
+    for needle in [
+        "scalar U256",
+        "type Show",
+        "type Ticket",
+        "type Listing",
+        "type Owner",
+        "type Did",
+        "enum TicketStatus",
+        "enum ListingStatus",
+        "nextCursor: String",
+    ] {
+        assert!(sdl.contains(needle), "missing {needle}");
+    }
+}
+
+#[test]
+fn listing_list_sql() {
+    let filter = ListingFilter {
+        chain_id: Some(10),
+        status: Some(ListingStatus::Active),
+        seller: Some("0xAbC".into()),
+        token_id: Some(DbU256::from(3u64)),
+    };
+    let cursor = ListingCursor {
+        listing_id: DbU256::from(9u64),
+    };
+    let mut qb = build_listing_list(&filter, Some(&cursor), 21);
+    let sql = qb.build().sql().to_string();
+    assert!(sql.contains("chain_id = $1"));
+    assert!(sql.contains("status = $2"));
+    assert!(sql.contains("lower(seller) = lower($3)"));
+    assert!(sql.contains("token_id = $4"));
+    assert!(sql.contains("listing_id > $5"));
+    assert!(sql.ends_with("ORDER BY listing_id ASC LIMIT $6"));
+}
@@ -0,0 +1,150 @@
+use alloy::primitives::{Address, address};
+use axum::{Router, body::Body, http::Request, routing::get};
+use backend::api::health::{CHECK_TIMEOUT, health, lag, timed};
+use backend::config::AddressMap;
+use backend::contract::Resume;
+use backend::contract::status::{IndexerStatus, SubscriptionState};
+use backend::repo::checkpoint_repo::CheckpointRecord;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn health_is_ok_without_dependencies() {
+    let app = Router::new().route("/health", get(health));
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["code"], 0);
+    assert_eq!(v["data"]["status"], "ok");
+}
+
+#[test]
+fn lag_in_blocks_and_seconds() {
+    // 链头 120 @ t=1000，检查点 100 @ t=760
+    assert_eq!(
+        lag(Some((120, 1000)), Some((100, Some(760)))),
+        (Some(20), Some(240))
+    );
+    // 检查点区块时间未知：只给出区块差
+    assert_eq!(lag(Some((120, 1000)), Some((100, None))), (Some(20), None));
+    // RPC 节点落后于检查点时不出现负数
+    assert_eq!(
+        lag(Some((90, 900)), Some((100, Some(1000)))),
+        (Some(0), Some(0))
+    );
+    assert_eq!(lag(None, Some((100, Some(1)))), (None, None));
+    assert_eq!(lag(Some((1, 1)), None), (None, None));
+}
+
+#[test]
+fn indexer_status_tracks_state_and_block_advances() {
+    const C: i64 = 31337;
+    let status = IndexerStatus::default();
+    assert_eq!(
+        status.snapshot(C).subscription,
+        SubscriptionState::Connecting
+    );
+    assert!(status.snapshot(C).last_log_at.is_none());
+
+    status.set_state(C, SubscriptionState::Failed, Some("boom".into()));
+    let snap = status.snapshot(C);
+    assert_eq!(snap.subscription, SubscriptionState::Failed);
+    assert_eq!(snap.last_error.as_deref(), Some("boom"));
+    status.set_state(C, SubscriptionState::Subscribed, None);
+    assert!(status.snapshot(C).last_error.is_none());
+
+    // 同一区块的后续日志不再推进进度
+    assert!(status.record_log(C, "show_manager", 10));
+    assert!(!status.record_log(C, "show_manager", 10));
+    assert!(!status.record_log(C, "show_manager", 9));
+    assert!(status.record_log(C, "marketplace", 9));
+    assert!(status.record_log(C, "show_manager", 11));
+    assert!(status.snapshot(C).last_log_at.is_some());
+
+    // 各链互不影响：另一条链仍在连接中，同一区块也能推进
+    assert_eq!(
+        status.snapshot(10).subscription,
+        SubscriptionState::Connecting
+    );
+    assert!(status.record_log(10, "show_manager", 11));
+}
+
+#[test]
+fn address_map_names_configured_contracts() {
+    let show = address!("0x8A791620dd6260079BF849Dc5567aDC3F2FdC318");
+    let did = address!("0x5FC8d32690cc91D4c39d9d3abcBD16989F875707");
+    let market = address!("0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9");
+    let map = AddressMap {
+        did_registry: did,
+        show_manager: show,
+        ticket_manager: None,
+        marketplace: Some(market),
+    };
+    let names: Vec<_> = map.contracts().into_iter().map(|(n, _)| n).collect();
+    assert_eq!(names, ["show_manager", "did_registry", "marketplace"]);
+    assert_eq!(map.contract_name(&market), Some("marketplace"));
+    assert_eq!(map.contract_name(&Address::ZERO), None);
+}
+
+#[test]
+fn resume_continues_after_last_processed_log() {
+    let checkpoint = |last_log_index| CheckpointRecord {
+        chain_id: 31337,
+        contract: "show_manager".into(),
+        address: "0x0".into(),
+        last_block: 100,
+        last_log_index,
+        updated_at: chrono::Utc::now(),
+    };
+
+    // 区块 100 处理到第 3 条日志时中断：从区块 100 继续，只跳过已处理的日志
+    let r =
+        Resume::from_checkpoint(Some(&checkpoint(Some(3))), Some(1)).unwrap();
+    assert_eq!((r.block, r.replay), (100, false));
+    assert!(r.skips(99, 9));
+    assert!(r.skips(100, 3));
+    assert!(!r.skips(100, 4));
+    assert!(!r.skips(101, 0));
+
+    // 升级前的检查点只有区块号：整块已处理
+    let r = Resume::from_checkpoint(Some(&checkpoint(None)), Some(1)).unwrap();
+    assert_eq!(r.block, 101);
+    assert!(r.skips(100, 50));
+    assert!(!r.skips(101, 0));
+
+    // 没有检查点：从部署区块导入历史，不发送通知
+    let r = Resume::from_checkpoint(None, Some(7)).unwrap();
+    assert_eq!((r.block, r.after, r.replay), (7, None, true));
+    assert!(Resume::from_checkpoint(None, None).is_none());
+}
+
+#[tokio::test]
+async fn failed_checks_do_not_leak_error_details() {
+    let failed = timed("postgres", async {
+        Err(eyre::eyre!(
+            "password authentication failed for user \"app\""
+        ))
+    })
+    .await;
+    assert!(!failed.ok);
+    assert_eq!(failed.error, Some("unavailable"));
+
+    let slow = timed("rpc", async {
+        tokio::time::sleep(CHECK_TIMEOUT * 2).await;
+        Ok(())
+    })
+    .await;
+    assert!(!slow.ok);
+    assert_eq!(slow.error, Some("timeout"));
+    assert!(timed("redis", async { Ok(()) }).await.ok);
+}
@@ -0,0 +1,95 @@
+use alloy::sol_types::SolEvent;
+use axum::{Router, body::Body, http::Request, routing::get};
+use backend::api::metrics::track_http;
+use backend::contract::bindings::{Marketplace, ShowManager};
+use backend::contract::event::names::event_name;
+use backend::db::cache::AppCache;
+use backend::metrics::{self, observe_rpc, record_indexer_event};
+use backend::utils::uint256::DbU256;
+use tower::ServiceExt;
+
+// 指标注册表是进程级全局的，各测试使用互不重叠的标签值。
+
+#[tokio::test]
+async fn http_metrics_use_route_templates() {
+    let app = Router::new()
+        .route("/metrics-test/{id}", get(|| async { "ok" }))
+        .layer(axum::middleware::from_fn(track_http));
+    for uri in [
+        "/metrics-test/1",
+        "/metrics-test/2",
+        "/metrics-test-missing",
+    ] {
+        app.clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+    }
+    let text = metrics::get().render();
+    assert!(text.contains(
+        r#"http_requests_total{method="GET",route="/metrics-test/{id}",status="200"} 2"#
+    ));
+    assert!(text.contains(
+        r#"http_requests_total{method="GET",route="unmatched",status="404"}"#
+    ));
+    assert!(text.contains(
+        r#"http_request_duration_seconds_count{method="GET",route="/metrics-test/{id}",status="200"} 2"#
+    ));
+}
+
+#[tokio::test]
+async fn rpc_latency_is_labelled_by_outcome() {
+    let _ = observe_rpc("test_ok", async { Ok::<_, ()>(1) }).await;
+    let _ = observe_rpc("test_err", async { Err::<(), _>("boom") }).await;
+    let text = metrics::get().render();
+    assert!(text.contains(
+        r#"rpc_request_duration_seconds_count{method="test_ok",outcome="ok"} 1"#
+    ));
+    assert!(text.contains(
+        r#"rpc_request_duration_seconds_count{method="test_err",outcome="error"} 1"#
+    ));
+}
+
+#[test]
+fn event_names_come_from_the_abi() {
+    let created = ShowManager::ShowCreated::SIGNATURE_HASH;
+    assert_eq!(event_name("show_manager", Some(&created)), "ShowCreated");
+    let listed = Marketplace::ListingCreated::SIGNATURE_HASH;
+    assert_eq!(event_name("marketplace", Some(&listed)), "ListingCreated");
+    // 选择器不属于该合约、没有 topic0 或合约未知
+    assert_eq!(event_name("marketplace", Some(&created)), "unknown");
+    assert_eq!(event_name("show_manager", None), "unknown");
+    assert_eq!(event_name("nope", Some(&created)), "unknown");
+
+    record_indexer_event(31337, "test_contract", "ShowCreated", true);
+    record_indexer_event(31337, "test_contract", "ShowCreated", false);
+    let text = metrics::get().render();
+    assert!(text.contains(
+        r#"indexer_events_total{chain_id="31337",contract="test_contract",event="ShowCreated",outcome="processed"} 1"#
+    ));
+    assert!(text.contains(
+        r#"indexer_events_total{chain_id="31337",contract="test_contract",event="ShowCreated",outcome="error"} 1"#
+    ));
+}
+
+#[tokio::test]
+async fn cache_lookups_count_hits_and_misses() {
+    let cache = AppCache::memory(16, 60);
+    let before = lookups("ticket", "hit");
+    let missed = lookups("ticket", "miss");
+    assert!(
+        cache
+            .get_ticket(31337, &DbU256::from(424242u64))
+            .await
+            .is_none()
+    );
+    assert_eq!(lookups("ticket", "miss"), missed + 1);
+    assert_eq!(lookups("ticket", "hit"), before);
+}
+
+fn lookups(kind: &str, result: &str) -> u64 {
+    metrics::get()
+        .cache_lookups
+        .with_label_values(&["memory", kind, result])
+        .get()
+}
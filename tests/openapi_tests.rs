@@ -0,0 +1,154 @@
+use axum::{
+    Router,
+    body::Body,
+    extract::FromRef,
+    http::{Method, Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use backend::api::{AppState, openapi::ApiDoc, routes};
+use std::collections::{BTreeMap, BTreeSet};
+use tower::ServiceExt;
+use utoipa::OpenApi;
+
+/// 路由探测用的 state：route_layer 在提取参数之前就返回，AppState 永远不会被取出
+#[derive(Clone)]
+struct NoState;
+
+impl FromRef<NoState> for AppState {
+    fn from_ref(_: &NoState) -> Self {
+        unreachable!("probe requests never reach a handler")
+    }
+}
+
+async fn matched(_req: Request<Body>, _next: Next) -> Response {
+    StatusCode::IM_A_TEAPOT.into_response()
+}
+
+/// 命中路由（路径 + 方法）时返回 418，未命中则为 axum 的 404/405
+fn probe_router() -> Router {
+    // MethodRouter::route_layer 只包装已注册的方法，方法不匹配时仍返回 405
+    routes::<NoState>()
+        .into_iter()
+        .fold(Router::new(), |r, (path, method)| {
+            r.route(path, method.route_layer(middleware::from_fn(matched)))
+        })
+        .with_state(NoState)
+}
+
+const METHODS: [Method; 5] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+fn spec_operations() -> BTreeMap<String, Vec<Method>> {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(|(path, item)| {
+            let methods = METHODS
+                .iter()
+                .filter(|m| item.get(m.as_str().to_lowercase()).is_some())
+                .cloned()
+                .collect();
+            (path.clone(), methods)
+        })
+        .collect()
+}
+
+#[test]
+fn spec_paths_match_routes() {
+    let routed: BTreeSet<String> = routes::<NoState>()
+        .into_iter()
+        .map(|(p, _)| p.to_string())
+        .collect();
+    let documented: BTreeSet<String> = spec_operations().into_keys().collect();
+    assert_eq!(
+        routed.difference(&documented).collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "routes missing from the OpenAPI spec"
+    );
+    assert_eq!(
+        documented.difference(&routed).collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "spec paths without a route"
+    );
+}
+
+#[tokio::test]
+async fn spec_methods_match_routes() {
+    let app = probe_router();
+    for (path, documented) in spec_operations() {
+        let uri = path.replace("{id}", "1");
+        for method in METHODS {
+            let res = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(method.clone())
+                        .uri(&uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let routed = res.status() == StatusCode::IM_A_TEAPOT;
+            assert_eq!(
+                routed,
+                documented.contains(&method),
+                "{method} {path}: routed={routed}, documented={}",
+                documented.contains(&method)
+            );
+        }
+    }
+}
+
+#[test]
+fn spec_describes_u256_and_error_codes() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let schemas = &spec["components"]["schemas"];
+    assert_eq!(schemas["DbU256"]["type"], "string");
+    assert!(schemas["ShowDataRecord"]["properties"]["id"].is_object());
+    assert!(schemas["CreateShowReq"].is_object());
+    assert!(schemas["UpdateShowReq"].is_object());
+
+    let text = spec.to_string();
+    for code in backend::api::error::ErrorCode::ALL {
+        assert!(
+            text.contains(&format!("{}: {:?}", code.code(), code)),
+            "ErrorCode {code:?} missing from spec"
+        );
+    }
+}
+#[test]
+fn spec_refs_resolve() {
+    fn collect(v: &serde_json::Value, out: &mut Vec<String>) {
+        match v {
+            serde_json::Value::Object(map) => {
+                if let Some(r) = map.get("$ref").and_then(|r| r.as_str()) {
+                    out.push(r.to_string());
+                }
+                map.values().for_each(|v| collect(v, out));
+            }
+            serde_json::Value::Array(items) => {
+                items.iter().for_each(|v| collect(v, out))
+            }
+            _ => {}
+        }
+    }
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let mut refs = Vec::new();
+    collect(&spec, &mut refs);
+    for r in refs {
+        let name = r.trim_start_matches("#/components/schemas/");
+        assert!(
+            spec["components"]["schemas"].get(name).is_some(),
+            "unresolved schema reference {r}"
+        );
+    }
+}
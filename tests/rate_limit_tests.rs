@@ -0,0 +1,270 @@
+use alloy::signers::{SignerSync, local::PrivateKeySigner};
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode},
+    middleware,
+    routing::{get, post},
+};
+use backend::api::auth::{
+    AUTH_ADDRESS, AUTH_SIGNATURE, AUTH_TIMESTAMP, Authenticator, auth_message,
+    authenticate,
+};
+use backend::api::rate_limit::{
+    RateLimitConfig, RateLimiter, RouteBudget, enforce, parse_route_budgets,
+};
+use std::sync::atomic::{AtomicI64, Ordering};
+use tower::ServiceExt;
+
+fn config(read: u64, write: u64, routes: &str) -> RateLimitConfig {
+    RateLimitConfig {
+        enabled: true,
+        window_secs: 60,
+        read_limit: read,
+        write_limit: write,
+        routes: parse_route_budgets(routes).unwrap(),
+        trust_forwarded: false,
+    }
+}
+
+fn app(cfg: RateLimitConfig) -> Router {
+    Router::new()
+        .route("/items", get(|| async { "ok" }))
+        .route("/show", post(|| async { "ok" }))
+        .route("/health", get(|| async { "ok" }))
+        .layer(middleware::from_fn_with_state(
+            RateLimiter::memory(cfg),
+            enforce,
+        ))
+}
+
+async fn call(
+    app: &Router,
+    method: Method,
+    uri: &str,
+) -> axum::response::Response {
+    let req = Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap()
+}
+
+fn header(res: &axum::response::Response, name: &str) -> Option<String> {
+    res.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+#[test]
+fn route_budgets_parse_and_resolve() {
+    let routes = parse_route_budgets("POST /show=10, /graphql=60").unwrap();
+    assert_eq!(
+        routes[0],
+        RouteBudget {
+            method: Some(Method::POST),
+            path: "/show".into(),
+            limit: 10
+        }
+    );
+    assert!(routes[1].method.is_none());
+    assert!(parse_route_budgets("/show").is_err());
+    assert!(parse_route_budgets("/show=0").is_err());
+    assert!(parse_route_budgets("show=1").is_err());
+
+    let cfg = config(100, 5, "POST /show=10,/graphql=60");
+    assert_eq!(
+        cfg.budget_for(&Method::POST, "/show"),
+        Some(("POST /show".to_string(), 10))
+    );
+    assert_eq!(
+        cfg.budget_for(&Method::POST, "/graphql"),
+        Some(("/graphql".to_string(), 60))
+    );
+    assert_eq!(
+        cfg.budget_for(&Method::GET, "/shows"),
+        Some(("read".to_string(), 100))
+    );
+    assert_eq!(
+        cfg.budget_for(&Method::DELETE, "/show/{id}"),
+        Some(("write".to_string(), 5))
+    );
+    assert_eq!(cfg.budget_for(&Method::GET, "/health"), None);
+}
+
+#[tokio::test]
+async fn exceeding_budget_returns_429_envelope_with_headers() {
+    let app = app(config(2, 5, ""));
+    let first = call(&app, Method::GET, "/items").await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(header(&first, "ratelimit-limit").as_deref(), Some("2"));
+    assert_eq!(header(&first, "ratelimit-remaining").as_deref(), Some("1"));
+    assert!(header(&first, "retry-after").is_none());
+
+    assert_eq!(
+        call(&app, Method::GET, "/items").await.status(),
+        StatusCode::OK
+    );
+    let limited = call(&app, Method::GET, "/items").await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        header(&limited, "ratelimit-remaining").as_deref(),
+        Some("0")
+    );
+    let retry: u64 = header(&limited, "retry-after").unwrap().parse().unwrap();
+    assert!((1..=60).contains(&retry));
+    let body = axum::body::to_bytes(limited.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], 4000);
+    assert!(
+        json["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("rate limit exceeded")
+    );
+
+    // 写请求与探针不受读额度影响
+    assert_eq!(
+        call(&app, Method::POST, "/show").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        call(&app, Method::GET, "/health").await.status(),
+        StatusCode::OK
+    );
+    assert!(
+        header(&call(&app, Method::GET, "/health").await, "ratelimit-limit")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn large_pages_cost_more() {
+    let app = app(config(10, 5, ""));
+    let res = call(&app, Method::GET, "/items?limit=1000").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(header(&res, "ratelimit-remaining").as_deref(), Some("0"));
+    let res = call(&app, Method::GET, "/items?limit=10").await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+/// 签名时间依次错开，每个请求的签名都不同，不会被当作重放
+static SIGNED: AtomicI64 = AtomicI64::new(0);
+
+/// 经代理转发的请求；key 不为 None 时带调用方签名
+fn forwarded_req(xff: &str, key: Option<&PrivateKeySigner>) -> Request<Body> {
+    let mut req = Request::builder()
+        .uri("/items")
+        .header("x-forwarded-for", xff);
+    if let Some(key) = key {
+        let now = chrono::Utc::now().timestamp()
+            - SIGNED.fetch_add(1, Ordering::Relaxed);
+        let message = auth_message(&Method::GET, "/items", now, &[]);
+        let signature = key.sign_message_sync(message.as_bytes()).unwrap();
+        req = req
+            .header(AUTH_ADDRESS, key.address().to_string())
+            .header(AUTH_TIMESTAMP, now.to_string())
+            .header(AUTH_SIGNATURE, signature.to_string());
+    }
+    req.body(Body::empty()).unwrap()
+}
+
+async fn status(app: &Router, req: Request<Body>) -> StatusCode {
+    app.clone().oneshot(req).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn authenticated_address_is_counted_in_addition_to_ip() {
+    let app = app(RateLimitConfig {
+        trust_forwarded: true,
+        ..config(1, 5, "")
+    })
+    .layer(middleware::from_fn_with_state(
+        Authenticator::memory(),
+        authenticate,
+    ));
+    let key = PrivateKeySigner::random();
+    assert_eq!(
+        status(&app, forwarded_req("1.1.1.1", Some(&key))).await,
+        StatusCode::OK
+    );
+    // 同一地址换 IP 仍按地址计数
+    assert_eq!(
+        status(&app, forwarded_req("2.2.2.2", Some(&key))).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    // 额度用完的 IP 上换一把新私钥签名也不能绕过
+    let fresh = PrivateKeySigner::random();
+    assert_eq!(
+        status(&app, forwarded_req("1.1.1.1", Some(&fresh))).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    assert_eq!(
+        status(&app, forwarded_req("1.1.1.1", None)).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn forwarded_for_is_only_used_when_trusted() {
+    let untrusted = app(config(1, 5, ""));
+    assert_eq!(
+        status(&untrusted, forwarded_req("1.1.1.1", None)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&untrusted, forwarded_req("2.2.2.2", None)).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    let trusted = app(RateLimitConfig {
+        trust_forwarded: true,
+        ..config(1, 5, "")
+    });
+    assert_eq!(
+        status(&trusted, forwarded_req("1.1.1.1", None)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&trusted, forwarded_req("2.2.2.2", None)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&trusted, forwarded_req("1.1.1.1", None)).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn forged_leading_forwarded_entry_is_ignored() {
+    let app = app(RateLimitConfig {
+        trust_forwarded: true,
+        ..config(1, 5, "")
+    });
+    // 代理追加的是最后一个地址；客户端伪造的前缀每次不同也落在同一个桶
+    assert_eq!(
+        status(&app, forwarded_req("9.9.9.1, 3.3.3.3", None)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(&app, forwarded_req("9.9.9.2, 3.3.3.3", None)).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn disabled_limiter_passes_through() {
+    let app = app(RateLimitConfig {
+        enabled: false,
+        ..config(1, 1, "")
+    });
+    for _ in 0..3 {
+        let res = call(&app, Method::GET, "/items").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(header(&res, "ratelimit-limit").is_none());
+    }
+}
@@ -0,0 +1,90 @@
+use backend::{
+    api::stream::{ServerMessage, Subscriptions, event_stream},
+    realtime::{EventHub, RealtimeEvent, Topic},
+    utils::uint256::DbU256,
+};
+use futures::StreamExt;
+use std::str::FromStr;
+use std::time::Duration;
+
+fn sold(show: u64, token: u64) -> RealtimeEvent {
+    RealtimeEvent::TicketSold {
+        chain_id: 31337,
+        show_id: DbU256::from(show),
+        token_id: DbU256::from(token),
+        owner: "0xabc".into(),
+        price: DbU256::from(100u64),
+    }
+}
+
+#[test]
+fn topics_parse_and_normalize() {
+    assert_eq!(Topic::from_str("shows").unwrap(), Topic::Shows);
+    assert_eq!(
+        Topic::from_str("show:0x10").unwrap(),
+        Topic::from_str("show:16").unwrap()
+    );
+    assert_eq!(Topic::from_str("ticket:7").unwrap().to_string(), "ticket:7");
+    assert!(Topic::from_str("show:").is_err());
+    assert!(Topic::from_str("users").is_err());
+
+    let ev = sold(1, 7);
+    assert!(ev.matches(&Topic::Show(DbU256::from(1u64))));
+    assert!(ev.matches(&Topic::Ticket(DbU256::from(7u64))));
+    assert!(!ev.matches(&Topic::Shows));
+    let json = serde_json::to_value(&ev).unwrap();
+    assert_eq!(json["type"], "ticket_sold");
+    assert_eq!(json["show_id"], "1");
+}
+
+#[test]
+fn ws_subscription_protocol() {
+    let mut subs = Subscriptions::default();
+    let reply = subs.handle(r#"{"op":"subscribe","topics":["show:0x1"]}"#);
+    assert!(matches!(reply, ServerMessage::Subscribed { .. }));
+    let ev = sold(1, 7);
+    assert_eq!(subs.matched(&ev), vec![Topic::Show(DbU256::from(1u64))]);
+    assert!(subs.matched(&sold(2, 8)).is_empty());
+
+    assert!(matches!(
+        subs.handle(r#"{"op":"ping"}"#),
+        ServerMessage::Pong
+    ));
+    assert!(matches!(
+        subs.handle(r#"{"op":"subscribe","topics":["nope"]}"#),
+        ServerMessage::Error { .. }
+    ));
+    assert!(matches!(
+        subs.handle("not json"),
+        ServerMessage::Error { .. }
+    ));
+
+    subs.handle(r#"{"op":"unsubscribe","topics":["show:1"]}"#);
+    assert!(subs.matched(&ev).is_empty());
+
+    let too_many: Vec<String> =
+        (0..101).map(|i| format!("\"show:{i}\"")).collect();
+    let msg =
+        format!(r#"{{"op":"subscribe","topics":[{}]}}"#, too_many.join(","));
+    assert!(matches!(subs.handle(&msg), ServerMessage::Error { .. }));
+}
+
+#[tokio::test]
+async fn local_hub_delivers_filtered_events() {
+    let hub = EventHub::local(16);
+    let topic = Topic::Show(DbU256::from(1u64));
+    let mut stream =
+        Box::pin(event_stream(hub.subscribe(), move |ev| ev.matches(&topic)));
+    hub.publish(sold(2, 9)).await;
+    hub.publish(sold(1, 7)).await;
+    let ev = tokio::time::timeout(Duration::from_secs(1), stream.next())
+        .await
+        .unwrap()
+        .unwrap();
+    match &*ev {
+        RealtimeEvent::TicketSold { token_id, .. } => {
+            assert_eq!(*token_id, DbU256::from(7u64))
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+}
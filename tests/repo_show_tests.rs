@@ -22,6 +22,7 @@ async fn upsert_and_get_show_roundtrip() {
     let id = DbU256(U256::from(42u64));
 
     let basic = ShowCreatedRecord {
+        chain_id: 31337,
         show_id: id.clone(),
         tx_hash: Some(format!("0x{:064x}", 0xfeed_u64)),
         block_number: Some(DbU256(U256::from(100u64))),
@@ -30,6 +31,7 @@ async fn upsert_and_get_show_roundtrip() {
         created_at: Utc::now(),
     };
     let detail = ShowCreatedDetailRecord {
+        chain_id: 31337,
         show_id: id.clone(),
         start_time: DbU256(U256::from(1_735_689_600u64)),
         end_time: DbU256(U256::from(1_735_696_800u64)),
@@ -46,6 +48,7 @@ async fn upsert_and_get_show_roundtrip() {
         created_at: Utc::now(),
     };
     let data = ShowDataRecord {
+        chain_id: 31337,
         id: id.clone(),
         name: "Test Show".to_string(),
         description: "A test show".to_string(),
@@ -57,14 +60,213 @@ async fn upsert_and_get_show_roundtrip() {
         is_active: true,
         organizer: "tester".to_string(),
         created_at: Utc::now(),
+        version: 0,
     };
 
     upsert_show_all(db.pool(), &basic, &detail, &data)
         .await
         .expect("upsert");
 
-    let found = get_show_by_id(db.pool(), id.clone()).await.expect("get");
+    let found = get_show_by_id(db.pool(), 31337, id.clone())
+        .await
+        .expect("get");
     let found = found.expect("some");
     assert_eq!(found.id.to_string(), id.to_string());
     assert_eq!(found.name, data.name);
+    // 其他链上没有这个 id
+    let other = get_show_by_id(db.pool(), 10, id.clone())
+        .await
+        .expect("get");
+    assert!(other.is_none());
+}
+
+#[test]
+fn build_show_search_applies_filters_and_sort() {
+    use backend::repo::show_repo::{
+        ShowFilter, ShowSortKey, SortOrder, build_show_search,
+    };
+
+    let filter = ShowFilter {
+        is_active: Some(true),
+        organizer: Some("0xabc".into()),
+        event_time_from: Some(DbU256(U256::from(1u64))),
+        location: Some("Hall".into()),
+        max_price: Some(DbU256(U256::from(10u64))),
+        q: Some("rock concert".into()),
+        ..Default::default()
+    };
+    let qb = build_show_search(
+        &filter,
+        Some(ShowSortKey::Price),
+        SortOrder::Asc,
+        None,
+        20,
+        0,
+    );
+    let sql = qb.sql();
+    assert!(sql.contains("is_active = $1"));
+    assert!(sql.contains("lower(organizer) = lower($2)"));
+    assert!(sql.contains("event_time >= $3"));
+    assert!(sql.contains("location ILIKE $4"));
+    assert!(sql.contains("ticket_price <= $5"));
+    assert!(
+        sql.contains("search_vector @@ websearch_to_tsquery('simple', $6)")
+    );
+    assert!(sql.contains("ORDER BY ticket_price ASC, id ASC"));
+    assert!(!sql.contains("event_time <="));
+
+    // 无 sort 且有 q：按相关度排序
+    let qb = build_show_search(&filter, None, SortOrder::Desc, None, 20, 0);
+    assert!(qb.sql().contains("ORDER BY ts_rank(search_vector"));
+
+    // 无任何过滤：只排除已软删除的演出，保持原有 created_at 倒序
+    let empty = ShowFilter::default();
+    let qb = build_show_search(&empty, None, SortOrder::Desc, None, 20, 0);
+    assert!(
+        qb.sql()
+            .contains("WHERE deleted_at IS NULL ORDER BY created_at DESC")
+    );
+}
+
+#[test]
+fn build_show_search_with_cursor_uses_keyset() {
+    use backend::repo::show_repo::{
+        ShowCursor, ShowCursorKey, ShowFilter, ShowSortKey, SortOrder,
+        build_show_search,
+    };
+
+    let filter = ShowFilter::default();
+    let cursor = ShowCursor {
+        key: ShowCursorKey::Price(DbU256(U256::from(5u64))),
+        order: SortOrder::Asc,
+        id: DbU256(U256::from(9u64)),
+    };
+    assert!(cursor.matches(Some(ShowSortKey::Price), SortOrder::Asc));
+    assert!(!cursor.matches(Some(ShowSortKey::Price), SortOrder::Desc));
+    assert!(!cursor.matches(None, SortOrder::Asc));
+
+    let qb = build_show_search(
+        &filter,
+        Some(ShowSortKey::Price),
+        SortOrder::Asc,
+        Some(&cursor),
+        21,
+        100,
+    );
+    let sql = qb.sql();
+    assert!(sql.contains("AND (ticket_price, id) > ($1, $2)"));
+    assert!(sql.contains("ORDER BY ticket_price ASC, id ASC"));
+
+    let cursor = ShowCursor {
+        key: ShowCursorKey::CreatedAt(chrono::Utc::now()),
+        order: SortOrder::Desc,
+        id: DbU256(U256::from(1u64)),
+    };
+    let qb =
+        build_show_search(&filter, None, SortOrder::Desc, Some(&cursor), 21, 0);
+    assert!(qb.sql().contains("AND (created_at, id) < ($1, $2)"));
+}
+
+#[test]
+fn build_ticket_and_transfer_lists_use_keyset() {
+    use backend::repo::ticket_repo::{
+        TicketCursor, TicketFilter, TransferCursor, TransferFilter,
+        build_ticket_list, build_transfer_list,
+    };
+
+    let filter = TicketFilter {
+        owner: Some("0xabc".into()),
+        ..Default::default()
+    };
+    let after = TicketCursor {
+        token_id: DbU256(U256::from(10u64)),
+    };
+    let qb = build_ticket_list(&filter, Some(&after), 21);
+    let sql = qb.sql();
+    assert!(sql.contains("lower(owner) = lower($1)"));
+    assert!(sql.contains("token_id > $2"));
+    assert!(sql.contains("ORDER BY token_id ASC LIMIT $3"));
+
+    let filter = TransferFilter {
+        address: Some("0xabc".into()),
+        ..Default::default()
+    };
+    let after = TransferCursor {
+        block_number: 100,
+        log_index: 2,
+    };
+    let qb = build_transfer_list(&filter, Some(&after), 21);
+    let sql = qb.sql();
+    assert!(sql.contains("(block_number, log_index) < ($3, $4)"));
+    assert!(sql.contains("ORDER BY block_number DESC, log_index DESC"));
+}
+
+#[test]
+fn list_builders_scope_by_chain_first() {
+    use backend::repo::show_repo::{ShowFilter, SortOrder, build_show_search};
+    use backend::repo::ticket_repo::{
+        TicketFilter, TransferFilter, build_ticket_list, build_transfer_list,
+    };
+
+    let filter = ShowFilter {
+        chain_id: Some(10),
+        is_active: Some(true),
+        ..Default::default()
+    };
+    let qb = build_show_search(&filter, None, SortOrder::Desc, None, 20, 0);
+    let sql = qb.sql();
+    assert!(sql.contains("WHERE deleted_at IS NULL AND chain_id = $1"));
+    assert!(sql.contains("is_active = $2"));
+
+    let filter = TicketFilter {
+        chain_id: Some(10),
+        owner: Some("0xabc".into()),
+        ..Default::default()
+    };
+    let qb = build_ticket_list(&filter, None, 21);
+    let sql = qb.sql();
+    assert!(sql.contains("chain_id = $1"));
+    assert!(sql.contains("lower(owner) = lower($2)"));
+
+    let filter = TransferFilter {
+        chain_id: Some(10),
+        ..Default::default()
+    };
+    let qb = build_transfer_list(&filter, None, 21);
+    assert!(qb.sql().contains("chain_id = $1"));
+}
+
+#[test]
+fn build_show_update_sets_only_changed_columns() {
+    use backend::repo::show_repo::{ShowPatch, build_show_update};
+    use backend::utils::uint256::DbU256;
+
+    let id = DbU256::from(7u64);
+    assert!(build_show_update(1, &id, &ShowPatch::default(), None).is_none());
+
+    let patch = ShowPatch {
+        name: Some("Renamed".into()),
+        max_tickets: Some(DbU256::from(50u64)),
+        ..Default::default()
+    };
+    let qb = build_show_update(1, &id, &patch, None).unwrap();
+    let sql = qb.sql();
+    assert!(sql.starts_with(
+        "UPDATE shows SET name = $1, max_tickets = $2, version = version + 1, updated_at = NOW() WHERE chain_id = $3 AND id = $4"
+    ));
+    assert!(sql.contains("AND deleted_at IS NULL"));
+    assert!(!sql.contains("created_at ="));
+    assert!(!sql.contains("description ="));
+    // 不能把总票数改到已售之下
+    assert!(sql.contains("AND sold_tickets <= $5"));
+    assert!(sql.contains("RETURNING "));
+
+    let versions = [3i64];
+    let patch = ShowPatch {
+        is_active: Some(false),
+        ..Default::default()
+    };
+    let qb = build_show_update(1, &id, &patch, Some(&versions)).unwrap();
+    assert!(qb.sql().contains("AND version = ANY($4)"));
+    assert!(!qb.sql().contains("sold_tickets <="));
 }
@@ -0,0 +1,48 @@
+use backend::api::show_jobs::ClaimedTxState;
+use backend::repo::show_job_repo::ShowJobStatus;
+
+#[test]
+fn claimed_tx_is_classified_from_chain_state() {
+    // 交易可查到时，无论 nonce 如何都不能判失败
+    assert_eq!(
+        ClaimedTxState::classify(true, None, 8, 7),
+        ClaimedTxState::Known
+    );
+    assert_eq!(
+        ClaimedTxState::classify(true, None, 7, 7),
+        ClaimedTxState::Known
+    );
+    assert_eq!(
+        ClaimedTxState::classify(true, Some(true), 8, 7),
+        ClaimedTxState::Known
+    );
+    assert_eq!(
+        ClaimedTxState::classify(false, None, 8, 7),
+        ClaimedTxState::NonceTaken
+    );
+    assert_eq!(
+        ClaimedTxState::classify(false, None, 7, 7),
+        ClaimedTxState::NotBroadcast
+    );
+}
+
+#[test]
+fn reverted_receipt_fails_the_job() {
+    // 已打包但执行失败：ShowCreated 不会出现，不能一直停在 SUBMITTED
+    assert_eq!(
+        ClaimedTxState::classify(true, Some(false), 8, 7),
+        ClaimedTxState::Reverted
+    );
+    assert_eq!(
+        ClaimedTxState::classify(false, Some(false), 8, 7),
+        ClaimedTxState::Reverted
+    );
+}
+
+#[test]
+fn submitting_status_serializes_uppercase() {
+    assert_eq!(
+        serde_json::to_value(ShowJobStatus::Submitting).unwrap(),
+        "SUBMITTING"
+    );
+}
@@ -0,0 +1,116 @@
+use axum::{Router, routing::get};
+use backend::shutdown::{self, Shutdown};
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::Notify, time::timeout};
+
+#[tokio::test]
+async fn trigger_wakes_all_waiters() {
+    let (trigger, shutdown) = shutdown::channel();
+    assert!(!shutdown.is_triggered());
+    let waiters: Vec<_> = (0..3)
+        .map(|_| {
+            let s = shutdown.clone();
+            tokio::spawn(async move { s.wait().await })
+        })
+        .collect();
+    trigger.trigger();
+    for w in waiters {
+        timeout(Duration::from_secs(1), w).await.unwrap().unwrap();
+    }
+    // 触发之后再等待立即返回
+    assert!(shutdown.is_triggered());
+    timeout(Duration::from_millis(100), shutdown.wait())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn dropped_trigger_counts_as_shutdown_but_never_does_not() {
+    let (trigger, shutdown) = shutdown::channel();
+    drop(trigger);
+    timeout(Duration::from_millis(100), shutdown.wait())
+        .await
+        .unwrap();
+
+    let never = Shutdown::never();
+    assert!(
+        timeout(Duration::from_millis(50), never.wait())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn serve_drains_in_flight_requests() {
+    let entered = Arc::new(Notify::new());
+    let app = Router::new().route(
+        "/slow",
+        get({
+            let entered = entered.clone();
+            move || async move {
+                entered.notify_one();
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                "done"
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (trigger, shutdown) = shutdown::channel();
+    let server = tokio::spawn(backend::api::serve(listener, app, shutdown));
+
+    let client = reqwest::Client::new();
+    let in_flight = tokio::spawn({
+        let client = client.clone();
+        async move {
+            client
+                .get(format!("http://{addr}/slow"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        }
+    });
+    // 请求进入 handler 后再触发关闭
+    entered.notified().await;
+    trigger.trigger();
+
+    assert_eq!(in_flight.await.unwrap(), "done");
+    timeout(Duration::from_secs(2), server)
+        .await
+        .expect("server should stop after draining")
+        .unwrap()
+        .unwrap();
+    // 关闭后不再接受新连接
+    assert!(
+        client
+            .get(format!("http://{addr}/slow"))
+            .send()
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn webhook_worker_stops_on_shutdown() {
+    use backend::repo::traits::Repos;
+    use backend::webhook::worker::{WebhookWorker, WorkerSettings};
+    let settings = WorkerSettings {
+        max_attempts: 3,
+        timeout: Duration::from_secs(1),
+        poll_interval: Duration::from_secs(3600),
+        retry_base: Duration::from_secs(1),
+        retry_max: Duration::from_secs(1),
+        batch_size: 1,
+    };
+    // 关闭已触发时 worker 不应再领取投递
+    let (repos, _) = Repos::memory();
+    let worker = WebhookWorker::new(repos.webhooks, settings).unwrap();
+    let (trigger, shutdown) = shutdown::channel();
+    trigger.trigger();
+    timeout(Duration::from_secs(1), worker.run(shutdown))
+        .await
+        .expect("worker should exit once shutdown is triggered");
+}
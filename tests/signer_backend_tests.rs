@@ -0,0 +1,144 @@
+use alloy::{
+    consensus::{SignableTransaction, TxLegacy},
+    network::TxSigner,
+    primitives::{Address, B256, TxKind, U256},
+    signers::{SignerSync, local::PrivateKeySigner},
+};
+use axum::{Json, Router, routing::post};
+use backend::contract::signers::{
+    BackendTxSigner, LocalKeyBackend, RemoteSignRequest, RemoteSignResponse,
+    RemoteSignerBackend, RemoteSignerSettings, SignerBackend,
+};
+use std::{
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const ANVIL_PK1: &str =
+    "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+fn settings() -> RemoteSignerSettings {
+    RemoteSignerSettings {
+        timeout: Duration::from_secs(5),
+        connect_timeout: Duration::from_secs(1),
+    }
+}
+
+// 本地起一个最小的远程签名服务替身：POST /sign -> {"signature": "0x..."}
+async fn spawn_remote_signer(key: PrivateKeySigner) -> String {
+    let app = Router::new().route(
+        "/sign",
+        post(move |Json(req): Json<RemoteSignRequest>| {
+            let key = key.clone();
+            async move {
+                let sig = key.sign_hash_sync(&req.hash).unwrap();
+                Json(RemoteSignResponse {
+                    signature: format!("0x{}", hex::encode(sig.as_bytes())),
+                })
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_remote_backend_signs_via_http() {
+    let key = PrivateKeySigner::from_str(ANVIL_PK1).unwrap();
+    let url = spawn_remote_signer(key.clone()).await;
+    let remote =
+        RemoteSignerBackend::new(url, key.address(), &settings()).unwrap();
+    assert_eq!(remote.kind(), "remote");
+    assert!(remote.export_key().is_none());
+
+    let hash = B256::repeat_byte(0x11);
+    let sig = remote.sign_hash(&hash).await.unwrap();
+    assert_eq!(
+        sig.recover_address_from_prehash(&hash).unwrap(),
+        key.address()
+    );
+}
+
+#[tokio::test]
+async fn test_remote_backend_rejects_wrong_account() {
+    let key = PrivateKeySigner::from_str(ANVIL_PK1).unwrap();
+    let url = spawn_remote_signer(key).await;
+    // 声称的地址与服务端实际签名的密钥不一致：恢复出的地址校验应失败
+    let remote =
+        RemoteSignerBackend::new(url, Address::repeat_byte(0x22), &settings())
+            .unwrap();
+    assert!(remote.sign_hash(&B256::ZERO).await.is_err());
+}
+
+#[tokio::test]
+async fn test_tx_signer_matches_across_backends() {
+    let key = PrivateKeySigner::from_str(ANVIL_PK1).unwrap();
+    let url = spawn_remote_signer(key.clone()).await;
+    let local: Arc<dyn SignerBackend> =
+        Arc::new(LocalKeyBackend::from_private_key(ANVIL_PK1).unwrap());
+    let remote: Arc<dyn SignerBackend> = Arc::new(
+        RemoteSignerBackend::new(url, key.address(), &settings()).unwrap(),
+    );
+
+    let mut tx = TxLegacy {
+        chain_id: Some(31337),
+        nonce: 0,
+        gas_price: 1_000_000_000,
+        gas_limit: 21_000,
+        to: TxKind::Call(Address::repeat_byte(0x33)),
+        value: U256::from(1u64),
+        input: Default::default(),
+    };
+    let a = BackendTxSigner(local)
+        .sign_transaction(&mut tx)
+        .await
+        .unwrap();
+    let b = BackendTxSigner(remote)
+        .sign_transaction(&mut tx)
+        .await
+        .unwrap();
+    assert_eq!(a, b);
+    assert_eq!(
+        a.recover_address_from_prehash(&tx.signature_hash())
+            .unwrap(),
+        key.address()
+    );
+}
+
+#[tokio::test]
+async fn test_remote_backend_times_out_on_hung_signer() {
+    // 接受连接但迟迟不响应的签名服务：请求应在配置的超时内失败
+    let app = Router::new().route(
+        "/sign",
+        post(|| async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            "{}"
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    let remote = RemoteSignerBackend::new(
+        format!("http://{addr}"),
+        Address::repeat_byte(0x22),
+        &RemoteSignerSettings {
+            timeout: Duration::from_millis(300),
+            connect_timeout: Duration::from_secs(1),
+        },
+    )
+    .unwrap();
+    let started = Instant::now();
+    let err = remote.sign_hash(&B256::ZERO).await.unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(5), "{err:?}");
+    let timed_out = err
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(reqwest::Error::is_timeout);
+    assert!(timed_out, "{err:?}");
+}
@@ -0,0 +1,116 @@
+use backend::{
+    api::{schema::Validate, webhooks::CreateWebhookReq},
+    repo::webhook_repo::{
+        DeliveryCursor, DeliveryFilter, DeliveryStatus, build_delivery_list,
+    },
+    webhook::{
+        WebhookEventType, hmac_sha256_hex, retry_delay, signature_header,
+    },
+};
+use std::{str::FromStr, time::Duration};
+
+#[test]
+fn hmac_matches_rfc4231_vector() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    let header = signature_header("secret", 1_700_000_000, b"{}");
+    let (t, v1) = header.split_once(',').unwrap();
+    assert_eq!(t, "t=1700000000");
+    assert_eq!(
+        v1.strip_prefix("v1=").unwrap(),
+        hmac_sha256_hex(b"secret", b"1700000000.{}")
+    );
+}
+
+#[test]
+fn retry_delay_is_exponential_and_capped() {
+    let base = Duration::from_secs(5);
+    let max = Duration::from_secs(3600);
+    assert_eq!(retry_delay(1, base, max), Duration::from_secs(5));
+    assert_eq!(retry_delay(2, base, max), Duration::from_secs(10));
+    assert_eq!(retry_delay(4, base, max), Duration::from_secs(40));
+    assert_eq!(retry_delay(20, base, max), max);
+    assert_eq!(retry_delay(u32::MAX, base, max), max);
+}
+
+#[test]
+fn event_types_parse_and_validate() {
+    for t in WebhookEventType::ALL {
+        assert_eq!(WebhookEventType::from_str(t.as_str()).unwrap(), t);
+    }
+    assert!(WebhookEventType::from_str("show_deleted").is_err());
+
+    let parse = |s: &str| {
+        serde_json::from_str::<CreateWebhookReq>(s)
+            .unwrap()
+            .validate()
+    };
+    let req = parse(
+        r#"{"url":" https://example.com/hook ","event_types":["ticket_sold","show_cancelled","ticket_sold"]}"#,
+    )
+    .unwrap();
+    assert_eq!(req.url, "https://example.com/hook");
+    assert_eq!(
+        req.event_types,
+        vec![
+            WebhookEventType::ShowCancelled,
+            WebhookEventType::TicketSold
+        ]
+    );
+    assert!(req.secret.is_none());
+
+    assert!(parse(r#"{"url":"ftp://example.com"}"#).is_err());
+    assert!(
+        parse(r#"{"url":"https://example.com","secret":"short"}"#).is_err()
+    );
+    assert!(
+        serde_json::from_str::<CreateWebhookReq>(
+            r#"{"url":"https://example.com","event_types":["nope"]}"#
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn build_delivery_list_uses_keyset() {
+    let filter = DeliveryFilter {
+        status: Some(DeliveryStatus::Failed),
+    };
+    let qb =
+        build_delivery_list(7, &filter, Some(&DeliveryCursor { id: 50 }), 21);
+    let sql = qb.sql();
+    assert!(sql.contains("WHERE webhook_id = $1"));
+    assert!(sql.contains("AND status = $2"));
+    assert!(sql.contains("AND id < $3"));
+    assert!(sql.contains("ORDER BY id DESC LIMIT $4"));
+}
+
+#[tokio::test]
+async fn webhook_targets_must_be_public() {
+    use backend::webhook::target::{check_target, is_public_ip};
+    for ip in [
+        "127.0.0.1",
+        "10.1.2.3",
+        "172.16.0.1",
+        "192.168.1.1",
+        "169.254.169.254",
+        "100.64.0.1",
+        "0.0.0.0",
+        "::1",
+        "fe80::1",
+        "fd00::1",
+        "::ffff:127.0.0.1",
+    ] {
+        assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+    }
+    assert!(is_public_ip("93.184.216.34".parse().unwrap()));
+    assert!(is_public_ip("2606:4700::1111".parse().unwrap()));
+
+    assert!(check_target("http://169.254.169.254/latest").await.is_err());
+    assert!(check_target("https://[::1]:8443/hook").await.is_err());
+    assert!(check_target("http://localhost:9000/hook").await.is_err());
+    assert!(check_target("https://93.184.216.34/hook").await.is_ok());
+}